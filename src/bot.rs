@@ -3,60 +3,188 @@
 //! This module handles the setup and execution of the Discord bot,
 //! including command registration and framework initialization.
 
-use crate::types::Data;
-use crate::commands::{ping, uuid, online, backup};
+use crate::types::{Data, Error};
+use crate::commands::{ping, uuid, online, backup, backups, sync, lookup, layer, settings, setserver, dbcheck, resolve, playercount, servers, history, draw, skin, seen, serverinfo, link, whoami, list, playtime, stats, importstats};
 use crate::database;
 use crate::config::Config;
+use crate::mojang::{MojangCache, MojangClient, PLAYERDB_API_BASE};
+use crate::status_poller::{spawn_status_poller, PollTargets};
+use crate::shutdown::{wait_for_signal, InFlight, SHUTDOWN_GRACE_PERIOD};
+use crate::reload::spawn_reload_listener;
+use crate::types::{LiveSettings, LiveSettingsHandle};
+use crate::utils::Cooldowns;
+use crate::http_server::{spawn_http_server, RateLimiter};
+use crate::metrics::METRICS;
 use poise::serenity_prelude as serenity;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::net::TcpListener;
+use tokio::sync::{watch, RwLock};
 use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// User-agent sent with every outgoing HTTP request; some hosts reject requests without one.
+pub const HTTP_USER_AGENT: &str = concat!("OxideVault/", env!("CARGO_PKG_VERSION"));
+
+/// Maximum time to establish a connection to an HTTP API.
+pub const HTTP_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Maximum time for a whole HTTP request, from connecting to reading the body.
+pub const HTTP_REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// How long an idle pooled connection is kept open for reuse.
+pub const HTTP_POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(60);
 
 /// Run the Discord bot.
 ///
 /// This function initializes the bot with configuration from environment variables,
 /// sets up the database, and starts the Discord client.
 ///
+/// On Ctrl+C or SIGTERM the gateway connections are closed, running commands
+/// and background tasks get [`SHUTDOWN_GRACE_PERIOD`] to finish, and the
+/// database is checkpointed before returning `Ok(())`.
+///
 /// # Errors
 ///
 /// Returns an error if configuration is invalid, database initialization fails,
 /// or the Discord client cannot be started.
 pub async fn run() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    // Load configuration from environment
-    let config = Config::from_env()?;
+    // Load configuration from the environment (and OXIDEVAULT_CONFIG file, if set)
+    let config = Config::load()?;
 
     // Initialize database
     database::init_db(&config.db_path).await?;
 
     // Create HTTP client for API requests (reused across requests for better performance)
-    let http_client = reqwest::Client::new();
+    let http_client = build_http_client()?;
 
     let intents = serenity::GatewayIntents::non_privileged();
 
+    let (commands, unknown) = filter_commands(
+        vec![ping(), uuid(), online(), backup(), backups(), sync(), lookup(), layer(), settings(), setserver(), dbcheck(), resolve(), playercount(), servers(), history(), draw(), skin(), seen(), serverinfo(), link(), whoami(), list(), playtime(), stats(), importstats()],
+        &config.disabled_commands,
+    );
+    for name in unknown {
+        tracing::warn!(command = %name, "DISABLED_COMMANDS contains an unknown command");
+    }
+
+    // Signals background tasks to stop once the client shuts down
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+
+    // Opt-in HTTP server for /health and /metrics
+    if let Some(address) = config.http_bind_address {
+        let listener = TcpListener::bind(address).await?;
+        tracing::info!(%address, "HTTP server listening");
+        spawn_http_server(
+            listener,
+            RateLimiter::new(config.http_rate_limit_burst, config.http_rate_limit_per_sec)
+                .exempt_loopback(config.http_rate_limit_exempt_loopback),
+            shutdown_rx.clone(),
+        );
+    }
+
+    // Settings swapped in place when the configuration is reloaded on SIGHUP
+    let live_settings = Arc::new(LiveSettingsHandle::new(LiveSettings::from_config(&config)));
+    spawn_reload_listener(live_settings.clone(), config.clone(), shutdown_rx.clone());
+
+    // Shared with Data so shutdown can checkpoint the pool and wait for commands
+    let players = database::PlayerRepository::with_pool_timeout(config.db_path.clone(), config.db_pool_timeout);
+    let in_flight = Arc::new(InFlight::default());
+    let setup_players = players.clone();
+    let setup_in_flight = in_flight.clone();
+
+    // Samples recorded before history was keyed by server came from the default server
+    match players.status_history().claim_unkeyed_samples(&config.mc_server_address).await {
+        Ok(0) => {}
+        Ok(claimed) => tracing::info!(claimed, server = %config.mc_server_address, "Attributed earlier player count samples"),
+        Err(e) => tracing::warn!(error = %e, "Failed to attribute earlier player count samples"),
+    }
+
+    // Report the pool's connection counts at /metrics
+    let metrics_players = players.clone();
+    METRICS.set_db_pool_source(move || metrics_players.pool_stats());
+
     let framework = poise::Framework::builder()
         .options(poise::FrameworkOptions {
-            commands: vec![ping(), uuid(), online(), backup()],
+            commands,
+            pre_command: |context| Box::pin(async move {
+                METRICS.record_command(&context.command().qualified_name);
+                context.data().in_flight.start()
+            }),
+            post_command: |context| Box::pin(async move { context.data().in_flight.finish() }),
+            on_error: |error| Box::pin(on_error(error)),
             ..Default::default()
         })
-        .setup(move |context, _ready, framework| {
-            let db_path = config.db_path.clone();
+        .setup(move |context, ready, framework| {
+            tracing::info!(user = %ready.user.name, guilds = ready.guilds.len(), "Connected to Discord");
+            let players = setup_players;
+            let guilds = players.guilds();
+            let status_history = players.status_history();
+            let in_flight = setup_in_flight;
+            let live_settings = live_settings.clone();
             let http_client = http_client.clone();
-            let mc_server_address = config.mc_server_address.clone();
-            let backup_folder = config.backup_folder.clone();
-            let backup_publish_root = config.backup_publish_root.clone();
-            let backup_public_base_url = config.backup_public_base_url.clone();
+            let mc_socks5_proxy = config.mc_socks5_proxy.clone();
+            let pl3xmap_markers_path = config.pl3xmap_markers_path.clone().map(PathBuf::from);
+            let mc_query_enabled = config.mc_query_enabled;
+            let admin_user_ids = config.admin_user_ids.clone();
+            let max_player_sample = config.max_player_sample;
+            let ping_log_path = config.ping_log_path.clone();
+            let ping_timeout = config.ping_timeout;
+            let ping_retries = config.ping_retries;
+            let mojang_cache_ttl = config.mojang_cache_ttl;
+            let minecraft_stats_dir = config.minecraft_stats_dir.clone().map(PathBuf::from);
+            let status_poll_interval = config.status_poll_interval;
+            let shutdown_rx = shutdown_rx.clone();
+            let mut mojang_client = MojangClient::new(http_client)
+                .with_api_base(config.mojang_api_url.clone())
+                .with_session_base(config.mojang_session_url.clone());
+            if config.playerdb_fallback {
+                mojang_client = mojang_client.with_fallback_base(PLAYERDB_API_BASE);
+            }
             Box::pin(async move {
                 poise::builtins::register_globally(context, &framework.options().commands).await?;
-                Ok(Data {
-                    db_path,
-                    http_client,
-                    mc_server_address,
-                    backup_folder,
-                    last_backup_time: Arc::new(RwLock::new(HashMap::new())),
-                    last_global_backup_time: Arc::new(RwLock::new(None)),
-                    backup_publish_root,
-                    backup_public_base_url,
-                })
+                let data = Data {
+                    live_settings,
+                    mc_socks5_proxy,
+                    mc_query_enabled,
+                    backup_cooldowns: Arc::new(Cooldowns::new()),
+                    uuid_cooldowns: Arc::new(Cooldowns::new()),
+                    pl3xmap_markers_path,
+                    admin_user_ids,
+                    max_player_sample,
+                    ping_log_path,
+                    ping_timeout,
+                    ping_retries,
+                    players,
+                    guilds,
+                    status_history,
+                    mojang_client,
+                    mojang_cache: Arc::new(MojangCache::new(mojang_cache_ttl)),
+                    minecraft_stats_dir,
+                    in_flight,
+                    last_known_statuses: Arc::new(RwLock::new(HashMap::new())),
+                };
+
+                // Record the server's player count periodically for /playercount,
+                // following MC_SERVER_ADDRESS when the configuration is reloaded
+                let targets = PollTargets {
+                    history: data.status_history_repository(),
+                    players: data.player_repository(),
+                    last_known: data.last_known_statuses.clone(),
+                };
+                let live_settings = data.live_settings.clone();
+                let poll_source = data.status_source_at(data.live().mc_server_address.clone());
+                spawn_status_poller(
+                    move || {
+                        let address = live_settings.current().mc_server_address.clone();
+                        (address.clone(), poll_source.clone().with_address(address))
+                    },
+                    status_poll_interval,
+                    targets,
+                    shutdown_rx,
+                );
+
+                Ok(data)
             })
         })
         .build();
@@ -65,7 +193,125 @@ pub async fn run() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         .framework(framework)
         .await?;
 
-    client.start().await?;
+    // Close the gateway connections on Ctrl+C / SIGTERM, which makes start() return
+    let shard_manager = client.shard_manager.clone();
+    tokio::spawn(async move {
+        wait_for_signal().await;
+        tracing::info!("Shutdown requested, closing Discord connections");
+        shard_manager.shutdown_all().await;
+    });
+
+    let result = client.start().await;
 
+    // Stop background tasks and let running commands finish
+    let _ = shutdown_tx.send(true);
+    if tokio::time::timeout(SHUTDOWN_GRACE_PERIOD, shutdown_tx.closed()).await.is_err() {
+        tracing::warn!("Background tasks did not stop in time");
+    }
+    if !in_flight.wait_idle(SHUTDOWN_GRACE_PERIOD).await {
+        tracing::warn!(running = in_flight.count(), "Commands still running at shutdown");
+    }
+
+    drop(client);
+    if let Err(e) = players.checkpoint().await {
+        tracing::warn!(error = %e, "Failed to checkpoint the database");
+    }
+
+    result?;
     Ok(())
 }
+
+/// Handle framework errors, first marking failed commands as finished.
+///
+/// These are the errors raised after `pre_command` ran, for which
+/// `post_command` is skipped.
+async fn on_error(error: poise::FrameworkError<'_, Data, Error>) {
+    match &error {
+        poise::FrameworkError::Command { error, ctx, .. } => {
+            tracing::error!(
+                command = %ctx.command().qualified_name,
+                user = %ctx.author().name,
+                error = %error,
+                "Command failed"
+            );
+            ctx.data().in_flight.finish();
+        }
+        poise::FrameworkError::CommandPanic { ctx, .. }
+        | poise::FrameworkError::ArgumentParse { ctx, .. } => ctx.data().in_flight.finish(),
+        poise::FrameworkError::CommandStructureMismatch { ctx, .. } => ctx.data().in_flight.finish(),
+        _ => {}
+    }
+
+    if let Err(e) = poise::builtins::on_error(error).await {
+        tracing::error!(error = %e, "Error while handling error");
+    }
+}
+
+/// Build the HTTP client shared by all commands.
+///
+/// Sets the [`HTTP_USER_AGENT`], connect and request timeouts, and an idle
+/// timeout for pooled connections.
+fn build_http_client() -> reqwest::Result<reqwest::Client> {
+    reqwest::Client::builder()
+        .user_agent(HTTP_USER_AGENT)
+        .connect_timeout(HTTP_CONNECT_TIMEOUT)
+        .timeout(HTTP_REQUEST_TIMEOUT)
+        .pool_idle_timeout(HTTP_POOL_IDLE_TIMEOUT)
+        .build()
+}
+
+/// Remove disabled commands from the list of commands to register.
+///
+/// Returns the remaining commands along with any disabled names that don't match
+/// a known command, so they can be reported to the operator.
+fn filter_commands(
+    commands: Vec<poise::Command<Data, Error>>,
+    disabled: &[String],
+) -> (Vec<poise::Command<Data, Error>>, Vec<String>) {
+    let unknown = disabled
+        .iter()
+        .filter(|name| !commands.iter().any(|command| command.name == **name))
+        .cloned()
+        .collect();
+
+    let enabled = commands
+        .into_iter()
+        .filter(|command| !disabled.contains(&command.name))
+        .collect();
+
+    (enabled, unknown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(commands: &[poise::Command<Data, Error>]) -> Vec<&str> {
+        commands.iter().map(|command| command.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_filter_commands() {
+        let all = || vec![ping(), uuid(), online(), backup()];
+
+        let (enabled, unknown) = filter_commands(all(), &[]);
+        assert_eq!(names(&enabled), vec!["ping", "uuid", "online", "backup"]);
+        assert!(unknown.is_empty());
+
+        let disabled = vec!["backup".to_string(), "uuid".to_string()];
+        let (enabled, unknown) = filter_commands(all(), &disabled);
+        assert_eq!(names(&enabled), vec!["ping", "online"]);
+        assert!(unknown.is_empty());
+
+        let disabled = vec!["draw".to_string(), "online".to_string()];
+        let (enabled, unknown) = filter_commands(all(), &disabled);
+        assert_eq!(names(&enabled), vec!["ping", "uuid", "backup"]);
+        assert_eq!(unknown, vec!["draw"]);
+    }
+
+    #[test]
+    fn test_http_user_agent() {
+        assert_eq!(HTTP_USER_AGENT, format!("OxideVault/{}", env!("CARGO_PKG_VERSION")));
+        assert!(build_http_client().is_ok());
+    }
+}
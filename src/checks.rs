@@ -0,0 +1,69 @@
+//! Command checks shared across commands.
+//!
+//! Checks run before a command executes and decide whether the invoking user
+//! may use it.
+
+use crate::types::{Context, Error};
+
+/// Message shown to users who are not allowed to run an admin command.
+const ADMIN_DENIED_MESSAGE: &str = "🚫 This command is restricted to administrators.";
+
+/// Decide whether a user has admin access.
+///
+/// A user is an admin if they hold the Discord ADMINISTRATOR permission in the
+/// current guild or if their ID is listed in the `ADMIN_USER_IDS` allowlist.
+pub fn is_admin(has_administrator: bool, user_id: u64, admin_user_ids: &[u64]) -> bool {
+    has_administrator || admin_user_ids.contains(&user_id)
+}
+
+/// Poise check restricting a command to administrators.
+///
+/// Combines the ADMINISTRATOR permission with the `ADMIN_USER_IDS` allowlist and
+/// replies with a uniform denial message when the check fails.
+pub async fn is_admin_check(context: Context<'_>) -> Result<bool, Error> {
+    let has_administrator = context
+        .author_member()
+        .await
+        .and_then(|member| member.permissions)
+        .is_some_and(|permissions| permissions.administrator());
+
+    let allowed = is_admin(
+        has_administrator,
+        context.author().id.get(),
+        &context.data().admin_user_ids,
+    );
+
+    if !allowed {
+        context
+            .send(
+                poise::CreateReply::default()
+                    .content(ADMIN_DENIED_MESSAGE)
+                    .ephemeral(true),
+            )
+            .await?;
+    }
+
+    Ok(allowed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_admin() {
+        let allowlist = [111, 222];
+
+        // Administrator permission alone is enough
+        assert!(is_admin(true, 999, &[]));
+        assert!(is_admin(true, 999, &allowlist));
+
+        // Allowlisted users don't need the permission
+        assert!(is_admin(false, 111, &allowlist));
+        assert!(is_admin(true, 222, &allowlist));
+
+        // Everyone else is denied
+        assert!(!is_admin(false, 999, &allowlist));
+        assert!(!is_admin(false, 111, &[]));
+    }
+}
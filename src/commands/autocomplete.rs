@@ -0,0 +1,19 @@
+//! Autocomplete callbacks shared by several commands.
+
+use crate::types::Context;
+
+/// Maximum number of suggestions Discord accepts.
+const MAX_SUGGESTIONS: usize = 25;
+
+/// Suggest known usernames containing the partial input.
+///
+/// Lookup errors produce no suggestions rather than failing the interaction.
+pub async fn autocomplete_username(context: Context<'_>, partial: &str) -> Vec<String> {
+    context
+        .data()
+        .player_repository()
+        .search_by_username(partial.trim(), MAX_SUGGESTIONS)
+        .await
+        .map(|players| players.into_iter().map(|player| player.username).collect())
+        .unwrap_or_default()
+}
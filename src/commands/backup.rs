@@ -3,142 +3,438 @@
 //! Publishes the most recent backup via an HTTPS link (served by your reverse proxy).
 //! Avoids Discord file size limits by sharing a downloadable URL instead of attachments.
 
-use crate::types::{Context, Error};
+use crate::types::{Context, Data, Error, ResponseMessage};
+use crate::checks::is_admin_check;
+use crate::utils::{format_duration, format_relative, Granularity};
 use rand::Rng;
-use std::fs;
-use std::path::PathBuf;
-use std::time::{Duration, Instant};
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tokio::sync::watch;
 
 const ALPHANUMERIC: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
 
+/// Length of the random token directory a backup is published under.
+const TOKEN_LENGTH: usize = 12;
+
+/// Number of tokens tried before giving up on finding a free token directory.
+const TOKEN_ATTEMPTS: usize = 5;
+
+/// Size of the buffer used when streaming backup files (64 KiB).
+const COPY_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Bytes copied between progress reports when a backup has to be copied (16 MiB).
+const PROGRESS_INTERVAL: u64 = 16 * 1024 * 1024;
+
 /// Publish the most recent backup file and provide a download link.
 ///
 /// The backup is published under a tokenized path served by your reverse proxy.
 /// This approach avoids external size limits and keeps transfers on your own infrastructure.
 ///
 /// Publishing is restricted to administrators to prevent unauthorized access to backups.
-#[poise::command(slash_command, default_member_permissions = "ADMINISTRATOR")]
-pub async fn backup(context: Context<'_>) -> Result<(), Error> {
-    // Global rate limiting: 2 hours cooldown between all publishes
-    const GLOBAL_COOLDOWN: Duration = Duration::from_secs(2 * 60 * 60);
-
-    // Per-user rate limiting: 1 day cooldown between publishes by the same user
-    const COOLDOWN_DURATION: Duration = Duration::from_secs(24 * 60 * 60);
-
-    let user_id = context.author().id.get();
-    let mut last_backup_map = context.data().last_backup_time.write().await;
-
-    if let Some(last_time) = last_backup_map.get(&user_id) {
-        let elapsed = last_time.elapsed();
-        if elapsed < COOLDOWN_DURATION {
-            let remaining = COOLDOWN_DURATION - elapsed;
-            let hours = remaining.as_secs() / 3600;
-            let minutes = (remaining.as_secs() % 3600) / 60;
-
-            context
-                .say(format!(
-                    "⏳ Backup command is on cooldown. Please wait {} hour{} and {} minute{}.",
-                    hours,
-                    if hours == 1 { "" } else { "s" },
-                    minutes,
-                    if minutes == 1 { "" } else { "s" }
-                ))
+/// Pass `dry_run` to see which backup would be published without publishing it.
+#[poise::command(slash_command, check = "is_admin_check")]
+pub async fn backup(
+    context: Context<'_>,
+    #[description = "Show what would be published without publishing it"]
+    dry_run: Option<bool>,
+) -> Result<(), Error> {
+    // Defer reply since processing might take a while
+    context.defer().await?;
+
+    // Copy progress is shown in a reply that is edited as the copy advances,
+    // then replaced by the final response
+    let (progress_tx, mut progress_rx) = watch::channel(0u8);
+    let guild_id = context.guild_id().map(|id| id.get());
+    let response = backup_response(
+        context.data(),
+        guild_id,
+        context.author().id.get(),
+        dry_run.unwrap_or(false),
+        progress_tx,
+    );
+    tokio::pin!(response);
+
+    let mut progress_reply = None;
+    let response = loop {
+        tokio::select! {
+            response = &mut response => break response?,
+            Ok(()) = progress_rx.changed() => {
+                let content = format!("📦 Copying… {}%", *progress_rx.borrow_and_update());
+                match &progress_reply {
+                    None => progress_reply = Some(context.say(content).await?),
+                    Some(reply) => reply.edit(context, poise::CreateReply::default().content(content)).await?,
+                }
+            }
+        }
+    };
+
+    match progress_reply {
+        Some(reply) => {
+            reply
+                .edit(context, poise::CreateReply::default().content(response.content()))
                 .await?;
-            return Ok(());
+            Ok(())
         }
+        None => response.send(context).await,
     }
+}
 
-    // Check global cooldown again after acquiring write lock to prevent race condition
-    let mut global_backup_time = context.data().last_global_backup_time.write().await;
-    if let Some(last_time) = *global_backup_time {
-        let elapsed = last_time.elapsed();
-        if elapsed < GLOBAL_COOLDOWN {
-            let remaining = GLOBAL_COOLDOWN - elapsed;
-            let hours = remaining.as_secs() / 3600;
-            let minutes = (remaining.as_secs() % 3600) / 60;
-
-            context
-                .say(format!(
-                    "⏳ Backup command is globally on cooldown. Please wait {} hour{} and {} minute{}.",
-                    hours,
-                    if hours == 1 { "" } else { "s" },
-                    minutes,
-                    if minutes == 1 { "" } else { "s" }
-                ))
-                .await?;
-            return Ok(());
+/// List the backup links that are currently published for this guild.
+///
+/// Restricted to administrators like `/backup`.
+#[poise::command(slash_command, check = "is_admin_check")]
+pub async fn backups(context: Context<'_>) -> Result<(), Error> {
+    let settings = context.data().live();
+    let (publish_root, base_url) = guild_publish_location(
+        &settings.backup_publish_root,
+        &settings.backup_public_base_url,
+        context.guild_id().map(|id| id.get()),
+    );
+
+    let links = tokio::task::spawn_blocking(move || list_published_links(Path::new(&publish_root))).await?;
+    let links = match links {
+        Ok(links) => links,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => {
+            return ResponseMessage::Failure(format!("❌ Failed to read published backups: {}", e))
+                .send(context)
+                .await;
+        }
+    };
+
+    let pages = published_link_pages(&links, &base_url, SystemTime::now());
+    match pages.as_slice() {
+        [] => {
+            context.say("📭 No backup links are currently published.").await?;
+        }
+        [page] => {
+            context.say(page).await?;
+        }
+        pages => {
+            let pages: Vec<&str> = pages.iter().map(String::as_str).collect();
+            poise::builtins::paginate(context, &pages).await?;
         }
     }
 
-    // Update last backup time (both global and per-user)
-    let now = Instant::now();
-    last_backup_map.insert(user_id, now);
-    *global_backup_time = Some(now);
-    drop(last_backup_map);
-    drop(global_backup_time);
+    Ok(())
+}
 
-    // Defer reply since processing might take a while
-    context.defer().await?;
+/// Build the `/backup` reply for the given user, enforcing cooldowns.
+///
+/// The backup is taken from the guild's backup folder (see [`Data::backup_folder_for`]).
+///
+/// A dry run only locates the backup and reports what would be published; it
+/// neither publishes anything nor consumes the cooldown. If the backup has to be
+/// copied rather than hard-linked, the percentage copied is sent on `progress`.
+pub async fn backup_response(
+    data: &Data,
+    guild_id: Option<u64>,
+    user_id: u64,
+    dry_run: bool,
+    progress: watch::Sender<u8>,
+) -> Result<ResponseMessage, Error> {
+    let settings = data.live();
+
+    // Global and per-user rate limiting between publishes (configurable)
+    if !dry_run {
+        if let Err(remaining) = data.backup_cooldowns.check_and_update(
+            user_id,
+            settings.backup_user_cooldown,
+            settings.backup_global_cooldown,
+        ) {
+            return Ok(ResponseMessage::Failure(format!(
+                "⏳ Backup command is on cooldown. Please wait {}.",
+                format_cooldown(remaining)
+            )));
+        }
+    }
 
     // Get backup and publish settings
-    let backup_folder = context.data().backup_folder.clone();
-    let publish_root = context.data().backup_publish_root.clone();
-    let publish_base_url = context.data().backup_public_base_url.clone();
-
-    // Find the most recent backup file
-    let backup_file = tokio::task::spawn_blocking(move || find_most_recent_backup(&backup_folder))
+    let backup_folder = data.backup_folder_for(guild_id).await;
+    let backup_extensions = settings.backup_extensions.clone();
+    let (publish_root, publish_base_url) = guild_publish_location(
+        &settings.backup_publish_root,
+        &settings.backup_public_base_url,
+        guild_id,
+    );
+    let link_ttl = settings.backup_link_ttl;
+
+    // Find the most recent backup file and its size
+    let candidate = tokio::task::spawn_blocking(move || locate_backup(&backup_folder, &backup_extensions))
         .await?;
 
-    let file_path = match backup_file {
-        Some(p) => p,
-        None => {
-            context
-                .say("❌ No backup found. The backup folder may not exist, is not accessible, or contains no files. Please check your BACKUP_FOLDER configuration.")
-                .await?;
-            return Ok(());
-        }
+    let Some(candidate) = candidate else {
+        return Ok(ResponseMessage::Failure(
+            "❌ No backup found. The backup folder may not exist, is not accessible, or contains no backup files. Please check your BACKUP_FOLDER and BACKUP_EXTENSIONS configuration.".to_string()
+        ));
     };
 
-    let file_name = file_path
-        .file_name()
-        .and_then(|n| n.to_str())
-        .unwrap_or("backup")
-        .to_string();
+    if dry_run {
+        return Ok(ResponseMessage::Success(dry_run_message(&candidate, &publish_base_url)));
+    }
 
-    // Publish backup: create tokenized link (hard-link or copy for portability)
+    let BackupCandidate { path: file_path, file_name, size_bytes } = candidate;
+
+    // Publish backup: create tokenized link (hard-link or copy for portability),
+    // then remove links that have expired
     let publish_result = tokio::task::spawn_blocking(move || {
-        publish_backup(&file_path, &publish_root, &publish_base_url)
+        let on_progress = |copied: u64| {
+            let percent = (copied.saturating_mul(100) / size_bytes.max(1)).min(100) as u8;
+            progress.send_if_modified(|current| std::mem::replace(current, percent) != percent);
+        };
+        let published = publish_backup(&file_path, &publish_root, &publish_base_url, on_progress)?;
+        tracing::info!(path = %published.local_path.display(), url = %published.url, "Published backup");
+        if let Err(e) = remove_expired_links(Path::new(&publish_root), link_ttl) {
+            tracing::warn!(publish_root = %publish_root, error = %e, "Failed to clean up expired backup links");
+        }
+        Ok::<_, Error>(published)
     })
     .await??;
 
     let size_mb = publish_result.size_bytes as f64 / (1024.0 * 1024.0);
 
-    context
-        .say(format!(
-            "📦 Backup ready for download: **{}** ({:.2} MB)\n\
-            🔗 Link: {}",
-            file_name, size_mb, publish_result.url
-        ))
-        .await?;
+    Ok(ResponseMessage::Success(format!(
+        "📦 Backup ready for download: **{}** ({:.2} MB)\n\
+        🔗 Link: {}\n\
+        🔒 SHA-256: `{}`",
+        file_name, size_mb, publish_result.url, publish_result.sha256
+    )))
+}
 
-    Ok(())
+/// Format the remaining cooldown, rounded up so the wait is never understated.
+///
+/// Waits under a minute are shown in seconds, longer ones in whole minutes.
+fn format_cooldown(remaining: Duration) -> String {
+    let secs = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+    if secs < 60 {
+        format_duration(Duration::from_secs(secs), Granularity::Seconds)
+    } else {
+        format_duration(Duration::from_secs(secs.div_ceil(60) * 60), Granularity::Minutes)
+    }
+}
+
+/// Publish directory and public base URL for a guild's backups.
+///
+/// Each guild publishes under a subdirectory named after its ID, so `/backups`
+/// only lists the calling guild's links. Outside of a guild the publish root
+/// itself is used.
+fn guild_publish_location(publish_root: &str, base_url: &str, guild_id: Option<u64>) -> (String, String) {
+    match guild_id {
+        Some(guild_id) => (
+            Path::new(publish_root).join(guild_id.to_string()).to_string_lossy().into_owned(),
+            format!("{}/{}", base_url.trim_end_matches('/'), guild_id),
+        ),
+        None => (publish_root.to_string(), base_url.to_string()),
+    }
+}
+
+/// Describe what publishing `candidate` would produce, for `/backup dry_run`.
+///
+/// The token is only generated when publishing, so the link shows a placeholder.
+fn dry_run_message(candidate: &BackupCandidate, base_url: &str) -> String {
+    format!(
+        "🧪 Dry run: would publish **{}** ({:.2} MB)\n\
+        🔗 Link: {}/<token>/{}\n\
+        Nothing was published and the cooldown was not used.",
+        candidate.file_name,
+        candidate.size_bytes as f64 / (1024.0 * 1024.0),
+        base_url.trim_end_matches('/'),
+        candidate.file_name
+    )
+}
+
+/// Whether a directory name looks like a generated publish token.
+fn is_token_dir_name(name: &str) -> bool {
+    name.len() == TOKEN_LENGTH && name.bytes().all(|b| b.is_ascii_alphanumeric())
+}
+
+/// A published token directory under the publish root.
+#[derive(Debug)]
+struct TokenDir {
+    path: PathBuf,
+    token: String,
+    /// Last modification time of the directory, i.e. when it was published
+    modified: SystemTime,
+}
+
+/// Scan `publish_root` for published token directories.
+///
+/// Only direct subdirectories named like generated tokens are returned;
+/// other files and symlinks are ignored. Entries that can't be inspected
+/// are skipped with a warning.
+fn scan_token_dirs(publish_root: &Path) -> std::io::Result<Vec<TokenDir>> {
+    let mut dirs = Vec::new();
+
+    for entry in fs::read_dir(publish_root)? {
+        let entry = entry?;
+        let Some(token) = entry.file_name().to_str().filter(|name| is_token_dir_name(name)).map(str::to_string) else {
+            continue;
+        };
+
+        // symlink_metadata so a symlink to a directory is never followed
+        let path = entry.path();
+        match fs::symlink_metadata(&path).and_then(|meta| Ok((meta.is_dir(), meta.modified()?))) {
+            Ok((true, modified)) => dirs.push(TokenDir { path, token, modified }),
+            Ok((false, _)) => {}
+            Err(e) => tracing::warn!(path = %path.display(), error = %e, "Failed to inspect published link"),
+        }
+    }
+
+    Ok(dirs)
+}
+
+/// Remove published token directories last modified more than `ttl` ago.
+///
+/// Only directories found by [`scan_token_dirs`] are considered; the root
+/// itself is left alone. Directories that can't be removed are skipped with
+/// a warning.
+///
+/// Returns the number of directories removed.
+fn remove_expired_links(publish_root: &Path, ttl: Duration) -> std::io::Result<usize> {
+    let now = SystemTime::now();
+    let mut removed = 0;
+
+    for dir in scan_token_dirs(publish_root)? {
+        if now.duration_since(dir.modified).unwrap_or_default() <= ttl {
+            continue;
+        }
+        match fs::remove_dir_all(&dir.path) {
+            Ok(()) => removed += 1,
+            Err(e) => tracing::warn!(path = %dir.path.display(), error = %e, "Failed to remove expired backup link"),
+        }
+    }
+
+    Ok(removed)
+}
+
+/// A backup currently published under a token directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PublishedLink {
+    token: String,
+    file_name: String,
+    size_bytes: u64,
+    published_at: SystemTime,
+}
+
+impl PublishedLink {
+    /// Public URL of the backup under `base_url`.
+    fn url(&self, base_url: &str) -> String {
+        format!("{}/{}/{}", base_url.trim_end_matches('/'), self.token, self.file_name)
+    }
+}
+
+/// List published backups, newest first.
+///
+/// Each token directory holds a single published file; empty directories
+/// are skipped.
+fn list_published_links(publish_root: &Path) -> std::io::Result<Vec<PublishedLink>> {
+    let mut links = Vec::new();
+
+    for dir in scan_token_dirs(publish_root)? {
+        let file = fs::read_dir(&dir.path).and_then(|entries| {
+            for entry in entries {
+                let entry = entry?;
+                let meta = entry.metadata()?;
+                if meta.is_file() {
+                    return Ok(Some((entry.file_name().to_string_lossy().into_owned(), meta.len())));
+                }
+            }
+            Ok(None)
+        });
+
+        match file {
+            Ok(Some((file_name, size_bytes))) => links.push(PublishedLink {
+                token: dir.token,
+                file_name,
+                size_bytes,
+                published_at: dir.modified,
+            }),
+            Ok(None) => {}
+            Err(e) => tracing::warn!(path = %dir.path.display(), error = %e, "Failed to read published link"),
+        }
+    }
+
+    links.sort_by_key(|link| std::cmp::Reverse(link.published_at));
+    Ok(links)
+}
+
+/// Number of published backups shown on each page.
+const LINKS_PER_PAGE: usize = 10;
+
+/// Split the published backups into pages for `/backups`.
+fn published_link_pages(links: &[PublishedLink], base_url: &str, now: SystemTime) -> Vec<String> {
+    let page_count = links.len().div_ceil(LINKS_PER_PAGE);
+
+    links
+        .chunks(LINKS_PER_PAGE)
+        .enumerate()
+        .map(|(index, chunk)| {
+            let entries: Vec<String> = chunk
+                .iter()
+                .map(|link| {
+                    let age = now.duration_since(link.published_at).unwrap_or_default();
+                    format!(
+                        "• **{}** ({:.2} MB), published {}\n  {}",
+                        link.file_name,
+                        link.size_bytes as f64 / (1024.0 * 1024.0),
+                        format_relative(age),
+                        link.url(base_url)
+                    )
+                })
+                .collect();
+            format!(
+                "📦 **Published backups:** {} (page {}/{})\n{}",
+                links.len(),
+                index + 1,
+                page_count,
+                entries.join("\n")
+            )
+        })
+        .collect()
+}
+
+/// Suffixes of files still being written, which are never published.
+const IN_PROGRESS_SUFFIXES: &[&str] = &[".tmp", ".part"];
+
+/// Whether a file name looks like a finished backup with one of `extensions`.
+///
+/// Extensions are lowercase without a leading dot and matched case-insensitively;
+/// an empty list accepts any extension. Hidden files and files with an
+/// in-progress suffix (e.g. `.tmp`) never match.
+fn is_backup_file_name(name: &str, extensions: &[String]) -> bool {
+    let name = name.to_lowercase();
+
+    if name.starts_with('.') || IN_PROGRESS_SUFFIXES.iter().any(|suffix| name.ends_with(suffix)) {
+        return false;
+    }
+
+    extensions.is_empty()
+        || extensions.iter().any(|ext| {
+            name.strip_suffix(ext.as_str())
+                .and_then(|stem| stem.strip_suffix('.'))
+                .is_some_and(|stem| !stem.is_empty())
+        })
 }
 
 /// Locate the most recent backup file in the specified directory.
 ///
-/// Returns the path to the most recently modified file by modification timestamp,
-/// or None if the directory is missing, inaccessible, or contains no files.
-fn find_most_recent_backup(backup_folder: &str) -> Option<PathBuf> {
+/// Only files accepted by [`is_backup_file_name`] are considered. Returns the path
+/// to the most recently modified one by modification timestamp, or None if the
+/// directory is missing, inaccessible, or contains no backup files.
+fn find_most_recent_backup(backup_folder: &str, extensions: &[String]) -> Option<PathBuf> {
     let path = PathBuf::from(backup_folder);
 
     // Check if the folder exists and is a directory
     if !path.exists() {
-        eprintln!("Backup folder does not exist: {}", backup_folder);
+        tracing::warn!(backup_folder, "Backup folder does not exist");
         return None;
     }
 
     if !path.is_dir() {
-        eprintln!("Backup folder path is not a directory: {}", backup_folder);
+        tracing::warn!(backup_folder, "Backup folder path is not a directory");
         return None;
     }
 
@@ -146,7 +442,7 @@ fn find_most_recent_backup(backup_folder: &str) -> Option<PathBuf> {
     let entries = match fs::read_dir(&path) {
         Ok(entries) => entries,
         Err(e) => {
-            eprintln!("Failed to read backup folder: {}", e);
+            tracing::warn!(backup_folder, error = %e, "Failed to read backup folder");
             return None;
         }
     };
@@ -162,6 +458,11 @@ fn find_most_recent_backup(backup_folder: &str) -> Option<PathBuf> {
             continue;
         }
 
+        // Skip stray files and backups still being written
+        if !is_backup_file_name(&entry.file_name().to_string_lossy(), extensions) {
+            continue;
+        }
+
         // Get modification time
         if let Ok(metadata) = entry.metadata() {
             if let Ok(modified) = metadata.modified() {
@@ -182,54 +483,80 @@ fn find_most_recent_backup(backup_folder: &str) -> Option<PathBuf> {
     most_recent.map(|(path, _)| path)
 }
 
+/// The backup that `/backup` would publish.
+#[derive(Debug)]
+struct BackupCandidate {
+    path: PathBuf,
+    file_name: String,
+    size_bytes: u64,
+}
+
+/// Locate the most recent backup and read its name and size.
+///
+/// Returns None if no backup is found or its metadata can't be read.
+fn locate_backup(backup_folder: &str, extensions: &[String]) -> Option<BackupCandidate> {
+    let path = find_most_recent_backup(backup_folder, extensions)?;
+
+    let size_bytes = match fs::metadata(&path) {
+        Ok(metadata) => metadata.len(),
+        Err(e) => {
+            tracing::warn!(path = %path.display(), error = %e, "Failed to read backup metadata");
+            return None;
+        }
+    };
+
+    let file_name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("backup")
+        .to_string();
+
+    Some(BackupCandidate { path, file_name, size_bytes })
+}
+
 struct PublishedBackup {
     url: String,
     local_path: PathBuf,
     size_bytes: u64,
+    /// Hex-encoded SHA-256 of the published file
+    sha256: String,
 }
 
 /// Publish a backup by creating a tokenized subdirectory and hard-linking (or copying) the file.
 /// Returns a PublishedBackup with the public URL and metadata.
+///
+/// When the file has to be copied, `on_progress` is called with the number of bytes
+/// copied so far as described in [`copy_streaming`]; a hard link never reports progress.
 fn publish_backup(
     file_path: &PathBuf,
     publish_root: &str,
     base_url: &str,
+    on_progress: impl FnMut(u64),
 ) -> Result<PublishedBackup, Box<dyn std::error::Error + Send + Sync>> {
     let file_name = file_path
         .file_name()
         .and_then(|n| n.to_str())
         .ok_or("Invalid file name")?;
 
-    // Generate a random 12-character token for obfuscation and easy revocation
-    let mut rng = rand::thread_rng();
-    let token: String = (0..12)
-        .map(|_| {
-            let idx = rng.gen_range(0..ALPHANUMERIC.len());
-            ALPHANUMERIC[idx] as char
-        })
-        .collect();
-
-    let target_dir = PathBuf::from(publish_root).join(&token);
-    fs::create_dir_all(&target_dir)?;
+    // Random 12-character token for obfuscation and easy revocation
+    let (token, target_dir) = create_token_dir(Path::new(publish_root), generate_token)?;
 
     let target_path = target_dir.join(file_name);
 
-    // Attempt hard-link for efficiency; fall back to copy if on different filesystems
-    match fs::hard_link(file_path, &target_path) {
-        Ok(_) => {}
+    // Attempt hard-link for efficiency; fall back to copy if on different filesystems.
+    // Either way the file is hashed in a single streaming pass.
+    let (size_bytes, sha256) = match fs::hard_link(file_path, &target_path) {
+        Ok(_) => sha256_file(&target_path)?,
         Err(e) => {
-            eprintln!(
-                "Warning: Failed to create hard link from '{}' to '{}': {}. Falling back to file copy.",
-                file_path.display(),
-                target_path.display(),
-                e
+            tracing::warn!(
+                source = %file_path.display(),
+                target = %target_path.display(),
+                error = %e,
+                "Failed to create hard link, falling back to file copy"
             );
-            fs::copy(file_path, &target_path)?;
+            copy_streaming(file_path, &target_path, PROGRESS_INTERVAL, on_progress)?
         }
-    }
-
-    let meta = fs::metadata(file_path)?;
-    let size_bytes = meta.len();
+    };
 
     let base = base_url.trim_end_matches('/');
     let url = format!("{}/{}/{}", base, token, file_name);
@@ -238,18 +565,138 @@ fn publish_backup(
         url,
         local_path: target_path,
         size_bytes,
+        sha256,
     })
 }
 
+/// Generate a random alphanumeric publish token.
+fn generate_token() -> String {
+    let mut rng = rand::rng();
+    (0..TOKEN_LENGTH)
+        .map(|_| {
+            let idx = rng.random_range(0..ALPHANUMERIC.len());
+            ALPHANUMERIC[idx] as char
+        })
+        .collect()
+}
+
+/// Create a new token directory under `publish_root`, returning the token and its path.
+///
+/// The directory is created with `create_dir`, which fails if it already exists,
+/// so an existing backup is never merged into. On a collision a new token is
+/// drawn from `next_token`, up to [`TOKEN_ATTEMPTS`] times in total.
+fn create_token_dir(
+    publish_root: &Path,
+    mut next_token: impl FnMut() -> String,
+) -> std::io::Result<(String, PathBuf)> {
+    fs::create_dir_all(publish_root)?;
+
+    for _ in 0..TOKEN_ATTEMPTS {
+        let token = next_token();
+        let target_dir = publish_root.join(&token);
+        match fs::create_dir(&target_dir) {
+            Ok(()) => return Ok((token, target_dir)),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                tracing::warn!(path = %target_dir.display(), "Publish token directory already exists, retrying");
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    Err(std::io::Error::new(
+        std::io::ErrorKind::AlreadyExists,
+        format!("Could not find a free publish token after {} attempts", TOKEN_ATTEMPTS),
+    ))
+}
+
+/// Read a file through a fixed-size buffer, passing each chunk to `on_chunk`.
+///
+/// Memory use stays constant regardless of file size. Returns the number of
+/// bytes read.
+fn stream_file(
+    source: &Path,
+    mut on_chunk: impl FnMut(&[u8]) -> std::io::Result<()>,
+) -> std::io::Result<u64> {
+    let mut reader = File::open(source)?;
+    let mut buffer = vec![0u8; COPY_BUFFER_SIZE];
+    let mut total = 0u64;
+
+    loop {
+        let read = match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        on_chunk(&buffer[..read])?;
+        total += read as u64;
+    }
+
+    Ok(total)
+}
+
+/// Hex-encode a digest.
+fn to_hex(digest: &[u8]) -> String {
+    digest.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Compute a file's size and hex SHA-256 in one streaming pass.
+fn sha256_file(path: &Path) -> std::io::Result<(u64, String)> {
+    let mut hasher = Sha256::new();
+    let size = stream_file(path, |chunk| {
+        hasher.update(chunk);
+        Ok(())
+    })?;
+    Ok((size, to_hex(&hasher.finalize())))
+}
+
+/// Copy a file through a fixed-size buffer, hashing it on the way.
+///
+/// `on_progress` is called with the number of bytes copied so far each time another
+/// `progress_interval` bytes have been copied, and once more when the copy completes.
+///
+/// Returns the number of bytes copied and the hex SHA-256 of the content.
+fn copy_streaming(
+    source: &Path,
+    destination: &Path,
+    progress_interval: u64,
+    mut on_progress: impl FnMut(u64),
+) -> std::io::Result<(u64, String)> {
+    let mut writer = File::create(destination)?;
+    let mut hasher = Sha256::new();
+    let mut written = 0u64;
+    let mut next_report = progress_interval;
+    let copied = stream_file(source, |chunk| {
+        hasher.update(chunk);
+        writer.write_all(chunk)?;
+        written += chunk.len() as u64;
+        if written >= next_report {
+            on_progress(written);
+            next_report = written.saturating_add(progress_interval.max(1));
+        }
+        Ok(())
+    })?;
+
+    writer.flush()?;
+    on_progress(copied);
+    Ok((copied, to_hex(&hasher.finalize())))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::DEFAULT_BACKUP_EXTENSIONS;
     use std::fs;
     use tempfile::TempDir;
 
+    /// The default backup extensions as configured when BACKUP_EXTENSIONS is unset.
+    fn default_extensions() -> Vec<String> {
+        DEFAULT_BACKUP_EXTENSIONS.iter().map(|ext| ext.to_string()).collect()
+    }
+
     /// Verify a backup file is located and matches the expected filename.
     fn assert_backup_found(temp_dir: &TempDir, expected_name: &str) {
-        let result = find_most_recent_backup(temp_dir.path().to_str().unwrap());
+        let result = find_most_recent_backup(temp_dir.path().to_str().unwrap(), &default_extensions());
         assert!(result.is_some());
         assert_eq!(result.unwrap().file_name().unwrap(), expected_name);
     }
@@ -270,13 +717,13 @@ mod tests {
     #[test]
     fn test_find_most_recent_backup_empty_folder() {
         let temp_dir = TempDir::new().unwrap();
-        let result = find_most_recent_backup(temp_dir.path().to_str().unwrap());
+        let result = find_most_recent_backup(temp_dir.path().to_str().unwrap(), &default_extensions());
         assert!(result.is_none());
     }
 
     #[test]
     fn test_find_most_recent_backup_nonexistent_folder() {
-        let result = find_most_recent_backup("/nonexistent/path/that/should/not/exist");
+        let result = find_most_recent_backup("/nonexistent/path/that/should/not/exist", &default_extensions());
         assert!(result.is_none());
     }
 
@@ -304,7 +751,7 @@ mod tests {
         let file2_path = temp_dir.path().join("backup2.tgz");
         fs::write(&file2_path, b"new data").unwrap();
 
-        let result = find_most_recent_backup(temp_dir.path().to_str().unwrap());
+        let result = find_most_recent_backup(temp_dir.path().to_str().unwrap(), &default_extensions());
         assert!(result.is_some());
         assert_eq!(result.unwrap().file_name().unwrap(), "backup2.tgz");
     }
@@ -324,12 +771,96 @@ mod tests {
         assert_backup_found(&temp_dir, "backup.tgz");
     }
 
+    #[test]
+    fn test_find_most_recent_backup_filters_extensions() {
+        let temp_dir = TempDir::new().unwrap();
+
+        fs::write(temp_dir.path().join("world.tgz"), b"old backup").unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(100));
+
+        // Newer files that aren't finished backups must not be picked
+        for name in ["backup.log", "world2.tgz.tmp", "world3.tar.gz.part", "notes.txt", ".hidden.zip", "tgz"] {
+            fs::write(temp_dir.path().join(name), b"not a backup").unwrap();
+        }
+
+        assert_backup_found(&temp_dir, "world.tgz");
+
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        fs::write(temp_dir.path().join("World4.TAR.GZ"), b"new backup").unwrap();
+        assert_backup_found(&temp_dir, "World4.TAR.GZ");
+    }
+
+    #[test]
+    fn test_find_most_recent_backup_custom_extensions() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("world.tgz"), b"data").unwrap();
+
+        let result = find_most_recent_backup(temp_dir.path().to_str().unwrap(), &["7z".to_string()]);
+        assert!(result.is_none());
+
+        fs::write(temp_dir.path().join("world.7z"), b"data").unwrap();
+        let result = find_most_recent_backup(temp_dir.path().to_str().unwrap(), &["7z".to_string()]);
+        assert_eq!(result.unwrap().file_name().unwrap(), "world.7z");
+    }
+
+    #[test]
+    fn test_is_backup_file_name() {
+        let extensions = default_extensions();
+
+        assert!(is_backup_file_name("world.tgz", &extensions));
+        assert!(is_backup_file_name("world.tar.gz", &extensions));
+        assert!(is_backup_file_name("WORLD.ZIP", &extensions));
+        assert!(!is_backup_file_name("world.tgz.tmp", &extensions));
+        assert!(!is_backup_file_name("world.zip.part", &extensions));
+        assert!(!is_backup_file_name("server.log", &extensions));
+        assert!(!is_backup_file_name(".tgz", &extensions));
+        assert!(!is_backup_file_name("worldtgz", &extensions));
+
+        // An empty list accepts any finished file
+        assert!(is_backup_file_name("server.log", &[]));
+        assert!(!is_backup_file_name("server.log.tmp", &[]));
+    }
+
+    #[test]
+    fn test_locate_backup_reports_name_and_size() {
+        let (temp_dir, publish_root, _) = setup_publish_test();
+
+        let candidate = locate_backup(temp_dir.path().to_str().unwrap(), &default_extensions()).unwrap();
+        assert_eq!(candidate.file_name, "backup1.tgz");
+        assert_eq!(candidate.size_bytes, 9);
+
+        // Locating a backup publishes nothing
+        assert!(!Path::new(&publish_root).exists());
+    }
+
+    #[test]
+    fn test_dry_run_message() {
+        let candidate = BackupCandidate {
+            path: PathBuf::from("/backups/world.tgz"),
+            file_name: "world.tgz".to_string(),
+            size_bytes: 3 * 1024 * 1024,
+        };
+
+        let message = dry_run_message(&candidate, "https://example.com/backups/");
+        assert!(message.contains("**world.tgz** (3.00 MB)"));
+        assert!(message.contains("https://example.com/backups/<token>/world.tgz"));
+    }
+
+    #[test]
+    fn test_format_cooldown_rounds_up() {
+        assert_eq!(format_cooldown(Duration::from_secs(30)), "30 seconds");
+        assert_eq!(format_cooldown(Duration::from_millis(500)), "1 second");
+        assert_eq!(format_cooldown(Duration::from_secs(60)), "1 minute");
+        assert_eq!(format_cooldown(Duration::from_secs(119)), "2 minutes");
+        assert_eq!(format_cooldown(Duration::from_secs(7200)), "2 hours");
+    }
+
     #[test]
     fn test_publish_backup_creates_tokenized_copy() {
         let (temp_dir, publish_root, base_url) = setup_publish_test();
         let file_path = temp_dir.path().join("backup1.tgz");
 
-        let result = publish_backup(&file_path, &publish_root, &base_url);
+        let result = publish_backup(&file_path, &publish_root, &base_url, |_| {});
         assert!(result.is_ok());
 
         let published = result.unwrap();
@@ -348,7 +879,7 @@ mod tests {
         // Try to publish a non-existent file
         let file_path = temp_dir.path().join("nonexistent.tgz");
 
-        let result = publish_backup(&file_path, &publish_root, &base_url);
+        let result = publish_backup(&file_path, &publish_root, &base_url, |_| {});
         assert!(result.is_err());
     }
 
@@ -358,15 +889,45 @@ mod tests {
         let file_path = temp_dir.path().join("backup1.tgz");
 
         // Publish multiple times and ensure tokens are different
-        let result1 = publish_backup(&file_path, &publish_root, &base_url).unwrap();
-        let result2 = publish_backup(&file_path, &publish_root, &base_url).unwrap();
-        let result3 = publish_backup(&file_path, &publish_root, &base_url).unwrap();
+        let result1 = publish_backup(&file_path, &publish_root, &base_url, |_| {}).unwrap();
+        let result2 = publish_backup(&file_path, &publish_root, &base_url, |_| {}).unwrap();
+        let result3 = publish_backup(&file_path, &publish_root, &base_url, |_| {}).unwrap();
 
         assert_ne!(result1.url, result2.url, "Tokens should be unique");
         assert_ne!(result1.url, result3.url, "Tokens should be unique");
         assert_ne!(result2.url, result3.url, "Tokens should be unique");
     }
 
+    #[test]
+    fn test_create_token_dir_retries_on_collision() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir(temp_dir.path().join("takentoken01")).unwrap();
+        fs::write(temp_dir.path().join("takentoken01").join("other.tgz"), b"other").unwrap();
+
+        let mut tokens = ["takentoken01", "freetoken002"].into_iter();
+        let (token, path) = create_token_dir(temp_dir.path(), || tokens.next().unwrap().to_string()).unwrap();
+        assert_eq!(token, "freetoken002");
+        assert_eq!(path, temp_dir.path().join("freetoken002"));
+        assert!(path.is_dir());
+
+        // The existing directory is left untouched
+        assert_eq!(fs::read_dir(temp_dir.path().join("takentoken01")).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_create_token_dir_gives_up() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir(temp_dir.path().join("takentoken01")).unwrap();
+
+        let mut attempts = 0;
+        let result = create_token_dir(temp_dir.path(), || {
+            attempts += 1;
+            "takentoken01".to_string()
+        });
+        assert_eq!(result.unwrap_err().kind(), std::io::ErrorKind::AlreadyExists);
+        assert_eq!(attempts, TOKEN_ATTEMPTS);
+    }
+
     #[test]
     fn test_publish_backup_url_format() {
         let temp_dir = TempDir::new().unwrap();
@@ -378,13 +939,13 @@ mod tests {
 
         // Test with URL without trailing slash
         let base_url1 = "http://example.com/backups";
-        let result1 = publish_backup(&file_path, publish_root.to_str().unwrap(), base_url1).unwrap();
+        let result1 = publish_backup(&file_path, publish_root.to_str().unwrap(), base_url1, |_| {}).unwrap();
         assert!(!result1.url.contains("//backups"), "Should not have double slashes");
         assert!(result1.url.ends_with("/backup1.tgz"), "Should end with filename");
 
         // Test with URL with trailing slash
         let base_url2 = "http://example.com/backups/";
-        let result2 = publish_backup(&file_path, publish_root.to_str().unwrap(), base_url2).unwrap();
+        let result2 = publish_backup(&file_path, publish_root.to_str().unwrap(), base_url2, |_| {}).unwrap();
         assert!(!result2.url.contains("backups//"), "Should not have double slashes");
         assert!(result2.url.ends_with("/backup1.tgz"), "Should end with filename");
     }
@@ -400,10 +961,32 @@ mod tests {
         let file_path = temp_dir.path().join("backup1.tgz");
         fs::write(&file_path, &test_data).unwrap();
 
-        let result = publish_backup(&file_path, publish_root.to_str().unwrap(), base_url).unwrap();
+        let result = publish_backup(&file_path, publish_root.to_str().unwrap(), base_url, |_| {}).unwrap();
         assert_eq!(result.size_bytes, test_data.len() as u64);
     }
 
+    #[test]
+    fn test_publish_backup_checksum() {
+        let (temp_dir, publish_root, base_url) = setup_publish_test();
+        let file_path = temp_dir.path().join("backup1.tgz");
+        fs::write(&file_path, b"abc").unwrap();
+
+        let result = publish_backup(&file_path, &publish_root, &base_url, |_| {}).unwrap();
+        assert_eq!(result.sha256, "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+    }
+
+    #[test]
+    fn test_sha256_file_streams_multiple_chunks() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("large.tgz");
+        let data: Vec<u8> = (0..COPY_BUFFER_SIZE * 3 + 7).map(|i| (i % 251) as u8).collect();
+        fs::write(&path, &data).unwrap();
+
+        let (size, sha256) = sha256_file(&path).unwrap();
+        assert_eq!(size, data.len() as u64);
+        assert_eq!(sha256, to_hex(&Sha256::digest(&data)));
+    }
+
     #[test]
     fn test_publish_backup_preserves_content() {
         let temp_dir = TempDir::new().unwrap();
@@ -415,10 +998,177 @@ mod tests {
         let file_path = temp_dir.path().join("backup1.tgz");
         fs::write(&file_path, test_content).unwrap();
 
-        let result = publish_backup(&file_path, publish_root.to_str().unwrap(), base_url).unwrap();
+        let result = publish_backup(&file_path, publish_root.to_str().unwrap(), base_url, |_| {}).unwrap();
 
         // Read the published file and verify content
         let published_content = fs::read(&result.local_path).unwrap();
         assert_eq!(published_content, test_content, "Published file should have same content as original");
     }
+
+    #[test]
+    fn test_copy_streaming_large_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("large.tgz");
+        let destination = temp_dir.path().join("copy.tgz");
+
+        // Several buffers' worth of data plus a partial chunk
+        let size = COPY_BUFFER_SIZE * 80 + 123;
+        let data: Vec<u8> = (0..size).map(|i| (i % 251) as u8).collect();
+        fs::write(&source, &data).unwrap();
+
+        let (copied, sha256) = copy_streaming(&source, &destination, PROGRESS_INTERVAL, |_| {}).unwrap();
+        assert_eq!(copied, size as u64);
+        assert_eq!(sha256, sha256_file(&source).unwrap().1);
+        assert_eq!(fs::metadata(&destination).unwrap().len(), size as u64);
+        assert_eq!(fs::read(&destination).unwrap(), data, "Copied file should have same content as original");
+    }
+
+    #[test]
+    fn test_copy_streaming_reports_progress() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("large.tgz");
+        let destination = temp_dir.path().join("copy.tgz");
+
+        let size = COPY_BUFFER_SIZE * 10 + 7;
+        fs::write(&source, vec![1u8; size]).unwrap();
+
+        // One report per 4 buffers copied, then a final one at completion
+        let mut reports = Vec::new();
+        let interval = (COPY_BUFFER_SIZE * 4) as u64;
+        copy_streaming(&source, &destination, interval, |copied| reports.push(copied)).unwrap();
+        assert_eq!(reports, vec![interval, 2 * interval, size as u64]);
+    }
+
+    #[test]
+    fn test_copy_streaming_missing_source() {
+        let temp_dir = TempDir::new().unwrap();
+        let result = copy_streaming(&temp_dir.path().join("missing.tgz"), &temp_dir.path().join("copy.tgz"), PROGRESS_INTERVAL, |_| {});
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_remove_expired_links() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let day = Duration::from_secs(86_400);
+        let set_age = |path: &Path, age: Duration| {
+            fs::File::open(path).unwrap().set_modified(SystemTime::now() - age).unwrap();
+        };
+
+        let old_token = root.join("AbCdEf123456");
+        let new_token = root.join("ZyXwVu654321");
+        let not_a_token = root.join("keep-me-dir1");
+        let short_name = root.join("short");
+        for dir in [&old_token, &new_token, &not_a_token, &short_name] {
+            fs::create_dir(dir).unwrap();
+            fs::write(dir.join("backup.tgz"), b"data").unwrap();
+        }
+        let old_file = root.join("AAAAAAAAAAAA");
+        fs::write(&old_file, b"not a directory").unwrap();
+
+        set_age(&old_token, 8 * day);
+        set_age(&new_token, day);
+        set_age(&not_a_token, 30 * day);
+        set_age(&short_name, 30 * day);
+        set_age(&old_file, 30 * day);
+
+        assert_eq!(remove_expired_links(root, 7 * day).unwrap(), 1);
+        assert!(!old_token.exists());
+        assert!(new_token.exists());
+        assert!(not_a_token.exists());
+        assert!(short_name.exists());
+        assert!(old_file.exists());
+        assert!(root.exists());
+    }
+
+    #[test]
+    fn test_list_published_links() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let day = Duration::from_secs(86_400);
+
+        let older = root.join("AbCdEf123456");
+        let newer = root.join("ZyXwVu654321");
+        let empty = root.join("EmptyDir0000");
+        for dir in [&older, &newer, &empty, &root.join("not-a-token")] {
+            fs::create_dir(dir).unwrap();
+        }
+        fs::write(older.join("old.tgz"), b"old").unwrap();
+        fs::write(newer.join("new.tgz"), b"newer").unwrap();
+        fs::write(root.join("not-a-token").join("x.tgz"), b"x").unwrap();
+        fs::File::open(&older).unwrap().set_modified(SystemTime::now() - 2 * day).unwrap();
+
+        let links = list_published_links(root).unwrap();
+        assert_eq!(links.len(), 2);
+        assert_eq!((links[0].file_name.as_str(), links[0].size_bytes), ("new.tgz", 5));
+        assert_eq!((links[1].file_name.as_str(), links[1].token.as_str()), ("old.tgz", "AbCdEf123456"));
+        assert_eq!(
+            links[1].url("http://example.com/backups/"),
+            "http://example.com/backups/AbCdEf123456/old.tgz"
+        );
+    }
+
+    #[test]
+    fn test_guild_publish_location() {
+        let (root, url) = guild_publish_location("/srv/public", "https://example.com/backups/", Some(42));
+        assert_eq!(Path::new(&root), Path::new("/srv/public/42"));
+        assert_eq!(url, "https://example.com/backups/42");
+
+        let (root, url) = guild_publish_location("/srv/public", "https://example.com/backups", None);
+        assert_eq!((root.as_str(), url.as_str()), ("/srv/public", "https://example.com/backups"));
+    }
+
+    #[test]
+    fn test_published_links_are_listed_per_guild() {
+        let (temp_dir, publish_root, base_url) = setup_publish_test();
+        let file_path = temp_dir.path().join("backup1.tgz");
+
+        let (first_root, first_url) = guild_publish_location(&publish_root, &base_url, Some(1));
+        let (second_root, _) = guild_publish_location(&publish_root, &base_url, Some(2));
+        let published = publish_backup(&file_path, &first_root, &first_url, |_| {}).unwrap();
+        assert!(published.url.starts_with("http://example.com/backups/1/"));
+
+        let links = list_published_links(Path::new(&first_root)).unwrap();
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].url(&first_url), published.url);
+
+        // Other guilds, and the root outside of any guild, don't see the link
+        fs::create_dir_all(&second_root).unwrap();
+        assert!(list_published_links(Path::new(&second_root)).unwrap().is_empty());
+        assert!(list_published_links(Path::new(&publish_root)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_published_link_pages() {
+        let now = SystemTime::now();
+        let link = |i: usize| PublishedLink {
+            token: format!("Token{:07}", i),
+            file_name: format!("backup{}.tgz", i),
+            size_bytes: 3 * 1024 * 1024,
+            published_at: now - Duration::from_secs(2 * 3600),
+        };
+
+        assert!(published_link_pages(&[], "http://example.com", now).is_empty());
+
+        let pages = published_link_pages(&[link(0)], "http://example.com", now);
+        assert_eq!(
+            pages,
+            vec!["📦 **Published backups:** 1 (page 1/1)\n\
+                • **backup0.tgz** (3.00 MB), published 2 hours ago\n  http://example.com/Token0000000/backup0.tgz"]
+        );
+
+        let links: Vec<PublishedLink> = (0..LINKS_PER_PAGE + 1).map(link).collect();
+        let pages = published_link_pages(&links, "http://example.com", now);
+        assert_eq!(pages.len(), 2);
+        assert!(pages[1].starts_with("📦 **Published backups:** 11 (page 2/2)\n• **backup10.tgz**"));
+    }
+
+    #[test]
+    fn test_is_token_dir_name() {
+        assert!(is_token_dir_name("AbCdEf123456"));
+        assert!(!is_token_dir_name("AbCdEf12345"));
+        assert!(!is_token_dir_name("AbCdEf1234567"));
+        assert!(!is_token_dir_name("AbCdEf-23456"));
+        assert!(!is_token_dir_name("../../etc/pa"));
+    }
 }
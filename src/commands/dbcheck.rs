@@ -0,0 +1,110 @@
+//! Database consistency check command.
+//!
+//! Reports (and optionally removes) rows that reference players missing from
+//! the database.
+
+use crate::types::{Context, Error};
+use crate::checks::is_admin_check;
+use crate::confirm::confirm;
+use crate::database::OrphanRow;
+
+/// Maximum number of orphan groups listed in a reply.
+const MAX_LISTED_ORPHANS: usize = 10;
+
+/// Format the orphan report for display.
+fn format_orphans(orphans: &[OrphanRow]) -> String {
+    if orphans.is_empty() {
+        return "✅ No orphaned rows found.".to_string();
+    }
+
+    let total: u64 = orphans.iter().map(|orphan| orphan.count).sum();
+    let mut lines = vec![format!(
+        "⚠️ Found {} orphaned row{} referencing {} missing player{}:",
+        total,
+        if total == 1 { "" } else { "s" },
+        orphans.len(),
+        if orphans.len() == 1 { "" } else { "s" }
+    )];
+
+    lines.extend(orphans.iter().take(MAX_LISTED_ORPHANS).map(|orphan| {
+        format!(
+            "• `{}`: {} row{} for `{}`",
+            orphan.table,
+            orphan.count,
+            if orphan.count == 1 { "" } else { "s" },
+            orphan.mc_uuid
+        )
+    }));
+
+    if orphans.len() > MAX_LISTED_ORPHANS {
+        lines.push(format!("…and {} more", orphans.len() - MAX_LISTED_ORPHANS));
+    }
+
+    lines.join("\n")
+}
+
+/// Check the database for rows referencing players that no longer exist.
+///
+/// Pass `clean` to delete the orphaned rows after reporting them and confirming.
+#[poise::command(slash_command, check = "is_admin_check")]
+pub async fn dbcheck(
+    context: Context<'_>,
+    #[description = "Delete the orphaned rows that were found"]
+    clean: Option<bool>,
+) -> Result<(), Error> {
+    context.defer().await?;
+
+    let repo = context.data().player_repository();
+    let orphans = repo.find_orphans().await?;
+    let message = format_orphans(&orphans);
+
+    if !clean.unwrap_or(false) || orphans.is_empty() {
+        context.say(message).await?;
+        return Ok(());
+    }
+
+    let prompt = format!("{}\n\nDelete these rows?", message);
+    if !confirm(context, &prompt).await? {
+        return Ok(());
+    }
+
+    let deleted = repo.delete_orphans().await?;
+    context
+        .say(format!(
+            "🧹 Deleted {} orphaned row{}.",
+            deleted,
+            if deleted == 1 { "" } else { "s" }
+        ))
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_orphans() {
+        assert_eq!(format_orphans(&[]), "✅ No orphaned rows found.");
+
+        let orphans = vec![OrphanRow {
+            table: "player_stats",
+            mc_uuid: "ghost".to_string(),
+            count: 2,
+        }];
+        let message = format_orphans(&orphans);
+        assert!(message.starts_with("⚠️ Found 2 orphaned rows referencing 1 missing player:"));
+        assert!(message.contains("• `player_stats`: 2 rows for `ghost`"));
+    }
+
+    #[test]
+    fn test_format_orphans_truncates_list() {
+        let orphans: Vec<_> = (0..15)
+            .map(|i| OrphanRow { table: "player_stats", mc_uuid: format!("uuid{}", i), count: 1 })
+            .collect();
+        let message = format_orphans(&orphans);
+        assert_eq!(message.lines().count(), 1 + MAX_LISTED_ORPHANS + 1);
+        assert!(message.ends_with("…and 5 more"));
+    }
+}
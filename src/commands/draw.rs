@@ -0,0 +1,346 @@
+//! Map drawing command.
+//!
+//! Adds circle markers to the Pl3xmap marker file configured with
+//! `PL3XMAP_MARKERS_PATH`, either one at a time or as a batch of points.
+
+use crate::types::{Context, Error, ResponseMessage};
+use crate::checks::is_admin_check;
+use crate::pl3xmap::{build_circle_marker, parse_color_to_argb, write_markers, Pl3xmapColors, RadiusLimits};
+use serde_json::Value;
+use std::path::Path;
+use tokio::sync::Mutex;
+
+/// Stroke color used when none is given.
+const DEFAULT_COLOR: &str = "#3388FF";
+
+/// Fill opacity used when none is given.
+const DEFAULT_FILL_OPACITY: f32 = 0.2;
+
+/// Maximum number of circles drawn at once with `points`.
+const MAX_POINTS: usize = 50;
+
+/// Reply when the marker file isn't configured.
+const NOT_CONFIGURED: &str = "❌ Drawing is not configured. Set PL3XMAP_MARKERS_PATH to enable it.";
+
+/// Serializes marker file updates so concurrent draws don't drop markers.
+static MARKERS_LOCK: Mutex<()> = Mutex::const_new(());
+
+/// A circle to draw on the map.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CircleRequest {
+    pub x: i32,
+    pub z: i32,
+    pub radius: u32,
+    pub scale: u32,
+    /// Stroke color as a name or hex string (see [`parse_color_to_argb`])
+    pub color: String,
+    /// Opacity of the fill, `0.0..=1.0`
+    pub fill_opacity: f32,
+}
+
+/// A parsed `x,z,radius` point, or why it couldn't be parsed.
+type PointResult = std::result::Result<(i32, i32, u32), String>;
+
+/// Validate a circle, append it to the marker file, and build the reply.
+pub async fn draw_response(
+    markers_path: Option<&Path>,
+    limits: &RadiusLimits,
+    circle: CircleRequest,
+) -> Result<ResponseMessage, Error> {
+    let Some(markers_path) = markers_path else {
+        return Ok(ResponseMessage::Failure(NOT_CONFIGURED.to_string()));
+    };
+
+    if let Err(e) = limits.validate(circle.radius, circle.scale) {
+        return Ok(ResponseMessage::Failure(format!("❌ {}", e)));
+    }
+
+    let colors = match circle_colors(&circle.color, circle.fill_opacity) {
+        Ok(colors) => colors,
+        Err(e) => return Ok(ResponseMessage::Failure(format!("❌ {}", e))),
+    };
+
+    let marker = build_circle_marker(circle.x, circle.z, circle.radius, circle.scale, &colors);
+    append_markers(markers_path, vec![marker]).await?;
+
+    Ok(ResponseMessage::Success(format!(
+        "🖌️ Drew a circle of radius {} at ({}, {})",
+        circle.radius, circle.x, circle.z
+    )))
+}
+
+/// Validate a batch of points, append the valid ones to the marker file, and
+/// build the reply.
+///
+/// `points` holds one `x,z,radius` circle per line (or separated by `;`), all
+/// drawn with the same scale, color and fill opacity. Invalid points are
+/// listed in the reply instead of failing the whole batch.
+pub async fn draw_points_response(
+    markers_path: Option<&Path>,
+    limits: &RadiusLimits,
+    points: &str,
+    scale: u32,
+    color: &str,
+    fill_opacity: f32,
+) -> Result<ResponseMessage, Error> {
+    let Some(markers_path) = markers_path else {
+        return Ok(ResponseMessage::Failure(NOT_CONFIGURED.to_string()));
+    };
+
+    // The style is shared, so a bad color fails every point
+    let colors = match circle_colors(color, fill_opacity) {
+        Ok(colors) => colors,
+        Err(e) => return Ok(ResponseMessage::Failure(format!("❌ {}", e))),
+    };
+
+    let points = parse_points(points);
+    if points.is_empty() {
+        return Ok(ResponseMessage::Failure("❌ No points given. Use one `x,z,radius` per line.".to_string()));
+    }
+    if points.len() > MAX_POINTS {
+        return Ok(ResponseMessage::Failure(format!(
+            "❌ Too many points ({}). At most {} can be drawn at once.",
+            points.len(),
+            MAX_POINTS
+        )));
+    }
+
+    let mut markers = Vec::new();
+    let mut failures = Vec::new();
+    for (number, point) in points {
+        let result = point.and_then(|(x, z, radius)| {
+            limits.validate(radius, scale).map_err(|e| e.to_string())?;
+            Ok(build_circle_marker(x, z, radius, scale, &colors))
+        });
+        match result {
+            Ok(marker) => markers.push(marker),
+            Err(e) => failures.push(format!("• Point {}: {}", number, e)),
+        }
+    }
+
+    if markers.is_empty() {
+        return Ok(ResponseMessage::Failure(format!("❌ No valid points to draw:\n{}", failures.join("\n"))));
+    }
+
+    let drawn = markers.len();
+    append_markers(markers_path, markers).await?;
+
+    let mut message = format!("🖌️ Drew {} circle{}", drawn, if drawn == 1 { "" } else { "s" });
+    if !failures.is_empty() {
+        message.push_str(&format!("\n⚠️ Skipped {}:\n{}", failures.len(), failures.join("\n")));
+    }
+    Ok(ResponseMessage::Success(message))
+}
+
+/// Resolve the stroke and fill colors of a circle.
+fn circle_colors(color: &str, fill_opacity: f32) -> crate::error::Result<Pl3xmapColors> {
+    Ok(Pl3xmapColors::new(
+        parse_color_to_argb(color, 1.0)?,
+        parse_color_to_argb(color, fill_opacity)?,
+    ))
+}
+
+/// Append markers to the marker file, one update at a time.
+async fn append_markers(markers_path: &Path, markers: Vec<Value>) -> Result<(), Error> {
+    let path = markers_path.to_path_buf();
+    let _guard = MARKERS_LOCK.lock().await;
+    tokio::task::spawn_blocking(move || write_markers(&path, &markers)).await??;
+    Ok(())
+}
+
+/// Parse the `points` option into numbered `(x, z, radius)` points.
+///
+/// Points are separated by newlines or `;`, and their values by commas,
+/// whitespace, or both. Blank entries are skipped; numbering starts at 1
+/// and counts only non-blank entries.
+fn parse_points(points: &str) -> Vec<(usize, PointResult)> {
+    points
+        .split(['\n', ';'])
+        .map(str::trim)
+        .filter(|point| !point.is_empty())
+        .enumerate()
+        .map(|(index, point)| (index + 1, parse_point(point)))
+        .collect()
+}
+
+/// Parse a single `x,z,radius` point.
+fn parse_point(point: &str) -> PointResult {
+    let values: Vec<&str> = point
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|value| !value.is_empty())
+        .collect();
+
+    let [x, z, radius] = values.as_slice() else {
+        return Err(format!("expected `x,z,radius`, got `{}`", point));
+    };
+
+    let coordinate = |value: &str, name: &str| value.parse::<i32>()
+        .map_err(|_| format!("{} must be an integer, got `{}`", name, value));
+    let x = coordinate(x, "x")?;
+    let z = coordinate(z, "z")?;
+    let radius = radius.parse::<u32>()
+        .map_err(|_| format!("radius must be a non-negative integer, got `{}`", radius))?;
+
+    Ok((x, z, radius))
+}
+
+/// Draw a circle on the Pl3xmap web map.
+///
+/// Give `x`, `z` and `radius` for one circle, or `points` to draw several
+/// circles sharing the same style.
+#[poise::command(slash_command, check = "is_admin_check")]
+#[allow(clippy::too_many_arguments)] // One per slash command option
+pub async fn draw(
+    context: Context<'_>,
+    #[description = "Center X coordinate"]
+    x: Option<i32>,
+    #[description = "Center Z coordinate"]
+    z: Option<i32>,
+    #[description = "Radius in blocks"]
+    radius: Option<u32>,
+    #[description = "Several circles as x,z,radius separated by new lines or ;"]
+    points: Option<String>,
+    #[description = "Map scale the circle is drawn for (default 1)"]
+    #[min = 1]
+    scale: Option<u32>,
+    #[description = "Color name or hex, e.g. red, minecraft:gold or #FF5733 (default #3388FF)"]
+    color: Option<String>,
+    #[description = "Fill opacity from 0 to 1 (default 0.2)"]
+    fill_opacity: Option<f32>,
+) -> Result<(), Error> {
+    let scale = scale.unwrap_or(1);
+    let color = color.unwrap_or_else(|| DEFAULT_COLOR.to_string());
+    let fill_opacity = fill_opacity.unwrap_or(DEFAULT_FILL_OPACITY);
+
+    let data = context.data();
+    let markers_path = data.pl3xmap_markers_path.as_deref();
+    let limits = &data.live().draw_limits;
+
+    let response = match (x, z, radius, points) {
+        (Some(x), Some(z), Some(radius), None) => {
+            let circle = CircleRequest { x, z, radius, scale, color, fill_opacity };
+            draw_response(markers_path, limits, circle).await?
+        }
+        (None, None, None, Some(points)) => {
+            draw_points_response(markers_path, limits, &points, scale, &color, fill_opacity).await?
+        }
+        _ => ResponseMessage::Failure(
+            "❌ Give either `x`, `z` and `radius` for one circle, or `points` for several.".to_string()
+        ),
+    };
+    response.send(context).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn circle(radius: u32, color: &str) -> CircleRequest {
+        CircleRequest {
+            x: 10,
+            z: -20,
+            radius,
+            scale: 1,
+            color: color.to_string(),
+            fill_opacity: 0.5,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_draw_response_writes_marker() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("markers.json");
+
+        let response = draw_response(Some(&path), &RadiusLimits::default(), circle(50, "#FF0000"))
+            .await
+            .unwrap();
+        assert_eq!(response, ResponseMessage::Success("🖌️ Drew a circle of radius 50 at (10, -20)".to_string()));
+
+        let markers: Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(markers.as_array().unwrap().len(), 1);
+        assert_eq!(markers[0]["data"]["radius"], 50);
+        assert_eq!(markers[0]["options"]["fill"]["opacity"], 0.5);
+    }
+
+    #[tokio::test]
+    async fn test_draw_response_rejects_invalid_input() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("markers.json");
+        let limits = RadiusLimits::default();
+
+        let not_configured = draw_response(None, &limits, circle(50, "#FF0000")).await.unwrap();
+        assert!(matches!(not_configured, ResponseMessage::Failure(_)));
+
+        let too_big = draw_response(Some(&path), &limits, circle(limits.max_radius + 1, "#FF0000")).await.unwrap();
+        assert!(too_big.content().contains("exceeds the global cap"));
+
+        let bad_color = draw_response(Some(&path), &limits, circle(50, "reddish")).await.unwrap();
+        assert!(bad_color.content().contains("Unknown color"));
+
+        // Nothing was written
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_parse_points() {
+        let points = parse_points("10,20,5\n  -3 4 7 ;\n\n1, 2, 3;oops\n1,2");
+        assert_eq!(points.len(), 5);
+        assert_eq!(points[0], (1, Ok((10, 20, 5))));
+        assert_eq!(points[1], (2, Ok((-3, 4, 7))));
+        assert_eq!(points[2], (3, Ok((1, 2, 3))));
+        assert_eq!(points[3].0, 4);
+        assert!(points[3].1.as_ref().unwrap_err().contains("expected `x,z,radius`"));
+        assert!(points[4].1.is_err());
+
+        assert!(parse_points(" \n ; ").is_empty());
+        assert!(parse_point("1,2,-3").unwrap_err().contains("radius"));
+        assert!(parse_point("a,2,3").unwrap_err().contains("x must be an integer"));
+    }
+
+    #[tokio::test]
+    async fn test_draw_points_response_reports_bad_points() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("markers.json");
+        let limits = RadiusLimits::default();
+
+        let points = format!("0,0,10\n5,5\n100,-100,20\n1,1,{}", limits.max_radius + 1);
+        let response = draw_points_response(Some(&path), &limits, &points, 1, "gold", 0.5)
+            .await
+            .unwrap();
+
+        let ResponseMessage::Success(message) = response else {
+            panic!("Expected a success message, got {:?}", response);
+        };
+        assert!(message.starts_with("🖌️ Drew 2 circles"));
+        assert!(message.contains("Skipped 2"));
+        assert!(message.contains("• Point 2: expected `x,z,radius`"));
+        assert!(message.contains("• Point 4:"));
+
+        let markers: Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(markers.as_array().unwrap().len(), 2);
+        assert_eq!(markers[1]["data"]["radius"], 20);
+    }
+
+    #[tokio::test]
+    async fn test_draw_points_response_rejects_batch() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("markers.json");
+        let limits = RadiusLimits::default();
+
+        let all_bad = draw_points_response(Some(&path), &limits, "x\ny", 1, "gold", 0.5).await.unwrap();
+        assert!(matches!(all_bad, ResponseMessage::Failure(message) if message.contains("No valid points")));
+
+        let bad_color = draw_points_response(Some(&path), &limits, "0,0,10", 1, "reddish", 0.5).await.unwrap();
+        assert!(bad_color.content().contains("Unknown color"));
+
+        let too_many = "0,0,1\n".repeat(MAX_POINTS + 1);
+        let too_many = draw_points_response(Some(&path), &limits, &too_many, 1, "gold", 0.5).await.unwrap();
+        assert!(too_many.content().contains("Too many points"));
+
+        let empty = draw_points_response(Some(&path), &limits, " ", 1, "gold", 0.5).await.unwrap();
+        assert!(matches!(empty, ResponseMessage::Failure(_)));
+
+        // Nothing was written
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,207 @@
+//! Player activity history command.
+//!
+//! Summarizes recorded status history as a text sparkline, a lighter
+//! alternative to the `/playercount` chart.
+
+use crate::types::{Context, Data, Error, ResponseMessage};
+use crate::checks::is_admin_check;
+use crate::commands::online::requested_server_address;
+use crate::database::StatusSample;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Number of buckets (characters) in the sparkline.
+const SPARKLINE_WIDTH: usize = 24;
+
+/// Block characters from lowest to highest level.
+const SPARK_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Shown for buckets without any samples.
+const SPARK_EMPTY: char = '·';
+
+/// Time range covered by `/history`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, poise::ChoiceParameter)]
+pub enum HistoryRange {
+    #[name = "Last hour"]
+    Hour,
+    #[name = "Last day"]
+    Day,
+    #[name = "Last week"]
+    Week,
+}
+
+impl HistoryRange {
+    /// Length of the range.
+    pub fn duration(self) -> Duration {
+        match self {
+            Self::Hour => Duration::from_secs(3600),
+            Self::Day => Duration::from_secs(24 * 3600),
+            Self::Week => Duration::from_secs(7 * 24 * 3600),
+        }
+    }
+
+    /// Lowercase description used in replies.
+    fn label(self) -> &'static str {
+        match self {
+            Self::Hour => "hour",
+            Self::Day => "day",
+            Self::Week => "week",
+        }
+    }
+}
+
+/// Render online player counts as a sparkline of `width` characters.
+///
+/// Samples are split into equal time buckets between `since` and `until`, and
+/// each bucket shows its average player count relative to the overall peak.
+/// Buckets without samples are shown as `·`.
+fn render_sparkline(samples: &[StatusSample], since: i64, until: i64, width: usize) -> String {
+    let width = width.max(1);
+    let span = (until - since).max(1);
+    let mut buckets = vec![(0u64, 0u64); width];
+
+    for sample in samples {
+        let offset = (sample.timestamp - since).clamp(0, span - 1);
+        let index = (offset as i128 * width as i128 / span as i128) as usize;
+        let (sum, count) = &mut buckets[index.min(width - 1)];
+        *sum += u64::from(sample.online);
+        *count += 1;
+    }
+
+    let peak = samples.iter().map(|sample| sample.online).max().unwrap_or(0);
+    let top = SPARK_LEVELS.len() - 1;
+
+    buckets
+        .iter()
+        .map(|&(sum, count)| match count {
+            0 => SPARK_EMPTY,
+            _ if peak == 0 => SPARK_LEVELS[0],
+            _ => {
+                let average = sum as f64 / count as f64;
+                SPARK_LEVELS[((average / f64::from(peak)) * top as f64).round() as usize]
+            }
+        })
+        .collect()
+}
+
+/// Format the `/history` reply from the samples of the chosen range.
+fn format_history(samples: &[StatusSample], range: HistoryRange, since: i64, until: i64) -> ResponseMessage {
+    if samples.is_empty() {
+        return ResponseMessage::Failure(format!(
+            "❌ No player counts were recorded in the last {}. They are recorded each time the server is checked.",
+            range.label()
+        ));
+    }
+
+    let peak = samples.iter().map(|sample| sample.online).max().unwrap_or(0);
+    let average = samples.iter().map(|sample| u64::from(sample.online)).sum::<u64>() as f64
+        / samples.len() as f64;
+
+    ResponseMessage::Success(format!(
+        "📊 **Player activity over the last {}**\n\
+        `{}`\n\
+        **Peak:** {}\n\
+        **Average:** {:.1}\n\
+        **Samples:** {}",
+        range.label(),
+        render_sparkline(samples, since, until, SPARKLINE_WIDTH),
+        peak,
+        average,
+        samples.len()
+    ))
+}
+
+/// Build the `/history` reply for the server at `address` over the given range.
+pub async fn history_response(data: &Data, address: &str, range: HistoryRange) -> Result<ResponseMessage, Error> {
+    let until = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() as i64);
+    let since = until - range.duration().as_secs() as i64;
+
+    let samples = data
+        .status_history_repository()
+        .get_status_history(address, since, until)
+        .await?;
+
+    Ok(format_history(&samples, range, since, until))
+}
+
+/// Show online player activity over time as a text sparkline.
+#[poise::command(slash_command, check = "is_admin_check")]
+pub async fn history(
+    context: Context<'_>,
+    #[description = "Time range to show (default last day)"]
+    range: Option<HistoryRange>,
+    #[description = "Server name (defaults to this server's configured address)"]
+    server: Option<String>,
+) -> Result<(), Error> {
+    context.defer().await?;
+
+    let data = context.data();
+    let guild_id = context.guild_id().map(|id| id.get());
+    let address = match requested_server_address(data, server.as_deref(), guild_id).await {
+        Ok(address) => address,
+        Err(reply) => return reply.send(context).await,
+    };
+    history_response(data, &address, range.unwrap_or(HistoryRange::Day))
+        .await?
+        .send(context)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(timestamp: i64, online: u16) -> StatusSample {
+        StatusSample { timestamp, online, max: 20 }
+    }
+
+    #[test]
+    fn test_render_sparkline() {
+        let samples = vec![sample(0, 0), sample(10, 4), sample(20, 8), sample(30, 8)];
+        assert_eq!(render_sparkline(&samples, 0, 40, 4), "▁▅██");
+
+        // Gaps in the history are visible
+        let samples = vec![sample(0, 2), sample(35, 2)];
+        assert_eq!(render_sparkline(&samples, 0, 40, 4), "█··█");
+
+        // Nobody online at all
+        let samples = vec![sample(0, 0), sample(30, 0)];
+        assert_eq!(render_sparkline(&samples, 0, 40, 4), "▁··▁");
+    }
+
+    #[test]
+    fn test_render_sparkline_averages_buckets() {
+        let samples = vec![sample(0, 8), sample(1, 0), sample(20, 8)];
+        assert_eq!(render_sparkline(&samples, 0, 40, 2), "▅█");
+    }
+
+    #[test]
+    fn test_render_sparkline_clamps_out_of_range_samples() {
+        let samples = vec![sample(-100, 1), sample(500, 1)];
+        assert_eq!(render_sparkline(&samples, 0, 40, 4), "█··█");
+    }
+
+    #[test]
+    fn test_format_history() {
+        let samples = vec![sample(0, 2), sample(1800, 4), sample(3599, 6)];
+        let ResponseMessage::Success(message) = format_history(&samples, HistoryRange::Hour, 0, 3600) else {
+            panic!("Expected a success message");
+        };
+        assert!(message.contains("last hour"));
+        assert!(message.contains("**Peak:** 6"));
+        assert!(message.contains("**Average:** 4.0"));
+        assert!(message.contains("**Samples:** 3"));
+
+        assert!(matches!(
+            format_history(&[], HistoryRange::Week, 0, 3600),
+            ResponseMessage::Failure(message) if message.contains("last week")
+        ));
+    }
+
+    #[test]
+    fn test_history_range_duration() {
+        assert_eq!(HistoryRange::Hour.duration(), Duration::from_secs(3600));
+        assert_eq!(HistoryRange::Week.duration(), Duration::from_secs(604_800));
+    }
+}
@@ -0,0 +1,103 @@
+//! Stats import command.
+//!
+//! Imports a player's vanilla stats file from the directory configured with
+//! `MINECRAFT_STATS_DIR` into the `player_stats` table.
+
+use crate::types::{Context, Data, Error, ResponseMessage};
+use crate::checks::is_admin_check;
+use crate::database::MinecraftPlayer;
+use crate::minecraft_stats::parse_minecraft_stats;
+use crate::utils::validation::{format_uuid, normalize_uuid};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Path of a player's stats file; vanilla servers name them by dashed UUID.
+///
+/// `uuid` must already be normalized.
+fn stats_file_path(stats_dir: &Path, uuid: &str) -> PathBuf {
+    let dashed = format_uuid(uuid).unwrap_or_else(|| uuid.to_string());
+    stats_dir.join(format!("{}.json", dashed))
+}
+
+/// Build the `/importstats` reply for the given UUID.
+pub async fn importstats_response(data: &Data, uuid: &str) -> Result<ResponseMessage, Error> {
+    let Some(stats_dir) = data.minecraft_stats_dir.as_deref() else {
+        return Ok(ResponseMessage::Failure(
+            "❌ Stats import is not configured. Set MINECRAFT_STATS_DIR to the server's world/stats directory.".to_string()
+        ));
+    };
+
+    let Some(uuid) = normalize_uuid(uuid) else {
+        return Ok(ResponseMessage::Failure(format!("❌ Invalid UUID: `{}`", uuid)));
+    };
+
+    let path = stats_file_path(stats_dir, &uuid);
+    let json = match tokio::fs::read_to_string(&path).await {
+        Ok(json) => json,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(ResponseMessage::Failure(format!(
+                "❌ No stats file found for `{}`. Has this player joined the server?",
+                uuid
+            )));
+        }
+        Err(e) => return Ok(ResponseMessage::Failure(format!("❌ Failed to read stats file: {}", e))),
+    };
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() as i64);
+    let stats = match parse_minecraft_stats(&json, &uuid, timestamp) {
+        Ok(stats) => stats,
+        Err(e) => return Ok(ResponseMessage::Failure(format!("❌ {}", e))),
+    };
+
+    // Stats reference the player, so make sure it is stored first
+    let repo = data.player_repository();
+    let player = match repo.get_player_by_uuid(&uuid).await? {
+        Some(player) => player,
+        None => match data.mojang_client.fetch_profile_by_uuid(&uuid).await {
+            Ok(Some(profile)) => {
+                let player = MinecraftPlayer { uuid: profile.id, username: profile.name };
+                repo.upsert_player(player.clone()).await?;
+                player
+            }
+            Ok(None) => {
+                return Ok(ResponseMessage::Failure(format!("❌ No Minecraft account has UUID `{}`.", uuid)));
+            }
+            Err(e) => return Ok(ResponseMessage::Failure(format!("❌ Failed to look up player: {}", e))),
+        },
+    };
+
+    let imported = repo.upsert_stats(stats).await?;
+    Ok(ResponseMessage::Success(format!(
+        "📥 Imported {} stats for **{}**",
+        imported, player.username
+    )))
+}
+
+/// Import a player's stats from the server's stats files.
+#[poise::command(slash_command, check = "is_admin_check")]
+pub async fn importstats(
+    context: Context<'_>,
+    #[description = "Player UUID (dashed or undashed)"]
+    #[min_length = 32]
+    #[max_length = 36]
+    uuid: String,
+) -> Result<(), Error> {
+    context.defer().await?;
+
+    importstats_response(context.data(), &uuid).await?.send(context).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats_file_path() {
+        assert_eq!(
+            stats_file_path(Path::new("/srv/world/stats"), "069a79f444e94726a5befca90e38aaf5"),
+            PathBuf::from("/srv/world/stats/069a79f4-44e9-4726-a5be-fca90e38aaf5.json")
+        );
+    }
+}
@@ -0,0 +1,85 @@
+//! Map layer command.
+//!
+//! Collects the markers accumulated in the Pl3xmap marker file configured with
+//! `PL3XMAP_MARKERS_PATH` into a named layer file written next to it.
+
+use crate::types::{Context, Error};
+use crate::checks::is_admin_check;
+use crate::pl3xmap::write_layer;
+use std::path::Path;
+
+/// Reply when the marker file isn't configured.
+const NOT_CONFIGURED: &str = "❌ Map layers are not configured. Set PL3XMAP_MARKERS_PATH to enable them.";
+
+/// Write the accumulated markers to the layer file for `key` and build the reply.
+async fn layer_reply(markers_path: Option<&Path>, name: &str, key: &str) -> Result<String, Error> {
+    let Some(markers_path) = markers_path else {
+        return Ok(NOT_CONFIGURED.to_string());
+    };
+
+    let markers_path = markers_path.to_path_buf();
+    let (name, key) = (name.trim().to_string(), key.trim().to_string());
+    let label = if name.is_empty() { key.clone() } else { name };
+
+    let written = tokio::task::spawn_blocking(move || write_layer(&markers_path, &label, &key)).await?;
+    Ok(match written {
+        Ok((path, 0)) => format!("🗺️ Wrote an empty layer to `{}`. No markers have been collected yet.", path.display()),
+        Ok((path, count)) => format!(
+            "🗺️ Wrote {} marker{} to the layer `{}`.",
+            count,
+            if count == 1 { "" } else { "s" },
+            path.display()
+        ),
+        Err(e) => format!("❌ {}", e),
+    })
+}
+
+/// Collect the accumulated map markers into a named Pl3xmap layer.
+#[poise::command(slash_command, check = "is_admin_check")]
+pub async fn layer(
+    context: Context<'_>,
+    #[description = "Layer label shown on the map"]
+    name: String,
+    #[description = "Layer identifier, also the file name (letters, numbers, _ and -)"]
+    key: String,
+) -> Result<(), Error> {
+    let markers_path = context.data().pl3xmap_markers_path.as_deref();
+    let reply = layer_reply(markers_path, &name, &key).await?;
+    context.say(reply).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_layer_reply() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let markers_path = temp_dir.path().join("markers.json");
+
+        let reply = layer_reply(Some(&markers_path), "Spawn", "spawn").await.unwrap();
+        assert!(reply.contains("empty layer"));
+
+        std::fs::write(&markers_path, r#"[{"type": "circle"}]"#).unwrap();
+        let reply = layer_reply(Some(&markers_path), "Spawn", "spawn").await.unwrap();
+        assert!(reply.contains("Wrote 1 marker to"));
+        assert!(temp_dir.path().join("spawn.json").exists());
+    }
+
+    #[tokio::test]
+    async fn test_layer_reply_rejects_bad_key() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let markers_path = temp_dir.path().join("markers.json");
+
+        let reply = layer_reply(Some(&markers_path), "Escape", "../escape").await.unwrap();
+        assert!(reply.starts_with("❌"));
+        assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_layer_reply_not_configured() {
+        let reply = layer_reply(None, "Spawn", "spawn").await.unwrap();
+        assert_eq!(reply, NOT_CONFIGURED);
+    }
+}
@@ -0,0 +1,106 @@
+//! Account linking command.
+//!
+//! Links a Discord account to a Minecraft account.
+
+use crate::types::{Context, Data, Error, ResponseMessage};
+use crate::database::{LinkOutcome, MinecraftPlayer};
+use crate::utils::validation::{format_uuid, validate_minecraft_username};
+
+/// Describe the result of a link attempt.
+fn format_link_outcome(discord_id: u64, name: &str, uuid: &str, outcome: &LinkOutcome) -> ResponseMessage {
+    let display_uuid = |uuid: &str| format_uuid(uuid).unwrap_or_else(|| uuid.to_string());
+
+    match outcome {
+        LinkOutcome::Linked => ResponseMessage::Success(format!(
+            "🔗 Linked <@{}> to **{}**\n**UUID:** `{}`",
+            discord_id, name, display_uuid(uuid)
+        )),
+        LinkOutcome::AlreadyLinked(existing) if existing == uuid => ResponseMessage::Success(format!(
+            "🔗 <@{}> is already linked to **{}**",
+            discord_id, name
+        )),
+        LinkOutcome::AlreadyLinked(existing) => ResponseMessage::Failure(format!(
+            "❌ Your account is already linked to `{}`. Ask an admin to remove that link first.",
+            display_uuid(existing)
+        )),
+        LinkOutcome::TakenBy(_) => ResponseMessage::Failure(format!(
+            "❌ **{}** is already linked to another Discord account.",
+            name
+        )),
+    }
+}
+
+/// Build the `/link` reply, linking `discord_id` to the named player.
+pub async fn link_response(data: &Data, discord_id: u64, name: &str) -> Result<ResponseMessage, Error> {
+    if let Err(e) = validate_minecraft_username(name) {
+        return Ok(ResponseMessage::Failure(format!("❌ {}", e)));
+    }
+
+    let profile = match data.mojang_cache.fetch_profile_cached(&data.mojang_client, name).await {
+        Ok(Some(profile)) => profile,
+        Ok(None) => {
+            return Ok(ResponseMessage::Failure(
+                "❌ Player not found! Make sure the username is correct.".to_string()
+            ));
+        }
+        Err(e) => {
+            return Ok(ResponseMessage::Failure(format!("❌ Failed to connect to Mojang API: {}", e)));
+        }
+    };
+
+    // The link references the stored player, so make sure it exists
+    let repo = data.player_repository();
+    repo.upsert_player(MinecraftPlayer {
+        uuid: profile.id.clone(),
+        username: profile.name.clone(),
+    }).await?;
+
+    let outcome = repo.link_account(discord_id, &profile.id).await?;
+    Ok(format_link_outcome(discord_id, &profile.name, &profile.id, &outcome))
+}
+
+/// Link your Discord account to a Minecraft account.
+#[poise::command(slash_command)]
+pub async fn link(
+    context: Context<'_>,
+    #[description = "Minecraft username"]
+    #[min_length = 1]
+    #[max_length = 16]
+    name: String,
+) -> Result<(), Error> {
+    context.defer().await?;
+
+    link_response(context.data(), context.author().id.get(), &name)
+        .await?
+        .send(context)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UUID: &str = "069a79f444e94726a5befca90e38aaf5";
+
+    #[test]
+    fn test_format_link_outcome() {
+        assert_eq!(
+            format_link_outcome(42, "Notch", UUID, &LinkOutcome::Linked),
+            ResponseMessage::Success(
+                "🔗 Linked <@42> to **Notch**\n**UUID:** `069a79f4-44e9-4726-a5be-fca90e38aaf5`".to_string()
+            )
+        );
+        assert_eq!(
+            format_link_outcome(42, "Notch", UUID, &LinkOutcome::AlreadyLinked(UUID.to_string())),
+            ResponseMessage::Success("🔗 <@42> is already linked to **Notch**".to_string())
+        );
+        assert!(matches!(
+            format_link_outcome(42, "Notch", UUID, &LinkOutcome::AlreadyLinked("a".repeat(32))),
+            ResponseMessage::Failure(_)
+        ));
+        assert_eq!(
+            format_link_outcome(42, "Notch", UUID, &LinkOutcome::TakenBy(7)),
+            ResponseMessage::Failure("❌ **Notch** is already linked to another Discord account.".to_string())
+        );
+    }
+}
@@ -0,0 +1,79 @@
+//! Player list command.
+//!
+//! Lists every player stored in the database, a page at a time.
+
+use crate::types::{Context, Error};
+use crate::database::MinecraftPlayer;
+
+/// Number of players shown on each page.
+const PLAYERS_PER_PAGE: usize = 25;
+
+/// Split the player list into pages, each headed with the total count.
+fn list_pages(players: &[MinecraftPlayer]) -> Vec<String> {
+    let page_count = players.len().div_ceil(PLAYERS_PER_PAGE);
+
+    players
+        .chunks(PLAYERS_PER_PAGE)
+        .enumerate()
+        .map(|(index, chunk)| {
+            let names: Vec<String> = chunk.iter().map(|player| format!("• {}", player.username)).collect();
+            format!(
+                "👥 **Tracked players:** {} (page {}/{})\n{}",
+                players.len(),
+                index + 1,
+                page_count,
+                names.join("\n")
+            )
+        })
+        .collect()
+}
+
+/// List all players tracked in the database.
+#[poise::command(slash_command)]
+pub async fn list(context: Context<'_>) -> Result<(), Error> {
+    let players = context.data().player_repository().get_all_players().await?;
+    let pages = list_pages(&players);
+
+    match pages.as_slice() {
+        [] => {
+            context.say("❌ No players are tracked yet.").await?;
+        }
+        [page] => {
+            context.say(page).await?;
+        }
+        pages => {
+            let pages: Vec<&str> = pages.iter().map(String::as_str).collect();
+            poise::builtins::paginate(context, &pages).await?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn players(count: usize) -> Vec<MinecraftPlayer> {
+        (0..count)
+            .map(|i| MinecraftPlayer {
+                uuid: format!("{:032x}", i),
+                username: format!("Player{:03}", i),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_list_pages() {
+        assert!(list_pages(&[]).is_empty());
+
+        let pages = list_pages(&players(2));
+        assert_eq!(pages, vec!["👥 **Tracked players:** 2 (page 1/1)\n• Player000\n• Player001"]);
+
+        let pages = list_pages(&players(51));
+        assert_eq!(pages.len(), 3);
+        assert!(pages[0].starts_with("👥 **Tracked players:** 51 (page 1/3)\n• Player000"));
+        assert_eq!(pages[1].lines().count(), PLAYERS_PER_PAGE + 1);
+        assert!(pages[2].ends_with("(page 3/3)\n• Player050"));
+    }
+}
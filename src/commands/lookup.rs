@@ -0,0 +1,55 @@
+//! Player lookup command.
+//!
+//! Resolves a Minecraft player from either a username or a UUID.
+
+use crate::types::{Context, Error};
+use crate::error::OxideVaultError;
+use crate::utils::validation::format_uuid;
+use crate::database::MinecraftPlayer;
+
+/// Look up a Minecraft player by username or UUID.
+///
+/// UUIDs are preferred when the input is valid as both.
+#[poise::command(slash_command)]
+pub async fn lookup(
+    context: Context<'_>,
+    #[description = "Minecraft username or UUID"]
+    #[min_length = 1]
+    #[max_length = 36]
+    player: String,
+) -> Result<(), Error> {
+    context.defer().await?;
+
+    match context.data().mojang_client.resolve(&player).await {
+        Ok(Some(profile)) => {
+            // Try to store in database (non-fatal if it fails)
+            let repo = context.data().player_repository();
+            let _ = repo.upsert_player(MinecraftPlayer {
+                uuid: profile.id.clone(),
+                username: profile.name.clone(),
+            }).await;
+
+            let uuid = format_uuid(&profile.id).unwrap_or(profile.id);
+            context
+                .say(format!("✅ **Player:** {}\n**UUID:** `{}`", profile.name, uuid))
+                .await?;
+        }
+        Ok(None) => {
+            context
+                .say("❌ Player not found! Make sure the username or UUID is correct.")
+                .await?;
+        }
+        Err(OxideVaultError::Validation(_)) => {
+            context
+                .say("❌ That doesn't look like a valid Minecraft username or UUID.")
+                .await?;
+        }
+        Err(e) => {
+            context
+                .say(format!("❌ Failed to connect to Mojang API: {}", e))
+                .await?;
+        }
+    }
+
+    Ok(())
+}
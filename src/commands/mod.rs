@@ -6,8 +6,50 @@ pub mod ping;
 pub mod uuid;
 pub mod online;
 pub mod backup;
+pub mod sync;
+pub mod lookup;
+pub mod layer;
+pub mod settings;
+pub mod setserver;
+pub mod dbcheck;
+pub mod resolve;
+pub mod playercount;
+pub mod servers;
+pub mod history;
+pub mod draw;
+pub mod skin;
+pub mod seen;
+pub mod serverinfo;
+pub mod link;
+pub mod whoami;
+pub mod list;
+pub mod playtime;
+pub mod stats;
+pub mod importstats;
+mod autocomplete;
+mod player;
 
 pub use ping::ping;
 pub use uuid::uuid;
 pub use online::online;
-pub use backup::backup;
+pub use backup::{backup, backups};
+pub use sync::sync;
+pub use lookup::lookup;
+pub use layer::layer;
+pub use settings::settings;
+pub use setserver::setserver;
+pub use dbcheck::dbcheck;
+pub use resolve::resolve;
+pub use playercount::playercount;
+pub use servers::servers;
+pub use history::history;
+pub use draw::draw;
+pub use skin::skin;
+pub use seen::seen;
+pub use serverinfo::serverinfo;
+pub use link::link;
+pub use whoami::whoami;
+pub use list::list;
+pub use playtime::playtime;
+pub use stats::stats;
+pub use importstats::importstats;
@@ -2,58 +2,637 @@
 //!
 //! Queries the Minecraft server for status and online player information.
 
-use crate::types::{Context, Error};
-use crate::mc_server;
+use crate::types::{Context, Data, Error, LastKnownStatuses, ResponseMessage};
+use crate::error::OxideVaultError;
+use crate::database::{PlayerRepository, StatusHistoryRepository, StatusSample};
+use crate::mc_server::{PlayersInfo, ServerStatus, StatusSource};
+use crate::utils::format_relative;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Most characters of the player list shown, keeping the reply within
+/// Discord's 2000-character message limit.
+const PLAYER_LIST_MAX_CHARS: usize = 1400;
+
+/// Most characters shown of a single player name.
+const PLAYER_NAME_MAX_CHARS: usize = 32;
+
+/// Most characters shown of the server's version string.
+const VERSION_MAX_CHARS: usize = 64;
+
+/// Most characters shown of the server's description.
+const DESCRIPTION_MAX_CHARS: usize = 256;
+
+/// Shorten `text` to at most `max` characters, ending with `…` when cut.
+fn truncate_chars(text: &str, max: usize) -> String {
+    match text.char_indices().nth(max) {
+        None => text.to_string(),
+        Some(_) => {
+            let cut = text.char_indices().nth(max.saturating_sub(1)).map_or(0, |(index, _)| index);
+            format!("{}…", &text[..cut])
+        }
+    }
+}
+
+/// Where the last successful status of the pinged server is remembered.
+pub struct LastKnown<'a> {
+    pub statuses: &'a LastKnownStatuses,
+    /// Address of the pinged server
+    pub address: &'a str,
+    /// How long a remembered status is served instead of pinging again (zero disables)
+    pub cache_ttl: Duration,
+}
+
+/// Whether the status sample leaves out some of the online players, either
+/// because it was capped or because the server only sent part of it.
+fn is_sample_partial(players: &PlayersInfo) -> bool {
+    let named = players.sample.iter().filter(|p| !p.is_anonymous()).count();
+    players.omitted > 0 || named < usize::from(players.online)
+}
+
+/// Render the online player list, noting any sample entries that were capped.
+///
+/// `full_list` (from the Query protocol) replaces the sample when given.
+/// Anonymous placeholder entries are left out; the reported player count is
+/// unaffected. Long names are shortened, and names past
+/// [`PLAYER_LIST_MAX_CHARS`] are counted in the "(+N more)" note instead.
+fn format_player_list(players: &PlayersInfo, full_list: Option<&[String]>) -> String {
+    let (names, omitted): (Vec<&str>, usize) = match full_list {
+        Some(names) => (names.iter().map(String::as_str).collect(), 0),
+        None => (
+            players.sample
+                .iter()
+                .filter(|p| !p.is_anonymous())
+                .map(|p| p.name.as_str())
+                .collect(),
+            players.omitted,
+        ),
+    };
+
+    if names.is_empty() {
+        return String::new();
+    }
+
+    let mut shown = String::new();
+    let mut shown_chars = 0;
+    let mut shown_count = 0;
+    for name in &names {
+        let name = truncate_chars(name, PLAYER_NAME_MAX_CHARS);
+        let separator = if shown_count == 0 { "" } else { ", " };
+        let added = separator.len() + name.chars().count();
+        if shown_chars + added > PLAYER_LIST_MAX_CHARS {
+            break;
+        }
+        shown.push_str(separator);
+        shown.push_str(&name);
+        shown_chars += added;
+        shown_count += 1;
+    }
+
+    let mut list = format!("\n**Players online:** {}", shown);
+    let omitted = omitted + (names.len() - shown_count);
+    if omitted > 0 {
+        list.push_str(&format!(" (+{} more)", omitted));
+    }
+    list
+}
+
+/// Render a server status for the `/online` reply.
+fn format_status(status: &ServerStatus, full_list: Option<&[String]>) -> String {
+    let player_list = format_player_list(&status.players, full_list);
+    let ping = status
+        .latency_ms
+        .map_or_else(String::new, |ms| format!("\n**Ping:** {}ms", ms));
+
+    format!(
+        "**Minecraft Server Status** 🎮\n\
+        **Version:** {}\n\
+        **Players:** {}/{}{}\n\
+        **Description:** {}{}",
+        truncate_chars(&status.version.name, VERSION_MAX_CHARS),
+        status.players.online,
+        status.players.max,
+        ping,
+        truncate_chars(&status.description.to_plain(), DESCRIPTION_MAX_CHARS),
+        player_list
+    )
+}
+
+/// Reply for a failed ping of a server last seen online `age` ago.
+fn offline_message(error: &OxideVaultError, age: Duration, status: &ServerStatus) -> ResponseMessage {
+    ResponseMessage::Failure(format!(
+        "🔴 Server appears offline. Last seen online {} with {}/{} players.\n({})",
+        format_relative(age),
+        status.players.online,
+        status.players.max,
+        error
+    ))
+}
+
+/// Record a successful ping to `history` under the given server address, and
+/// sampled players' last-seen times to `players`, if given.
+///
+/// Failures are ignored: recording is best-effort and never fails a command.
+pub async fn record_status(
+    status: &ServerStatus,
+    history: Option<(&StatusHistoryRepository, &str)>,
+    players: Option<&PlayerRepository>,
+) {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() as i64);
+
+    if let Some(players) = players {
+        for sample in status.players.sample.iter().filter(|sample| !sample.is_anonymous()) {
+            let _ = players.update_last_seen(&sample.id.replace('-', ""), timestamp).await;
+        }
+    }
+
+    if let Some((history, server)) = history {
+        let _ = history.record_status(server, StatusSample {
+            timestamp,
+            online: status.players.online,
+            max: status.players.max,
+        }).await;
+    }
+}
+
+/// Build the `/online` reply from the given status source.
+///
+/// When the status sample leaves players out, the full list is fetched from
+/// the source if it has one. Successful pings are also recorded (see
+/// [`record_status`]). With
+/// `last_known`, successful statuses are remembered and a failed ping reports
+/// when the server was last seen online instead of just the error. A status
+/// remembered less than `cache_ttl` ago is served without pinging, noting its age.
+pub async fn online_response<S: StatusSource>(
+    source: &S,
+    history: Option<(&StatusHistoryRepository, &str)>,
+    players: Option<&PlayerRepository>,
+    last_known: Option<LastKnown<'_>>,
+) -> Result<ResponseMessage, Error> {
+    // Serve a recent enough status without pinging the server again
+    if let Some(last_known) = &last_known {
+        if let Some((seen_at, status)) = last_known.statuses.read().await.get(last_known.address) {
+            let age = seen_at.elapsed();
+            if age < last_known.cache_ttl {
+                let mut message = format_status(status, None);
+                message.push_str(&format!("\n(cached {}s ago)", age.as_secs()));
+                return Ok(ResponseMessage::Success(message));
+            }
+        }
+    }
+
+    match source.fetch_status().await {
+        Ok(status) => {
+            if let Some(last_known) = &last_known {
+                last_known
+                    .statuses
+                    .write()
+                    .await
+                    .insert(last_known.address.to_string(), (Instant::now(), status.clone()));
+            }
+
+            record_status(&status, history, players).await;
+
+            // Ask for the full player list when the sample leaves players out
+            let full_list = if is_sample_partial(&status.players) {
+                source.fetch_player_names().await.filter(|names| !names.is_empty())
+            } else {
+                None
+            };
+
+            Ok(ResponseMessage::Success(format_status(&status, full_list.as_deref())))
+        }
+        Err(e) => {
+            if let Some(last_known) = &last_known {
+                if let Some((seen_at, status)) = last_known.statuses.read().await.get(last_known.address) {
+                    return Ok(offline_message(&e, seen_at.elapsed(), status));
+                }
+            }
+            Ok(ResponseMessage::Failure(match e {
+                OxideVaultError::DnsResolution(_) => {
+                    format!("❌ Could not find the server. Check that the address is spelled correctly. ({})", e)
+                }
+                e => format!("❌ Failed to connect to server: {}", e),
+            }))
+        }
+    }
+}
+
+/// Reply for a server name that isn't configured, listing the known names.
+fn unknown_server_message<'a>(name: &str, known: impl Iterator<Item = &'a String>) -> ResponseMessage {
+    let mut names: Vec<&str> = known.map(String::as_str).collect();
+    names.sort_unstable();
+    ResponseMessage::Failure(format!(
+        "❌ Unknown server '{}'. Known servers: {}",
+        name,
+        names.join(", ")
+    ))
+}
+
+/// Resolve the address of the server named in `MC_SERVERS`, or of the
+/// guild's configured server when no name is given.
+///
+/// Unknown names produce the reply listing the known ones.
+pub async fn requested_server_address(
+    data: &Data,
+    name: Option<&str>,
+    guild_id: Option<u64>,
+) -> Result<String, ResponseMessage> {
+    match name {
+        Some(name) => data
+            .named_server_address(name)
+            .ok_or_else(|| unknown_server_message(name, data.live().mc_servers.keys())),
+        None => Ok(data.server_address_for(guild_id).await),
+    }
+}
 
 /// Check the status and online players of the configured Minecraft server.
 #[poise::command(slash_command)]
 pub async fn online(
     context: Context<'_>,
+    #[description = "Server name (defaults to this server's configured address)"]
+    server: Option<String>,
 ) -> Result<(), Error> {
     // Defer reply since server ping might take a moment
     context.defer().await?;
 
-    // Get server address from bot data
-    let server_address = context.data().mc_server_address.clone();
-
-    // Ping the server in a blocking task
-    let result = tokio::task::spawn_blocking(move || {
-        mc_server::ping_server(&server_address)
-    }).await;
-
-    match result {
-        Ok(Ok(status)) => {
-            let player_list = if !status.players.sample.is_empty() {
-                let players: Vec<&str> = status.players.sample
-                    .iter()
-                    .map(|p| p.name.as_str())
-                    .collect();
-                format!("\n**Players online:** {}", players.join(", "))
-            } else {
-                String::new()
-            };
+    // Ping the requested or configured server (directly or through the configured proxy)
+    let data = context.data();
+    let guild_id = context.guild_id().map(|id| id.get());
+    let address = match requested_server_address(data, server.as_deref(), guild_id).await {
+        Ok(address) => address,
+        Err(reply) => return reply.send(context).await,
+    };
+    let source = data.status_source_at(address.clone());
 
-            let response = format!(
-                "**Minecraft Server Status** 🎮\n\
-                **Version:** {}\n\
-                **Players:** {}/{}\n\
-                **Description:** {}{}",
-                status.version.name,
-                status.players.online,
-                status.players.max,
-                status.description.text(),
-                player_list
-            );
+    let history = data.status_history_repository();
+    let players = data.player_repository();
+    let last_known = LastKnown {
+        statuses: &data.last_known_statuses,
+        address: &address,
+        cache_ttl: data.live().status_cache_ttl,
+    };
+
+    online_response(&source, Some((&history, &address)), Some(&players), Some(last_known))
+        .await?
+        .send(context)
+        .await
+}
 
-            context.say(response).await?;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Result;
+    use crate::mc_server::ServerStatus;
+
+    /// Status source returning a fixed status, or an error when `None`.
+    struct MockStatusSource {
+        status: Option<ServerStatus>,
+    }
+
+    impl StatusSource for MockStatusSource {
+        async fn fetch_status(&self) -> Result<ServerStatus> {
+            self.status
+                .clone()
+                .ok_or_else(|| OxideVaultError::ServerProtocol("Connection failed".to_string()))
         }
-        Ok(Err(e)) => {
-            context.say(format!("❌ Failed to connect to server: {}", e)).await?;
+    }
+
+    #[test]
+    fn test_format_player_list() {
+        let status = ServerStatus::builder().build();
+        assert_eq!(format_player_list(&status.players, None), "");
+
+        let status = ServerStatus::builder()
+            .player("Steve", "id1")
+            .player("Alex", "id2")
+            .build();
+        assert_eq!(format_player_list(&status.players, None), "\n**Players online:** Steve, Alex");
+    }
+
+    #[test]
+    fn test_format_player_list_capped() {
+        let mut status = ServerStatus::builder()
+            .player("Steve", "id1")
+            .player("Alex", "id2")
+            .player("Notch", "id3")
+            .build();
+        status.players.cap_sample(2);
+        assert_eq!(
+            format_player_list(&status.players, None),
+            "\n**Players online:** Steve, Alex (+1 more)"
+        );
+    }
+
+    #[test]
+    fn test_format_player_list_long_names() {
+        let long_name = "W".repeat(500);
+        let status = (0..100)
+            .fold(ServerStatus::builder().players(100, 100), |builder, i| {
+                builder.player(&format!("{}{}", long_name, i), "id")
+            })
+            .build();
+
+        let list = format_player_list(&status.players, None);
+        assert!(list.chars().count() <= PLAYER_LIST_MAX_CHARS + 50);
+        assert!(list.contains(&format!("{}…", "W".repeat(PLAYER_NAME_MAX_CHARS - 1))));
+
+        let shown = list.matches('…').count();
+        assert!(shown > 0 && shown < 100);
+        assert!(list.ends_with(&format!(" (+{} more)", 100 - shown)));
+    }
+
+    #[test]
+    fn test_format_status_fits_discord_limit() {
+        let status = (0..100)
+            .fold(
+                ServerStatus::builder()
+                    .version(&"v".repeat(5000), 767)
+                    .players(100, 100)
+                    .description(&"M".repeat(5000))
+                    .latency(42),
+                |builder, i| builder.player(&format!("{}{}", "W".repeat(500), i), "id"),
+            )
+            .build();
+
+        let message = format_status(&status, None);
+        assert!(message.chars().count() <= 2000);
+    }
+
+    #[test]
+    fn test_truncate_chars() {
+        assert_eq!(truncate_chars("Steve", 5), "Steve");
+        assert_eq!(truncate_chars("Steve", 4), "Ste…");
+        assert_eq!(truncate_chars("ééééé", 3), "éé…");
+    }
+
+    #[test]
+    fn test_format_player_list_skips_anonymous() {
+        let status = ServerStatus::builder()
+            .players(3, 20)
+            .player("Anonymous Player", "00000000-0000-0000-0000-000000000000")
+            .player("Steve", "8667ba71-b85a-4004-af54-457a9734eed7")
+            .build();
+        assert_eq!(format_player_list(&status.players, None), "\n**Players online:** Steve");
+
+        let hidden = ServerStatus::builder()
+            .players(2, 20)
+            .player("Anonymous Player", "00000000-0000-0000-0000-000000000000")
+            .build();
+        assert_eq!(format_player_list(&hidden.players, None), "");
+    }
+
+    #[tokio::test]
+    async fn test_online_response_fetches_full_list() {
+        /// Status source whose server also answers queries.
+        struct QueryableSource {
+            status: ServerStatus,
         }
-        Err(e) => {
-            context.say(format!("❌ Error: {}", e)).await?;
+
+        impl StatusSource for QueryableSource {
+            async fn fetch_status(&self) -> Result<ServerStatus> {
+                Ok(self.status.clone())
+            }
+
+            async fn fetch_player_names(&self) -> Option<Vec<String>> {
+                Some(vec!["Steve".to_string(), "Alex".to_string(), "Notch".to_string()])
+            }
+        }
+
+        // A partial sample is replaced by the full list
+        let partial = QueryableSource {
+            status: ServerStatus::builder().players(3, 20).player("Steve", "id1").build(),
+        };
+        let response = online_response(&partial, None, None, None).await.unwrap();
+        assert!(response.content().ends_with("**Players online:** Steve, Alex, Notch"));
+
+        // A complete sample is shown as-is
+        let complete = QueryableSource {
+            status: ServerStatus::builder().players(1, 20).player("Steve", "id1").build(),
+        };
+        let response = online_response(&complete, None, None, None).await.unwrap();
+        assert!(response.content().ends_with("**Players online:** Steve"));
+    }
+
+    #[test]
+    fn test_is_sample_partial() {
+        let complete = ServerStatus::builder().players(1, 20).player("Steve", "id1").build();
+        assert!(!is_sample_partial(&complete.players));
+
+        let hidden = ServerStatus::builder()
+            .players(1, 20)
+            .player("Anonymous Player", "00000000-0000-0000-0000-000000000000")
+            .build();
+        assert!(is_sample_partial(&hidden.players));
+
+        let mut capped = ServerStatus::builder()
+            .players(2, 20)
+            .player("Steve", "id1")
+            .player("Alex", "id2")
+            .build();
+        capped.players.cap_sample(1);
+        assert!(is_sample_partial(&capped.players));
+    }
+
+    #[test]
+    fn test_unknown_server_message() {
+        let known = ["survival".to_string(), "default".to_string()];
+        assert_eq!(
+            unknown_server_message("creative", known.iter()),
+            ResponseMessage::Failure("❌ Unknown server 'creative'. Known servers: default, survival".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_online_response_server_up() {
+        let source = MockStatusSource {
+            status: Some(
+                ServerStatus::builder()
+                    .version("1.21.4", 769)
+                    .players(1, 20)
+                    .player("Steve", "id1")
+                    .description("Welcome!")
+                    .latency(42)
+                    .build(),
+            ),
+        };
+
+        let response = online_response(&source, None, None, None).await.unwrap();
+        assert_eq!(
+            response,
+            ResponseMessage::Success(
+                "**Minecraft Server Status** 🎮\n\
+                **Version:** 1.21.4\n\
+                **Players:** 1/20\n\
+                **Ping:** 42ms\n\
+                **Description:** Welcome!\n\
+                **Players online:** Steve"
+                    .to_string()
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_online_response_flattens_motd() {
+        let mut status = ServerStatus::builder().players(0, 20).build();
+        status.description = serde_json::from_str(
+            r#"{"text":"","extra":[{"text":"§cHello","color":"red"},{"text":" World"}]}"#,
+        ).unwrap();
+        let source = MockStatusSource { status: Some(status) };
+
+        let response = online_response(&source, None, None, None).await.unwrap();
+        assert!(response.content().contains("**Description:** Hello World"));
+    }
+
+    #[tokio::test]
+    async fn test_online_response_server_down() {
+        let source = MockStatusSource { status: None };
+
+        let response = online_response(&source, None, None, None).await.unwrap();
+        assert_eq!(
+            response,
+            ResponseMessage::Failure(
+                "❌ Failed to connect to server: Server protocol error: Connection failed".to_string()
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_online_response_unknown_host() {
+        struct UnresolvableSource;
+
+        impl StatusSource for UnresolvableSource {
+            async fn fetch_status(&self) -> Result<ServerStatus> {
+                Err(OxideVaultError::DnsResolution("Failed to resolve address: no such host".to_string()))
+            }
         }
+
+        let response = online_response(&UnresolvableSource, None, None, None).await.unwrap();
+        assert_eq!(
+            response.content(),
+            "❌ Could not find the server. Check that the address is spelled correctly. \
+            (DNS resolution error: Failed to resolve address: no such host)"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_online_response_falls_back_to_last_known() {
+        let statuses = LastKnownStatuses::default();
+        let last_known = || Some(LastKnown { statuses: &statuses, address: "mc.example.com", cache_ttl: Duration::ZERO });
+
+        // No status remembered yet: plain error
+        let down = MockStatusSource { status: None };
+        let response = online_response(&down, None, None, last_known()).await.unwrap();
+        assert!(response.content().starts_with("❌ Failed to connect to server"));
+
+        let up = MockStatusSource { status: Some(ServerStatus::builder().players(3, 20).build()) };
+        online_response(&up, None, None, last_known()).await.unwrap();
+        assert!(statuses.read().await.contains_key("mc.example.com"));
+
+        let response = online_response(&down, None, None, last_known()).await.unwrap();
+        assert_eq!(
+            response,
+            ResponseMessage::Failure(
+                "🔴 Server appears offline. Last seen online just now with 3/20 players.\n\
+                (Server protocol error: Connection failed)"
+                    .to_string()
+            )
+        );
+
+        // Other servers don't share the remembered status
+        let other = LastKnown { statuses: &statuses, address: "other.example.com", cache_ttl: Duration::ZERO };
+        let response = online_response(&down, None, None, Some(other)).await.unwrap();
+        assert!(response.content().starts_with("❌"));
+    }
+
+    #[tokio::test]
+    async fn test_online_response_serves_cached_status() {
+        let statuses = LastKnownStatuses::default();
+        let cached = || Some(LastKnown { statuses: &statuses, address: "mc.example.com", cache_ttl: Duration::from_secs(10) });
+
+        let up = MockStatusSource { status: Some(ServerStatus::builder().players(3, 20).build()) };
+        let response = online_response(&up, None, None, cached()).await.unwrap();
+        assert!(!response.content().contains("cached"));
+
+        // A fresh status is served even though the server is now down
+        let down = MockStatusSource { status: None };
+        let response = online_response(&down, None, None, cached()).await.unwrap();
+        assert!(response.content().contains("**Players:** 3/20"));
+        assert!(response.content().ends_with("(cached 0s ago)"));
+
+        // Once stale, the server is pinged again
+        statuses.write().await.get_mut("mc.example.com").unwrap().0 -= Duration::from_secs(11);
+        let response = online_response(&down, None, None, cached()).await.unwrap();
+        assert!(response.content().starts_with("🔴 Server appears offline"));
+    }
+
+    #[test]
+    fn test_offline_message_age() {
+        let status = ServerStatus::builder().players(5, 50).build();
+        let error = OxideVaultError::ServerProtocol("timed out".to_string());
+        assert_eq!(
+            offline_message(&error, Duration::from_secs(7200), &status).content(),
+            "🔴 Server appears offline. Last seen online 2 hours ago with 5/50 players.\n(Server protocol error: timed out)"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_online_response_records_history() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db").to_str().unwrap().to_string();
+        crate::database::init_db(&db_path).await.unwrap();
+        let history = StatusHistoryRepository::new(db_path);
+
+        let source = MockStatusSource { status: Some(ServerStatus::builder().players(4, 20).build()) };
+        online_response(&source, Some((&history, "mc.example.com")), None, None).await.unwrap();
+
+        let samples = history.get_status_history("mc.example.com", 0, i64::MAX).await.unwrap();
+        assert_eq!(samples.len(), 1);
+        assert_eq!((samples[0].online, samples[0].max), (4, 20));
+    }
+
+    #[tokio::test]
+    async fn test_online_response_records_history_per_server() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db").to_str().unwrap().to_string();
+        crate::database::init_db(&db_path).await.unwrap();
+        let history = StatusHistoryRepository::new(db_path);
+
+        // A named server and a guild's own server don't mix with the default one
+        let survival = MockStatusSource { status: Some(ServerStatus::builder().players(4, 20).build()) };
+        let creative = MockStatusSource { status: Some(ServerStatus::builder().players(1, 10).build()) };
+        online_response(&survival, Some((&history, "survival.example.com")), None, None).await.unwrap();
+        online_response(&creative, Some((&history, "creative.example.com")), None, None).await.unwrap();
+
+        let counts = |samples: Vec<StatusSample>| samples.iter().map(|s| (s.online, s.max)).collect::<Vec<_>>();
+        assert_eq!(counts(history.get_count_history("survival.example.com", 0).await.unwrap()), vec![(4, 20)]);
+        assert_eq!(counts(history.get_count_history("creative.example.com", 0).await.unwrap()), vec![(1, 10)]);
+        assert!(history.get_count_history("mc.example.com", 0).await.unwrap().is_empty());
     }
 
-    Ok(())
+    #[tokio::test]
+    async fn test_online_response_records_last_seen() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db").to_str().unwrap().to_string();
+        crate::database::init_db(&db_path).await.unwrap();
+        let players = PlayerRepository::new(db_path);
+        players.upsert_player(crate::database::MinecraftPlayer {
+            uuid: "8667ba71b85a4004af54457a9734eed7".to_string(),
+            username: "Steve".to_string(),
+        }).await.unwrap();
+
+        let source = MockStatusSource {
+            status: Some(
+                ServerStatus::builder()
+                    .players(1, 20)
+                    .player("Steve", "8667ba71-b85a-4004-af54-457a9734eed7")
+                    .build(),
+            ),
+        };
+        online_response(&source, None, Some(&players), None).await.unwrap();
+
+        let last_seen = players.get_last_seen("8667ba71b85a4004af54457a9734eed7").await.unwrap();
+        assert!(last_seen.is_some_and(|timestamp| timestamp > 0));
+    }
 }
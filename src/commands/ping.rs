@@ -1,10 +1,56 @@
 //! Ping command for bot health check.
 
 use crate::types::{Context, Error};
+use std::time::{Duration, Instant};
 
-/// Simple ping command to check if the bot is responsive.
+/// Format the measured latencies for the `/ping` reply.
+///
+/// A zero heartbeat means the shard hasn't completed a heartbeat yet.
+fn format_latency(heartbeat: Duration, round_trip: Duration) -> String {
+    let heartbeat = if heartbeat.is_zero() {
+        "not measured yet".to_string()
+    } else {
+        format!("{} ms", heartbeat.as_millis())
+    };
+
+    format!(
+        "Pong! 🏓\n\
+        **Gateway heartbeat:** {}\n\
+        **Round trip:** {} ms",
+        heartbeat,
+        round_trip.as_millis()
+    )
+}
+
+/// Check that the bot is responsive and report its latency.
+///
+/// Shows the gateway heartbeat latency of the current shard and the time taken
+/// to answer the interaction.
 #[poise::command(slash_command)]
 pub async fn ping(context: Context<'_>) -> Result<(), Error> {
-    context.say("Pong! 🏓").await?;
+    let received = Instant::now();
+    let reply = context.say("Pinging…").await?;
+    let round_trip = received.elapsed();
+
+    let heartbeat = context.ping().await;
+    reply
+        .edit(context, poise::CreateReply::default().content(format_latency(heartbeat, round_trip)))
+        .await?;
+
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_latency() {
+        let message = format_latency(Duration::from_millis(42), Duration::from_millis(180));
+        assert!(message.contains("**Gateway heartbeat:** 42 ms"));
+        assert!(message.contains("**Round trip:** 180 ms"));
+
+        let message = format_latency(Duration::ZERO, Duration::from_millis(95));
+        assert!(message.contains("**Gateway heartbeat:** not measured yet"));
+    }
+}
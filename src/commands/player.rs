@@ -0,0 +1,27 @@
+//! Player lookup shared by commands.
+
+use crate::database::MinecraftPlayer;
+use crate::types::{Data, Error, ResponseMessage};
+use crate::utils::validation::validate_minecraft_username;
+
+/// Resolve a username to a player.
+///
+/// Known players are resolved from the database, others through Mojang.
+/// Invalid or unknown names resolve to the failure reply to send instead.
+pub async fn resolve_player(data: &Data, name: &str) -> Result<Result<MinecraftPlayer, ResponseMessage>, Error> {
+    if let Err(e) = validate_minecraft_username(name) {
+        return Ok(Err(ResponseMessage::Failure(format!("❌ {}", e))));
+    }
+
+    if let Some(player) = data.player_repository().get_player_by_username(name).await? {
+        return Ok(Ok(player));
+    }
+
+    match data.mojang_cache.fetch_profile_cached(&data.mojang_client, name).await {
+        Ok(Some(profile)) => Ok(Ok(MinecraftPlayer { uuid: profile.id, username: profile.name })),
+        Ok(None) => Ok(Err(ResponseMessage::Failure(
+            "❌ Player not found! Make sure the username is correct.".to_string()
+        ))),
+        Err(e) => Ok(Err(ResponseMessage::Failure(format!("❌ Failed to look up player: {}", e)))),
+    }
+}
@@ -0,0 +1,205 @@
+//! Player count history chart command.
+//!
+//! Renders recorded status history as a PNG line chart of online players.
+
+use crate::types::{Context, Error};
+use crate::checks::is_admin_check;
+use crate::commands::online::requested_server_address;
+use crate::database::StatusSample;
+use crate::error::OxideVaultError;
+use crate::utils::{format_duration, Granularity};
+use plotters::prelude::*;
+use poise::serenity_prelude as serenity;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Chart dimensions in pixels.
+const CHART_WIDTH: u32 = 800;
+const CHART_HEIGHT: u32 = 400;
+
+/// Convert a plotting or encoding failure into a crate error.
+fn render_error(e: impl std::fmt::Display) -> OxideVaultError {
+    OxideVaultError::Io(std::io::Error::other(format!("Failed to render chart: {}", e)))
+}
+
+/// Render online player counts over time as a PNG line chart.
+///
+/// # Arguments
+///
+/// * `samples` - Status samples, oldest first
+///
+/// # Errors
+///
+/// Returns an error if fewer than two samples are given or rendering fails.
+pub fn render_player_chart(samples: &[StatusSample]) -> crate::error::Result<Vec<u8>> {
+    let (first, last) = match (samples.first(), samples.last()) {
+        (Some(first), Some(last)) if samples.len() >= 2 => (first.timestamp, last.timestamp),
+        _ => return Err(OxideVaultError::Validation(
+            "At least two samples are needed to draw a chart".to_string()
+        )),
+    };
+
+    // Plot hours since the first sample; avoid an empty range when every
+    // sample shares a timestamp
+    let hours_since_first = |timestamp: i64| (timestamp - first) as f64 / 3600.0;
+    let x_range = 0.0..hours_since_first(last).max(1.0 / 60.0);
+    let y_max = samples
+        .iter()
+        .map(|sample| sample.online.max(sample.max))
+        .max()
+        .unwrap_or(0)
+        .max(1);
+
+    let mut pixels = vec![0u8; (CHART_WIDTH * CHART_HEIGHT * 3) as usize];
+    {
+        let root = BitMapBackend::with_buffer(&mut pixels, (CHART_WIDTH, CHART_HEIGHT))
+            .into_drawing_area();
+        root.fill(&WHITE).map_err(render_error)?;
+
+        let mut chart = ChartBuilder::on(&root)
+            .margin(20)
+            .build_cartesian_2d(x_range, 0u32..u32::from(y_max) + 1)
+            .map_err(render_error)?;
+
+        chart
+            .configure_mesh()
+            .disable_axes()
+            .light_line_style(WHITE)
+            .draw()
+            .map_err(render_error)?;
+
+        chart
+            .draw_series(LineSeries::new(
+                samples
+                    .iter()
+                    .map(|sample| (hours_since_first(sample.timestamp), u32::from(sample.online))),
+                BLUE.stroke_width(2),
+            ))
+            .map_err(render_error)?;
+
+        root.present().map_err(render_error)?;
+    }
+
+    let mut png_bytes = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut png_bytes, CHART_WIDTH, CHART_HEIGHT);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(render_error)?;
+        writer.write_image_data(&pixels).map_err(render_error)?;
+    }
+
+    Ok(png_bytes)
+}
+
+/// Summarize the charted samples as text.
+fn format_summary(samples: &[StatusSample], range: Duration) -> String {
+    let peak = samples.iter().map(|sample| sample.online).max().unwrap_or(0);
+    let average = samples.iter().map(|sample| u64::from(sample.online)).sum::<u64>() as f64
+        / samples.len().max(1) as f64;
+
+    format!(
+        "📈 **Player count over the last {}**\n\
+        **Samples:** {}\n\
+        **Peak:** {}\n\
+        **Average:** {:.1}",
+        format_duration(range, Granularity::Hours),
+        samples.len(),
+        peak,
+        average
+    )
+}
+
+/// Show a chart of online players over time.
+#[poise::command(slash_command, check = "is_admin_check")]
+pub async fn playercount(
+    context: Context<'_>,
+    #[description = "How many hours of history to show (default 24)"]
+    #[min = 1]
+    #[max = 720]
+    hours: Option<u32>,
+    #[description = "Server name (defaults to this server's configured address)"]
+    server: Option<String>,
+) -> Result<(), Error> {
+    context.defer().await?;
+
+    let range = Duration::from_secs(u64::from(hours.unwrap_or(24)) * 3600);
+    let until = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() as i64);
+    let since = until - range.as_secs() as i64;
+
+    let data = context.data();
+    let guild_id = context.guild_id().map(|id| id.get());
+    let address = match requested_server_address(data, server.as_deref(), guild_id).await {
+        Ok(address) => address,
+        Err(reply) => return reply.send(context).await,
+    };
+    let samples = data
+        .status_history_repository()
+        .get_status_history(&address, since, until)
+        .await?;
+
+    if samples.len() < 2 {
+        context
+            .say("❌ Not enough data to draw a chart yet. Player counts are recorded each time the server is checked.")
+            .await?;
+        return Ok(());
+    }
+
+    let summary = format_summary(&samples, range);
+    let png_bytes = tokio::task::spawn_blocking(move || render_player_chart(&samples)).await??;
+
+    context
+        .send(
+            poise::CreateReply::default()
+                .content(summary)
+                .attachment(serenity::CreateAttachment::bytes(png_bytes, "playercount.png")),
+        )
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(timestamp: i64, online: u16) -> StatusSample {
+        StatusSample { timestamp, online, max: 20 }
+    }
+
+    #[test]
+    fn test_render_player_chart_produces_png() {
+        let samples: Vec<_> = (0..48).map(|i| sample(i * 1800, (i % 12) as u16)).collect();
+
+        let png_bytes = render_player_chart(&samples).unwrap();
+        assert!(png_bytes.starts_with(&[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]));
+
+        let decoder = png::Decoder::new(png_bytes.as_slice());
+        let mut reader = decoder.read_info().unwrap();
+        assert_eq!(reader.info().width, CHART_WIDTH);
+        assert_eq!(reader.info().height, CHART_HEIGHT);
+
+        // The line was actually drawn on the white background
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        reader.next_frame(&mut pixels).unwrap();
+        assert!(pixels.chunks(3).any(|rgb| rgb != [255, 255, 255]));
+    }
+
+    #[test]
+    fn test_render_player_chart_needs_two_samples() {
+        assert!(render_player_chart(&[]).is_err());
+        assert!(render_player_chart(&[sample(0, 1)]).is_err());
+        assert!(render_player_chart(&[sample(5, 1), sample(5, 2)]).is_ok());
+    }
+
+    #[test]
+    fn test_format_summary() {
+        let samples = vec![sample(0, 2), sample(60, 4), sample(120, 6)];
+        let summary = format_summary(&samples, Duration::from_secs(24 * 3600));
+        assert!(summary.contains("last 1 day"));
+        assert!(summary.contains("**Samples:** 3"));
+        assert!(summary.contains("**Peak:** 6"));
+        assert!(summary.contains("**Average:** 4.0"));
+    }
+}
@@ -0,0 +1,80 @@
+//! Play time command.
+//!
+//! Reports a player's total play time from the `playtime_seconds` stat.
+
+use crate::types::{Context, Data, Error, ResponseMessage};
+use crate::commands::autocomplete::autocomplete_username;
+use crate::commands::player::resolve_player;
+use crate::database::PlayerStat;
+use crate::minecraft_stats::PLAYTIME_SECONDS_STAT;
+use crate::utils::format_compact;
+use std::time::Duration;
+
+/// Format the `/playtime` reply from a player's play time stat.
+fn format_playtime(name: &str, stat: Option<&PlayerStat>) -> ResponseMessage {
+    match stat {
+        Some(stat) => ResponseMessage::Success(format!(
+            "⏱️ **{}** has played for {} (as of <t:{}:R>)",
+            name,
+            format_compact(Duration::from_secs(stat.stat_value.max(0) as u64)),
+            stat.timestamp
+        )),
+        None => ResponseMessage::Failure(format!("❌ Play time isn't tracked for **{}**.", name)),
+    }
+}
+
+/// Build the `/playtime` reply for the given username.
+pub async fn playtime_response(data: &Data, name: &str) -> Result<ResponseMessage, Error> {
+    let player = match resolve_player(data, name).await? {
+        Ok(player) => player,
+        Err(message) => return Ok(message),
+    };
+
+    let stat = data.player_repository().get_stat(&player.uuid, PLAYTIME_SECONDS_STAT).await?;
+    Ok(format_playtime(&player.username, stat.as_ref()))
+}
+
+/// Show how long a player has played on the server.
+#[poise::command(slash_command)]
+pub async fn playtime(
+    context: Context<'_>,
+    #[description = "Minecraft username"]
+    #[autocomplete = "autocomplete_username"]
+    #[min_length = 1]
+    #[max_length = 16]
+    name: String,
+) -> Result<(), Error> {
+    context.defer().await?;
+
+    playtime_response(context.data(), &name).await?.send(context).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stat(stat_value: i64) -> PlayerStat {
+        PlayerStat {
+            mc_uuid: "069a79f444e94726a5befca90e38aaf5".to_string(),
+            stat_name: PLAYTIME_SECONDS_STAT.to_string(),
+            stat_value,
+            timestamp: 1_700_000_000,
+        }
+    }
+
+    #[test]
+    fn test_format_playtime() {
+        assert_eq!(
+            format_playtime("Notch", Some(&stat(3 * 86_400 + 4 * 3_600 + 5 * 60 + 6))),
+            ResponseMessage::Success("⏱️ **Notch** has played for 3d 4h 5m (as of <t:1700000000:R>)".to_string())
+        );
+        assert_eq!(
+            format_playtime("Notch", Some(&stat(-5))).content(),
+            "⏱️ **Notch** has played for 0m (as of <t:1700000000:R>)"
+        );
+        assert_eq!(
+            format_playtime("Alex", None),
+            ResponseMessage::Failure("❌ Play time isn't tracked for **Alex**.".to_string())
+        );
+    }
+}
@@ -0,0 +1,99 @@
+//! Server address resolution command.
+//!
+//! Shows how the configured Minecraft server address resolves, for diagnostics.
+
+use crate::types::{Context, Error};
+use crate::checks::is_admin_check;
+use crate::mc_server::{resolve_server, DnsResolver, ResolvedServer, Socks5Proxy};
+
+/// Format a resolution result for display.
+fn format_resolution(resolved: &ResolvedServer, proxy: Option<&Socks5Proxy>) -> String {
+    let mut lines = vec![format!(
+        "🔎 **Resolution for** `{}:{}`",
+        resolved.host, resolved.port
+    )];
+
+    match &resolved.srv {
+        Some(srv) => lines.push(format!("**SRV record:** `{}:{}`", srv.host, srv.port)),
+        None => lines.push("**SRV record:** none".to_string()),
+    }
+
+    lines.push(format!("**Resolved addresses ({}):**", resolved.addresses.len()));
+    lines.extend(resolved.addresses.iter().map(|addr| format!("• `{}`", addr.ip())));
+
+    if let Some(target) = resolved.connect_target() {
+        lines.push(format!("**Connect target:** `{}`", target));
+    }
+
+    if let Some(proxy) = proxy {
+        lines.push(format!(
+            "ℹ️ Pings are routed through the SOCKS5 proxy at `{}`, which resolves the host itself.",
+            proxy.address
+        ));
+    }
+
+    lines.join("\n")
+}
+
+/// Show the IP addresses and SRV record behind the configured server address.
+#[poise::command(slash_command, check = "is_admin_check")]
+pub async fn resolve(context: Context<'_>) -> Result<(), Error> {
+    context.defer().await?;
+
+    let guild_id = context.guild_id().map(|id| id.get());
+    let address = context.data().server_address_for(guild_id).await;
+
+    match resolve_server(&DnsResolver, &address).await {
+        Ok(resolved) => {
+            let proxy = context.data().mc_socks5_proxy.as_ref();
+            context.say(format_resolution(&resolved, proxy)).await?;
+        }
+        Err(e) => {
+            context
+                .say(format!("❌ Failed to resolve `{}`: {}", address, e))
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mc_server::SrvTarget;
+
+    #[test]
+    fn test_format_resolution_multiple_addresses() {
+        let resolved = ResolvedServer {
+            host: "example.com".to_string(),
+            port: 25565,
+            srv: Some(SrvTarget { host: "node1.example.net".to_string(), port: 25570 }),
+            addresses: vec![
+                "198.51.100.7:25570".parse().unwrap(),
+                "[2001:db8::7]:25570".parse().unwrap(),
+            ],
+        };
+
+        let message = format_resolution(&resolved, None);
+        assert!(message.contains("`example.com:25565`"));
+        assert!(message.contains("**SRV record:** `node1.example.net:25570`"));
+        assert!(message.contains("**Resolved addresses (2):**\n• `198.51.100.7`\n• `2001:db8::7`"));
+        assert!(message.ends_with("**Connect target:** `198.51.100.7:25570`"));
+    }
+
+    #[test]
+    fn test_format_resolution_with_proxy() {
+        let resolved = ResolvedServer {
+            host: "127.0.0.1".to_string(),
+            port: 25565,
+            srv: None,
+            addresses: vec!["127.0.0.1:25565".parse().unwrap()],
+        };
+        let proxy = Socks5Proxy::parse("proxy.example.com:1080").unwrap();
+
+        let message = format_resolution(&resolved, Some(&proxy));
+        assert!(message.contains("**SRV record:** none"));
+        assert!(message.contains("`proxy.example.com:1080`"));
+    }
+}
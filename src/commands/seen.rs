@@ -0,0 +1,80 @@
+//! Last-seen command.
+//!
+//! Reports when a player was last seen online on the server.
+
+use crate::types::{Context, Data, Error, ResponseMessage};
+use crate::commands::autocomplete::autocomplete_username;
+use crate::commands::player::resolve_player;
+use crate::utils::format_relative;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Format the `/seen` reply from a player's last-seen Unix timestamp.
+///
+/// The absolute time uses Discord's timestamp markup, so it is shown in each
+/// reader's own timezone.
+fn format_seen(name: &str, last_seen: Option<i64>, now: i64) -> ResponseMessage {
+    match last_seen {
+        Some(timestamp) => {
+            let elapsed = Duration::from_secs(now.saturating_sub(timestamp).max(0) as u64);
+            ResponseMessage::Success(format!(
+                "👀 **{}** was last seen {} (<t:{}:F>)",
+                name,
+                format_relative(elapsed),
+                timestamp
+            ))
+        }
+        None => ResponseMessage::Failure(format!("❌ **{}** has never been seen on the server.", name)),
+    }
+}
+
+/// Build the `/seen` reply for the given username.
+pub async fn seen_response(data: &Data, name: &str) -> Result<ResponseMessage, Error> {
+    let player = match resolve_player(data, name).await? {
+        Ok(player) => player,
+        Err(message) => return Ok(message),
+    };
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() as i64);
+    let last_seen = data.player_repository().get_last_seen(&player.uuid).await?;
+
+    Ok(format_seen(&player.username, last_seen, now))
+}
+
+/// Show when a player was last seen online.
+#[poise::command(slash_command)]
+pub async fn seen(
+    context: Context<'_>,
+    #[description = "Minecraft username"]
+    #[autocomplete = "autocomplete_username"]
+    #[min_length = 1]
+    #[max_length = 16]
+    name: String,
+) -> Result<(), Error> {
+    context.defer().await?;
+
+    seen_response(context.data(), &name).await?.send(context).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_seen() {
+        let now = 1_700_000_000;
+        assert_eq!(
+            format_seen("Steve", Some(now - 2 * 86_400), now),
+            ResponseMessage::Success("👀 **Steve** was last seen 2 days ago (<t:1699827200:F>)".to_string())
+        );
+        assert_eq!(
+            format_seen("Steve", Some(now + 10), now),
+            ResponseMessage::Success(format!("👀 **Steve** was last seen just now (<t:{}:F>)", now + 10))
+        );
+        assert_eq!(
+            format_seen("Alex", None, now),
+            ResponseMessage::Failure("❌ **Alex** has never been seen on the server.".to_string())
+        );
+    }
+}
@@ -0,0 +1,151 @@
+//! Server info command.
+//!
+//! Shows the Minecraft server's status as a rich embed.
+
+use crate::types::{Context, Error};
+use crate::error::Result;
+use crate::mc_server::{ServerStatus, StatusSource};
+use poise::serenity_prelude as serenity;
+
+/// Embed color for a reachable server.
+const COLOR_ONLINE: u32 = 0x2ECC71;
+/// Embed color for an unreachable server.
+const COLOR_OFFLINE: u32 = 0xE74C3C;
+
+/// File name the favicon is attached under.
+const FAVICON_FILE: &str = "favicon.png";
+
+/// Contents of the `/serverinfo` embed, independent of Discord types.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerInfo {
+    pub title: String,
+    pub description: Option<String>,
+    pub color: u32,
+    /// Inline `(name, value)` fields
+    pub fields: Vec<(String, String)>,
+    /// Decoded favicon PNG, shown as the thumbnail
+    pub favicon: Option<Vec<u8>>,
+}
+
+/// Build the embed contents from a ping result.
+fn server_info(result: Result<ServerStatus>) -> ServerInfo {
+    match result {
+        Ok(status) => {
+            // The first MOTD line is the title; any further lines the description
+            let motd = status.description.to_plain();
+            let (title, rest) = motd.split_once('\n').unwrap_or((&motd, ""));
+            let title = match title.trim() {
+                "" => "Minecraft Server",
+                title => title,
+            };
+            let description = Some(rest.trim().to_string()).filter(|rest| !rest.is_empty());
+            let latency = status
+                .latency_ms
+                .map_or_else(|| "Unknown".to_string(), |ms| format!("{}ms", ms));
+
+            ServerInfo {
+                title: format!("🟢 {}", title),
+                description,
+                color: COLOR_ONLINE,
+                fields: vec![
+                    ("Version".to_string(), status.version.name.clone()),
+                    ("Protocol".to_string(), status.version.protocol.to_string()),
+                    ("Players".to_string(), format!("{}/{}", status.players.online, status.players.max)),
+                    ("Latency".to_string(), latency),
+                ],
+                favicon: status.favicon_png(),
+            }
+        }
+        Err(e) => ServerInfo {
+            title: "🔴 Server unreachable".to_string(),
+            description: Some(format!("Failed to connect to server: {}", e)),
+            color: COLOR_OFFLINE,
+            fields: Vec::new(),
+            favicon: None,
+        },
+    }
+}
+
+/// Ping the server and build the embed contents.
+pub async fn server_info_response<S: StatusSource>(source: &S) -> ServerInfo {
+    server_info(source.fetch_status().await)
+}
+
+/// Show the Minecraft server's status as an embed.
+#[poise::command(slash_command)]
+pub async fn serverinfo(context: Context<'_>) -> std::result::Result<(), Error> {
+    context.defer().await?;
+
+    let guild_id = context.guild_id().map(|id| id.get());
+    let source = context.data().status_source(guild_id).await;
+    let info = server_info_response(&source).await;
+
+    let mut embed = serenity::CreateEmbed::new()
+        .title(info.title)
+        .color(info.color);
+    if let Some(description) = info.description {
+        embed = embed.description(description);
+    }
+    for (name, value) in info.fields {
+        embed = embed.field(name, value, true);
+    }
+
+    let mut reply = poise::CreateReply::default();
+    if let Some(favicon) = info.favicon {
+        embed = embed.thumbnail(format!("attachment://{}", FAVICON_FILE));
+        reply = reply.attachment(serenity::CreateAttachment::bytes(favicon, FAVICON_FILE));
+    }
+
+    context.send(reply.embed(embed)).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::OxideVaultError;
+
+    #[test]
+    fn test_server_info_online() {
+        let status = ServerStatus::builder()
+            .version("1.21.4", 769)
+            .players(3, 20)
+            .description("§aMy Server\nCome play!")
+            .favicon("data:image/png;base64,iVBORw==")
+            .latency(42)
+            .build();
+
+        let info = server_info(Ok(status));
+        assert_eq!(info.title, "🟢 My Server");
+        assert_eq!(info.description.as_deref(), Some("Come play!"));
+        assert_eq!(info.color, COLOR_ONLINE);
+        assert_eq!(info.fields, vec![
+            ("Version".to_string(), "1.21.4".to_string()),
+            ("Protocol".to_string(), "769".to_string()),
+            ("Players".to_string(), "3/20".to_string()),
+            ("Latency".to_string(), "42ms".to_string()),
+        ]);
+        assert_eq!(info.favicon, Some(vec![0x89, b'P', b'N', b'G']));
+    }
+
+    #[test]
+    fn test_server_info_empty_motd() {
+        let info = server_info(Ok(ServerStatus::builder().description("").build()));
+        assert_eq!(info.title, "🟢 Minecraft Server");
+        assert_eq!(info.description, None);
+        assert_eq!(info.fields[3].1, "Unknown");
+        assert_eq!(info.favicon, None);
+    }
+
+    #[test]
+    fn test_server_info_unreachable() {
+        let info = server_info(Err(OxideVaultError::ServerProtocol("Connection failed".to_string())));
+        assert_eq!(info.title, "🔴 Server unreachable");
+        assert_eq!(info.color, COLOR_OFFLINE);
+        assert!(info.fields.is_empty());
+        assert_eq!(
+            info.description.as_deref(),
+            Some("Failed to connect to server: Server protocol error: Connection failed")
+        );
+    }
+}
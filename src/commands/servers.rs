@@ -0,0 +1,91 @@
+//! Server network status command.
+//!
+//! Pings every server configured in `MC_SERVERS` at once, for networks of
+//! several servers.
+
+use crate::types::{Context, Error, ResponseMessage};
+use crate::error::Result;
+use crate::mc_server::{ping_servers_with, ServerStatus, DEFAULT_PING_CONCURRENCY};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Render one server's ping result.
+fn format_server_line(name: &str, address: &str, result: &Result<ServerStatus>) -> String {
+    match result {
+        Ok(status) => format!(
+            "🟢 **{}** ({}): {}/{} players, {}",
+            name, address, status.players.online, status.players.max, status.version.name
+        ),
+        Err(e) => format!("🔴 **{}** ({}): {}", name, address, e),
+    }
+}
+
+/// Ping all `servers` (name to address) concurrently and list their status,
+/// sorted by name.
+///
+/// Each ping is bounded by `timeout`, so one unreachable server doesn't hold
+/// up the others.
+pub async fn servers_response(servers: &HashMap<String, String>, timeout: Duration) -> ResponseMessage {
+    let mut named: Vec<(&String, &String)> = servers.iter().collect();
+    named.sort_unstable();
+
+    let addresses: Vec<String> = named.iter().map(|(_, address)| address.to_string()).collect();
+    let results = ping_servers_with(&addresses, DEFAULT_PING_CONCURRENCY, timeout).await;
+
+    let lines: Vec<String> = named
+        .iter()
+        .zip(&results)
+        .map(|((name, _), (address, result))| format_server_line(name, address, result))
+        .collect();
+
+    ResponseMessage::Success(format!("🌐 **Servers**\n{}", lines.join("\n")))
+}
+
+/// Check the status of every configured Minecraft server at once.
+///
+/// Servers are pinged directly, even when `MC_SOCKS5_PROXY` is set.
+#[poise::command(slash_command)]
+pub async fn servers(context: Context<'_>) -> std::result::Result<(), Error> {
+    context.defer().await?;
+
+    let data = context.data();
+    servers_response(&data.live().mc_servers, data.ping_timeout)
+        .await
+        .send(context)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::OxideVaultError;
+
+    #[test]
+    fn test_format_server_line() {
+        let status = ServerStatus::builder().version("1.21.4", 769).players(3, 20).build();
+        assert_eq!(
+            format_server_line("survival", "mc.example.com:25565", &Ok(status)),
+            "🟢 **survival** (mc.example.com:25565): 3/20 players, 1.21.4"
+        );
+
+        let error = Err(OxideVaultError::ServerProtocol("Connection refused".to_string()));
+        assert_eq!(
+            format_server_line("creative", "creative.example.com:25565", &error),
+            "🔴 **creative** (creative.example.com:25565): Server protocol error: Connection refused"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_servers_response_sorted_by_name() {
+        let servers = HashMap::from([
+            ("survival".to_string(), "127.0.0.1:1".to_string()),
+            ("creative".to_string(), "127.0.0.1:2".to_string()),
+        ]);
+
+        let response = servers_response(&servers, Duration::from_secs(2)).await;
+        let lines: Vec<&str> = response.content().lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].starts_with("🔴 **creative** (127.0.0.1:2)"));
+        assert!(lines[2].starts_with("🔴 **survival** (127.0.0.1:1)"));
+    }
+}
@@ -0,0 +1,77 @@
+//! Guild server selection command.
+//!
+//! Lets administrators choose the Minecraft server their guild monitors.
+
+use crate::types::{Context, Error, ResponseMessage};
+use crate::checks::is_admin_check;
+use crate::config::Config;
+use std::collections::HashMap;
+
+/// Resolve a `/setserver` argument to a server address.
+///
+/// Names configured in `MC_SERVERS` (case-insensitive) map to their address;
+/// anything else must be a valid "host:port" address.
+fn resolve_server_argument(input: &str, named_servers: &HashMap<String, String>) -> crate::error::Result<String> {
+    let input = input.trim();
+    if let Some(address) = named_servers.get(&input.to_lowercase()) {
+        return Ok(address.clone());
+    }
+
+    Config::validate_server_address(input)?;
+    Ok(input.to_string())
+}
+
+/// Set the Minecraft server monitored by this guild.
+///
+/// Accepts a "host:port" address or the name of a configured server. Run
+/// without a server to go back to the bot's global default.
+#[poise::command(slash_command, guild_only, check = "is_admin_check")]
+pub async fn setserver(
+    context: Context<'_>,
+    #[description = "Server address (host:port) or configured server name; omit to reset"]
+    server: Option<String>,
+) -> Result<(), Error> {
+    let guild_id = match context.guild_id() {
+        Some(id) => id.get(),
+        None => {
+            context.say("❌ This command can only be used in a server.").await?;
+            return Ok(());
+        }
+    };
+
+    let data = context.data();
+    let settings = data.live();
+    let address = match server.as_deref().map(|server| resolve_server_argument(server, &settings.mc_servers)) {
+        Some(Ok(address)) => Some(address),
+        Some(Err(e)) => {
+            return ResponseMessage::Failure(format!("❌ {}", e)).send(context).await;
+        }
+        None => None,
+    };
+
+    data.guild_repository().set_server_address(guild_id, address.clone()).await?;
+
+    let message = match address {
+        Some(address) => format!("✅ This server now monitors **{}**.", address),
+        None => format!(
+            "✅ Server reset. This server now monitors the default, **{}**.",
+            settings.mc_server_address
+        ),
+    };
+    ResponseMessage::Success(message).send(context).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_server_argument() {
+        let named = HashMap::from([("survival".to_string(), "survival.example.com:25565".to_string())]);
+
+        assert_eq!(resolve_server_argument("Survival", &named).unwrap(), "survival.example.com:25565");
+        assert_eq!(resolve_server_argument(" mc.example.com:25566 ", &named).unwrap(), "mc.example.com:25566");
+        assert!(resolve_server_argument("creative", &named).is_err());
+        assert!(resolve_server_argument("mc.example.com:99999", &named).is_err());
+    }
+}
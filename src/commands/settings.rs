@@ -0,0 +1,198 @@
+//! Guild settings command.
+//!
+//! Lets administrators view and change the settings stored for their guild.
+
+use crate::types::{Context, Error};
+use crate::checks::is_admin_check;
+use crate::config::Config;
+use crate::database::{GuildSettingKey, GuildSettings};
+use crate::utils::validation::validate_subfolder;
+use poise::serenity_prelude as serenity;
+
+/// A setting `/settings` can reset to the global default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, poise::ChoiceParameter)]
+pub enum ResetSetting {
+    #[name = "Status channel"]
+    StatusChannel,
+    #[name = "Server address"]
+    ServerAddress,
+    #[name = "Locale"]
+    Locale,
+    #[name = "Backup folder"]
+    BackupFolder,
+}
+
+impl ResetSetting {
+    /// Whether `update` also gives this setting a new value.
+    fn is_set_in(self, update: &GuildSettings) -> bool {
+        match self {
+            Self::StatusChannel => update.status_channel.is_some(),
+            Self::ServerAddress => update.server_address.is_some(),
+            Self::Locale => update.locale.is_some(),
+            Self::BackupFolder => update.backup_folder.is_some(),
+        }
+    }
+}
+
+impl From<ResetSetting> for GuildSettingKey {
+    fn from(setting: ResetSetting) -> Self {
+        match setting {
+            ResetSetting::StatusChannel => Self::StatusChannel,
+            ResetSetting::ServerAddress => Self::ServerAddress,
+            ResetSetting::Locale => Self::Locale,
+            ResetSetting::BackupFolder => Self::BackupFolder,
+        }
+    }
+}
+
+/// Format guild settings for display, showing global fallbacks for unset values.
+fn format_settings(settings: &GuildSettings, default_server: &str, default_backup_folder: &str) -> String {
+    let status_channel = settings
+        .status_channel
+        .map_or_else(|| "not set".to_string(), |id| format!("<#{}>", id));
+    let server_address = settings
+        .server_address
+        .clone()
+        .unwrap_or_else(|| format!("{} (global default)", default_server));
+    let locale = settings.locale.as_deref().unwrap_or("not set");
+    let backup_folder = settings
+        .backup_folder
+        .as_ref()
+        .map_or_else(
+            || format!("{} (global default)", default_backup_folder),
+            |folder| format!("{}/{}", default_backup_folder.trim_end_matches('/'), folder),
+        );
+
+    format!(
+        "⚙️ **Guild Settings**\n\
+        **Status channel:** {}\n\
+        **Server address:** {}\n\
+        **Locale:** {}\n\
+        **Backup folder:** {}",
+        status_channel, server_address, locale, backup_folder
+    )
+}
+
+/// View or change this guild's bot settings.
+///
+/// Run without options to view the current settings. Unset values fall back to
+/// the bot's global configuration, and `reset` clears a setting back to it.
+#[poise::command(slash_command, guild_only, check = "is_admin_check")]
+pub async fn settings(
+    context: Context<'_>,
+    #[description = "Channel for status announcements"]
+    status_channel: Option<serenity::GuildChannel>,
+    #[description = "Minecraft server address (host:port)"]
+    server_address: Option<String>,
+    #[description = "Preferred locale (e.g. en-US)"]
+    #[max_length = 16]
+    locale: Option<String>,
+    #[description = "Backup folder, relative to the global backup folder"]
+    backup_folder: Option<String>,
+    #[description = "Setting to reset to the global default"]
+    reset: Option<ResetSetting>,
+) -> Result<(), Error> {
+    let guild_id = match context.guild_id() {
+        Some(id) => id.get(),
+        None => {
+            context.say("❌ This command can only be used in a server.").await?;
+            return Ok(());
+        }
+    };
+
+    if let Some(address) = &server_address {
+        if let Err(e) = Config::validate_server_address(address) {
+            context.say(format!("❌ {}", e)).await?;
+            return Ok(());
+        }
+    }
+
+    if let Some(folder) = &backup_folder {
+        if let Err(e) = validate_subfolder(folder) {
+            context.say(format!("❌ {}", e)).await?;
+            return Ok(());
+        }
+    }
+
+    let update = GuildSettings {
+        guild_id,
+        status_channel: status_channel.map(|channel| channel.id.get()),
+        server_address,
+        locale,
+        backup_folder,
+    };
+
+    if reset.is_some_and(|setting| setting.is_set_in(&update)) {
+        context.say("❌ A setting can't be changed and reset at the same time.").await?;
+        return Ok(());
+    }
+
+    let repo = context.data().guild_repository();
+    let updated = update.status_channel.is_some()
+        || update.server_address.is_some()
+        || update.locale.is_some()
+        || update.backup_folder.is_some();
+
+    if updated {
+        repo.upsert_guild_settings(update).await?;
+    }
+    if let Some(setting) = reset {
+        repo.clear_guild_setting(guild_id, setting.into()).await?;
+    }
+    let changed = updated || reset.is_some();
+
+    let settings = repo
+        .get_guild_settings(guild_id)
+        .await?
+        .unwrap_or(GuildSettings { guild_id, ..Default::default() });
+
+    let live = context.data().live();
+    let mut message = format_settings(&settings, &live.mc_server_address, &live.backup_folder);
+    if changed {
+        message.insert_str(0, "✅ Settings updated.\n");
+    }
+
+    context.say(message).await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reset_setting_is_set_in() {
+        let update = GuildSettings {
+            guild_id: 1,
+            locale: Some("fr".to_string()),
+            ..Default::default()
+        };
+        assert!(ResetSetting::Locale.is_set_in(&update));
+        assert!(!ResetSetting::StatusChannel.is_set_in(&update));
+        assert!(!ResetSetting::BackupFolder.is_set_in(&update));
+    }
+
+    #[test]
+    fn test_format_settings() {
+        let settings = GuildSettings {
+            guild_id: 1,
+            status_channel: Some(42),
+            server_address: Some("mc.example.com:25565".to_string()),
+            locale: Some("fr".to_string()),
+            backup_folder: Some("survival".to_string()),
+        };
+        let message = format_settings(&settings, "localhost:25565", "/backups");
+        assert!(message.contains("**Status channel:** <#42>"));
+        assert!(message.contains("**Server address:** mc.example.com:25565"));
+        assert!(message.contains("**Locale:** fr"));
+        assert!(message.contains("**Backup folder:** /backups/survival"));
+
+        let empty = GuildSettings { guild_id: 1, ..Default::default() };
+        let message = format_settings(&empty, "localhost:25565", "/backups");
+        assert!(message.contains("**Status channel:** not set"));
+        assert!(message.contains("**Server address:** localhost:25565 (global default)"));
+        assert!(message.contains("**Locale:** not set"));
+        assert!(message.contains("**Backup folder:** /backups (global default)"));
+    }
+}
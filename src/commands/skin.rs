@@ -0,0 +1,149 @@
+//! Skin command.
+//!
+//! Shows a rendered view of a Minecraft player's skin.
+
+use crate::types::{Context, Data, Error, ResponseMessage};
+use crate::commands::autocomplete::autocomplete_username;
+use crate::utils::validation::validate_minecraft_username;
+use crate::mojang::{MojangProfile, PlayerTextures, SkinModel};
+use poise::serenity_prelude as serenity;
+
+/// Base URL of the skin render service.
+const RENDER_BASE: &str = "https://crafatar.com/renders/body";
+
+/// A player's skin, ready to be shown in an embed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkinCard {
+    pub name: String,
+    /// Undashed UUID
+    pub uuid: String,
+    /// Rendered body image
+    pub image_url: String,
+    /// Raw skin texture, `None` for default skins
+    pub skin_url: Option<String>,
+    pub model: SkinModel,
+}
+
+/// Reply produced by `/skin`: a skin card, or a text message on failure.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SkinReply {
+    Card(SkinCard),
+    Message(ResponseMessage),
+}
+
+/// Build the skin card for a profile and its textures.
+///
+/// The render falls back to the default skin matching the player's model
+/// (Steve or Alex) when they have no custom skin.
+fn skin_card(profile: MojangProfile, textures: PlayerTextures) -> SkinCard {
+    let default_skin = match textures.model {
+        SkinModel::Classic => "MHF_Steve",
+        SkinModel::Slim => "MHF_Alex",
+    };
+
+    SkinCard {
+        image_url: format!("{}/{}?overlay&default={}", RENDER_BASE, profile.id, default_skin),
+        name: profile.name,
+        uuid: profile.id,
+        skin_url: textures.skin_url,
+        model: textures.model,
+    }
+}
+
+/// Build the `/skin` reply for the given username.
+pub async fn skin_response(data: &Data, name: &str) -> Result<SkinReply, Error> {
+    if let Err(e) = validate_minecraft_username(name) {
+        return Ok(SkinReply::Message(ResponseMessage::Failure(format!("❌ {}", e))));
+    }
+
+    let profile = match data.mojang_cache.fetch_profile_cached(&data.mojang_client, name).await {
+        Ok(Some(profile)) => profile,
+        Ok(None) => {
+            return Ok(SkinReply::Message(ResponseMessage::Failure(
+                "❌ Player not found! Make sure the username is correct.".to_string()
+            )));
+        }
+        Err(e) => {
+            return Ok(SkinReply::Message(ResponseMessage::Failure(
+                format!("❌ Failed to look up player: {}", e)
+            )));
+        }
+    };
+
+    match data.mojang_client.fetch_textures(&profile.id).await {
+        Ok(textures) => Ok(SkinReply::Card(skin_card(profile, textures))),
+        Err(e) => Ok(SkinReply::Message(ResponseMessage::Failure(
+            format!("❌ Failed to fetch skin: {}", e)
+        ))),
+    }
+}
+
+/// Show a Minecraft player's skin.
+#[poise::command(slash_command)]
+pub async fn skin(
+    context: Context<'_>,
+    #[description = "Minecraft username"]
+    #[autocomplete = "autocomplete_username"]
+    #[min_length = 1]
+    #[max_length = 16]
+    name: String,
+) -> Result<(), Error> {
+    context.defer().await?;
+
+    let card = match skin_response(context.data(), &name).await? {
+        SkinReply::Card(card) => card,
+        SkinReply::Message(message) => return message.send(context).await,
+    };
+
+    let model = match card.model {
+        SkinModel::Classic => "Classic (Steve)",
+        SkinModel::Slim => "Slim (Alex)",
+    };
+    let description = match &card.skin_url {
+        Some(url) => format!("**Model:** {}\n[Download skin]({})", model, url),
+        None => format!("**Model:** {}\nUsing the default skin", model),
+    };
+
+    let embed = serenity::CreateEmbed::new()
+        .title(format!("🎨 {}", card.name))
+        .description(description)
+        .image(card.image_url);
+
+    context.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile() -> MojangProfile {
+        serde_json::from_str(r#"{"id":"069a79f444e94726a5befca90e38aaf5","name":"Notch"}"#).unwrap()
+    }
+
+    #[test]
+    fn test_skin_card_custom_skin() {
+        let textures = PlayerTextures {
+            skin_url: Some("http://textures.minecraft.net/texture/abc".to_string()),
+            cape_url: None,
+            model: SkinModel::Classic,
+        };
+
+        let card = skin_card(profile(), textures);
+        assert_eq!(card.name, "Notch");
+        assert_eq!(
+            card.image_url,
+            "https://crafatar.com/renders/body/069a79f444e94726a5befca90e38aaf5?overlay&default=MHF_Steve"
+        );
+        assert_eq!(card.skin_url.as_deref(), Some("http://textures.minecraft.net/texture/abc"));
+    }
+
+    #[test]
+    fn test_skin_card_default_slim_skin() {
+        let textures = PlayerTextures { skin_url: None, cape_url: None, model: SkinModel::Slim };
+
+        let card = skin_card(profile(), textures);
+        assert!(card.image_url.ends_with("default=MHF_Alex"));
+        assert_eq!(card.skin_url, None);
+    }
+}
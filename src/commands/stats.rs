@@ -0,0 +1,143 @@
+//! Player statistics command.
+//!
+//! Lists every statistic recorded for a player in the `player_stats` table.
+
+use crate::types::{Context, Data, Error, ResponseMessage};
+use crate::commands::autocomplete::autocomplete_username;
+use crate::commands::player::resolve_player;
+use crate::database::PlayerStat;
+use poise::serenity_prelude as serenity;
+
+/// Maximum number of fields Discord allows in one embed.
+const MAX_FIELDS: usize = 25;
+
+/// Contents of the `/stats` embed, independent of Discord types.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatsCard {
+    pub name: String,
+    /// `(stat_name, stat_value)` fields, sorted by name
+    pub fields: Vec<(String, String)>,
+    /// Stats left out because of the embed field limit
+    pub omitted: usize,
+    /// Most recent stat timestamp (Unix seconds)
+    pub updated_at: i64,
+}
+
+/// Reply produced by `/stats`: a stats card, or a text message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StatsReply {
+    Card(StatsCard),
+    Message(ResponseMessage),
+}
+
+/// Build the `/stats` reply from a player's recorded stats.
+fn stats_reply(name: &str, mut stats: Vec<PlayerStat>) -> StatsReply {
+    let Some(updated_at) = stats.iter().map(|stat| stat.timestamp).max() else {
+        return StatsReply::Message(ResponseMessage::Failure(
+            format!("❌ No stats are recorded for **{}**.", name)
+        ));
+    };
+
+    stats.sort_by(|a, b| a.stat_name.cmp(&b.stat_name));
+    let omitted = stats.len().saturating_sub(MAX_FIELDS);
+    let fields = stats
+        .into_iter()
+        .take(MAX_FIELDS)
+        .map(|stat| (stat.stat_name, stat.stat_value.to_string()))
+        .collect();
+
+    StatsReply::Card(StatsCard { name: name.to_string(), fields, omitted, updated_at })
+}
+
+/// Build the `/stats` reply for the given username.
+pub async fn stats_response(data: &Data, name: &str) -> Result<StatsReply, Error> {
+    let player = match resolve_player(data, name).await? {
+        Ok(player) => player,
+        Err(message) => return Ok(StatsReply::Message(message)),
+    };
+
+    let stats = data.player_repository().get_stats_for_player(&player.uuid).await?;
+    Ok(stats_reply(&player.username, stats))
+}
+
+/// Show all statistics recorded for a player.
+#[poise::command(slash_command)]
+pub async fn stats(
+    context: Context<'_>,
+    #[description = "Minecraft username"]
+    #[autocomplete = "autocomplete_username"]
+    #[min_length = 1]
+    #[max_length = 16]
+    name: String,
+) -> Result<(), Error> {
+    context.defer().await?;
+
+    let card = match stats_response(context.data(), &name).await? {
+        StatsReply::Card(card) => card,
+        StatsReply::Message(message) => return message.send(context).await,
+    };
+
+    let mut description = format!("Last updated <t:{}:R>", card.updated_at);
+    if card.omitted > 0 {
+        description.push_str(&format!("\n+{} more not shown", card.omitted));
+    }
+
+    let mut embed = serenity::CreateEmbed::new()
+        .title(format!("📊 {}", card.name))
+        .description(description);
+    for (name, value) in card.fields {
+        embed = embed.field(name, value, true);
+    }
+
+    context.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stat(stat_name: &str, stat_value: i64, timestamp: i64) -> PlayerStat {
+        PlayerStat {
+            mc_uuid: "069a79f444e94726a5befca90e38aaf5".to_string(),
+            stat_name: stat_name.to_string(),
+            stat_value,
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn test_stats_reply() {
+        let reply = stats_reply("Notch", vec![stat("kills", 12, 300), stat("deaths", 4, 500)]);
+        assert_eq!(
+            reply,
+            StatsReply::Card(StatsCard {
+                name: "Notch".to_string(),
+                fields: vec![
+                    ("deaths".to_string(), "4".to_string()),
+                    ("kills".to_string(), "12".to_string()),
+                ],
+                omitted: 0,
+                updated_at: 500,
+            })
+        );
+
+        assert_eq!(
+            stats_reply("Alex", Vec::new()),
+            StatsReply::Message(ResponseMessage::Failure("❌ No stats are recorded for **Alex**.".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_stats_reply_caps_fields() {
+        let stats = (0..MAX_FIELDS + 3).map(|i| stat(&format!("stat_{:02}", i), i as i64, 1)).collect();
+        match stats_reply("Notch", stats) {
+            StatsReply::Card(card) => {
+                assert_eq!(card.fields.len(), MAX_FIELDS);
+                assert_eq!(card.omitted, 3);
+                assert_eq!(card.fields[0].0, "stat_00");
+            }
+            other => panic!("Expected a stats card, got {:?}", other),
+        }
+    }
+}
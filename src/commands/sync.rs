@@ -0,0 +1,252 @@
+//! Player sync command.
+//!
+//! Pulls the currently online players from the Minecraft server into the database,
+//! optionally refreshing their usernames from the Mojang API.
+
+use crate::types::{Context, Error};
+use crate::checks::is_admin_check;
+use crate::database::{MinecraftPlayer, PlayerRepository};
+use crate::mc_server::StatusSource;
+use crate::mojang::MojangClient;
+
+/// Summary of a completed player sync.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SyncReport {
+    /// Players that were not in the database before
+    pub added: usize,
+    /// Known players whose username changed
+    pub updated: usize,
+    /// Known players that were already up to date
+    pub unchanged: usize,
+    /// Players whose Mojang refresh failed (the server-provided name was kept)
+    pub refresh_failures: usize,
+}
+
+/// Outcome of a sync attempt.
+#[derive(Debug)]
+pub enum SyncOutcome {
+    /// The server could not be reached, so nothing was synced
+    ServerOffline(String),
+    /// The sync ran to completion
+    Completed(SyncReport),
+}
+
+/// Synchronize all currently online players into the database.
+///
+/// Pings the server through `source` and upserts every player in the status sample.
+/// When `mojang_client` is provided, each player's username and UUID are refreshed
+/// from the Mojang API before being stored.
+///
+/// # Errors
+///
+/// Returns an error if a database operation fails. An unreachable server is not an
+/// error and is reported as [`SyncOutcome::ServerOffline`].
+pub async fn run_sync<S: StatusSource>(
+    source: &S,
+    repo: &PlayerRepository,
+    mojang_client: Option<&MojangClient>,
+) -> crate::error::Result<SyncOutcome> {
+    let status = match source.fetch_status().await {
+        Ok(status) => status,
+        Err(e) => return Ok(SyncOutcome::ServerOffline(e.to_string())),
+    };
+
+    let mut report = SyncReport::default();
+
+    // Placeholder entries from servers hiding their player list aren't real players
+    for sample in status.players.sample.iter().filter(|sample| !sample.is_anonymous()) {
+        let mut player = MinecraftPlayer {
+            uuid: sample.id.replace('-', ""),
+            username: sample.name.clone(),
+        };
+
+        if let Some(client) = mojang_client {
+            match client.fetch_profile(&sample.name).await {
+                Ok(Some(profile)) => {
+                    player.uuid = profile.id;
+                    player.username = profile.name;
+                }
+                Ok(None) | Err(_) => report.refresh_failures += 1,
+            }
+        }
+
+        match repo.get_player_by_uuid(&player.uuid).await? {
+            None => report.added += 1,
+            Some(existing) if existing.username != player.username => report.updated += 1,
+            Some(_) => report.unchanged += 1,
+        }
+
+        repo.upsert_player(player).await?;
+    }
+
+    Ok(SyncOutcome::Completed(report))
+}
+
+/// Format a sync report for display in Discord.
+fn format_report(report: &SyncReport) -> String {
+    let mut message = format!(
+        "✅ **Player sync complete**\n\
+        **Added:** {}\n\
+        **Updated:** {}\n\
+        **Unchanged:** {}",
+        report.added, report.updated, report.unchanged
+    );
+
+    if report.refresh_failures > 0 {
+        message.push_str(&format!(
+            "\n⚠️ Could not refresh {} player{} from Mojang.",
+            report.refresh_failures,
+            if report.refresh_failures == 1 { "" } else { "s" }
+        ));
+    }
+
+    message
+}
+
+/// Sync all online players into the database.
+///
+/// Restricted to administrators since it may issue many Mojang API requests.
+#[poise::command(slash_command, check = "is_admin_check")]
+pub async fn sync(
+    context: Context<'_>,
+    #[description = "Refresh usernames from the Mojang API"]
+    refresh_usernames: Option<bool>,
+) -> Result<(), Error> {
+    let progress = context.say("🔄 Syncing online players...").await?;
+
+    let guild_id = context.guild_id().map(|id| id.get());
+    let source = context.data().status_source(guild_id).await;
+    let repo = context.data().player_repository();
+    let mojang_client = refresh_usernames
+        .unwrap_or(false)
+        .then_some(&context.data().mojang_client);
+
+    let message = match run_sync(&source, &repo, mojang_client).await? {
+        SyncOutcome::ServerOffline(reason) => {
+            format!("❌ Server is offline, nothing was synced: {}", reason)
+        }
+        SyncOutcome::Completed(report) => format_report(&report),
+    };
+
+    progress
+        .edit(context, poise::CreateReply::default().content(message))
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::init_db;
+    use crate::error::OxideVaultError;
+    use crate::mc_server::ServerStatus;
+    use tempfile::TempDir;
+
+    /// Status source returning a fixed result.
+    struct MockStatusSource {
+        status: Option<ServerStatus>,
+    }
+
+    impl StatusSource for MockStatusSource {
+        async fn fetch_status(&self) -> crate::error::Result<ServerStatus> {
+            self.status
+                .clone()
+                .ok_or_else(|| OxideVaultError::ServerProtocol("Connection failed".to_string()))
+        }
+    }
+
+    fn status_with_players(players: &[(&str, &str)]) -> ServerStatus {
+        players
+            .iter()
+            .fold(ServerStatus::builder(), |builder, (name, id)| builder.player(name, id))
+            .players(players.len() as u16, 20)
+            .build()
+    }
+
+    async fn setup_test_db() -> (TempDir, PlayerRepository) {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let db_path = temp_dir.path().join("test.db");
+        let db_path_str = db_path.to_str().expect("Invalid path").to_string();
+
+        init_db(&db_path_str).await.expect("Failed to initialize database");
+
+        (temp_dir, PlayerRepository::new(db_path_str))
+    }
+
+    #[tokio::test]
+    async fn test_run_sync_reports_counts() {
+        let (_temp_dir, repo) = setup_test_db().await;
+
+        // One player already known under their current name, one under an old name
+        repo.upsert_player(MinecraftPlayer {
+            uuid: "550e8400e29b41d4a716446655440000".to_string(),
+            username: "Alice".to_string(),
+        }).await.unwrap();
+        repo.upsert_player(MinecraftPlayer {
+            uuid: "550e8400e29b41d4a716446655440001".to_string(),
+            username: "OldBob".to_string(),
+        }).await.unwrap();
+
+        let source = MockStatusSource {
+            status: Some(status_with_players(&[
+                ("Alice", "550e8400-e29b-41d4-a716-446655440000"),
+                ("Bob", "550e8400-e29b-41d4-a716-446655440001"),
+                ("Charlie", "550e8400-e29b-41d4-a716-446655440002"),
+            ])),
+        };
+
+        let outcome = run_sync(&source, &repo, None).await.unwrap();
+        match outcome {
+            SyncOutcome::Completed(report) => {
+                assert_eq!(report, SyncReport { added: 1, updated: 1, unchanged: 1, refresh_failures: 0 });
+            }
+            SyncOutcome::ServerOffline(_) => panic!("Expected a completed sync"),
+        }
+
+        let players = repo.get_all_players().await.unwrap();
+        let names: Vec<&str> = players.iter().map(|p| p.username.as_str()).collect();
+        assert_eq!(names, vec!["Alice", "Bob", "Charlie"]);
+    }
+
+    #[tokio::test]
+    async fn test_run_sync_offline_server_syncs_nothing() {
+        let (_temp_dir, repo) = setup_test_db().await;
+        let source = MockStatusSource { status: None };
+
+        let outcome = run_sync(&source, &repo, None).await.unwrap();
+        assert!(matches!(outcome, SyncOutcome::ServerOffline(_)));
+        assert!(repo.get_all_players().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_run_sync_skips_anonymous_players() {
+        let (_temp_dir, repo) = setup_test_db().await;
+        let source = MockStatusSource {
+            status: Some(status_with_players(&[
+                ("Anonymous Player", "00000000-0000-0000-0000-000000000000"),
+                ("Alice", "550e8400-e29b-41d4-a716-446655440000"),
+            ])),
+        };
+
+        let outcome = run_sync(&source, &repo, None).await.unwrap();
+        assert!(matches!(outcome, SyncOutcome::Completed(SyncReport { added: 1, .. })));
+
+        let players = repo.get_all_players().await.unwrap();
+        assert_eq!(players.len(), 1);
+        assert_eq!(players[0].username, "Alice");
+    }
+
+    #[test]
+    fn test_format_report() {
+        let report = SyncReport { added: 2, updated: 1, unchanged: 0, refresh_failures: 1 };
+        let message = format_report(&report);
+        assert!(message.contains("**Added:** 2"));
+        assert!(message.contains("**Updated:** 1"));
+        assert!(message.contains("**Unchanged:** 0"));
+        assert!(message.contains("Could not refresh 1 player from Mojang."));
+
+        let report = SyncReport { added: 0, updated: 0, unchanged: 3, refresh_failures: 0 };
+        assert!(!format_report(&report).contains("Mojang"));
+    }
+}
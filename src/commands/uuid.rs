@@ -1,63 +1,138 @@
 //! UUID lookup command.
 //!
-//! Allows users to look up Minecraft player UUIDs by username.
+//! Allows users to look up Minecraft player UUIDs by username, or compute the
+//! UUID an offline-mode server would assign.
 
-use crate::types::{Context, Error};
-use crate::mojang;
+use crate::types::{Context, Data, Error, ResponseMessage};
+use crate::commands::autocomplete::autocomplete_username;
 use crate::utils::validation::{validate_minecraft_username, format_uuid};
 use crate::database::MinecraftPlayer;
+use crate::mojang::offline_uuid;
+use crate::error::OxideVaultError;
+use crate::utils::{format_duration, Granularity};
+use std::time::Duration;
 
-/// Look up a Minecraft player's UUID by their username.
+/// Minimum time between `/uuid` lookups by the same user, protecting the shared
+/// Mojang API quota.
+pub const UUID_USER_COOLDOWN: Duration = Duration::from_secs(5);
+
+/// Describe a failed Mojang lookup for the user.
+fn lookup_error_message(e: &OxideVaultError) -> String {
+    match e {
+        OxideVaultError::RateLimited { retry_after: Some(delay) } => {
+            format!("⏳ Rate limited by Mojang, try again in {}s", delay.as_secs().max(1))
+        }
+        OxideVaultError::RateLimited { retry_after: None } => {
+            "⏳ Rate limited by Mojang, try again later".to_string()
+        }
+        e => format!("❌ Failed to connect to Mojang API: {}", e),
+    }
+}
+
+/// Describe the offline-mode UUID of a player.
+fn offline_uuid_message(name: &str) -> String {
+    format!("✅ **Player:** {}\n**Offline UUID:** `{}`", name, offline_uuid(name))
+}
+
+/// Build the `/uuid` reply for the given user and username.
 ///
-/// This command queries the Mojang API and optionally stores the result in the database.
-#[poise::command(slash_command)]
-pub async fn uuid(
-    context: Context<'_>,
-    #[description = "Minecraft username"]
-    #[min_length = 1]
-    #[max_length = 16]
-    name: String,
-) -> Result<(), Error> {
+/// With `offline`, the offline-mode UUID is computed locally and no cooldown
+/// applies. Otherwise each user may look up a player once per
+/// [`UUID_USER_COOLDOWN`], and found players are stored in the database
+/// (non-fatal if that fails).
+pub async fn uuid_response(data: &Data, user_id: u64, name: &str, offline: bool) -> Result<ResponseMessage, Error> {
     // Validate username format
-    if let Err(e) = validate_minecraft_username(&name) {
-        context
-            .say(format!("❌ {}", e))
-            .await?;
-        return Ok(());
+    if let Err(e) = validate_minecraft_username(name) {
+        return Ok(ResponseMessage::Failure(format!("❌ {}", e)));
     }
 
-    context.defer().await?;
+    if offline {
+        return Ok(ResponseMessage::Success(offline_uuid_message(name)));
+    }
 
-    match mojang::fetch_profile(&context.data().http_client, &name).await {
+    if let Err(remaining) = data.uuid_cooldowns.check_and_update(user_id, UUID_USER_COOLDOWN, Duration::ZERO) {
+        // Round up so the reply never says "0 seconds"
+        let remaining = Duration::from_secs(remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0));
+        return Ok(ResponseMessage::Failure(format!(
+            "⏳ Please wait {} before looking up another player.",
+            format_duration(remaining, Granularity::Seconds)
+        )));
+    }
+
+    let response = match data.mojang_cache.fetch_profile_cached(&data.mojang_client, name).await {
         Ok(Some(profile)) => {
             // Try to store in database (non-fatal if it fails)
-            let repo = context.data().player_repository();
+            let repo = data.player_repository();
             let _ = repo.upsert_player(MinecraftPlayer {
                 uuid: profile.id.clone(),
                 username: profile.name.clone(),
             }).await;
 
-            if let Some(formatted_uuid) = format_uuid(&profile.id) {
-                context
-                    .say(format!("✅ **Player:** {}\n**UUID:** `{}`", profile.name, formatted_uuid))
-                    .await?;
-            } else {
-                context
-                    .say("❌ Unexpected UUID format returned from Mojang API.")
-                    .await?;
+            match format_uuid(&profile.id) {
+                Some(formatted_uuid) => ResponseMessage::Success(
+                    format!("✅ **Player:** {}\n**UUID:** `{}`", profile.name, formatted_uuid)
+                ),
+                None => ResponseMessage::Failure(
+                    "❌ Unexpected UUID format returned from Mojang API.".to_string()
+                ),
             }
         }
-        Ok(None) => {
-            context
-                .say("❌ Player not found! Make sure the username is correct.")
-                .await?;
-        }
-        Err(e) => {
-            context
-                .say(format!("❌ Failed to connect to Mojang API: {}", e))
-                .await?;
-        }
+        Ok(None) => ResponseMessage::Failure(
+            "❌ Player not found! Make sure the username is correct.".to_string()
+        ),
+        Err(e) => ResponseMessage::Failure(lookup_error_message(&e)),
+    };
+
+    Ok(response)
+}
+
+/// Look up a Minecraft player's UUID by their username.
+///
+/// This command queries the Mojang API and optionally stores the result in the database.
+/// With `offline`, it instead computes the UUID an offline-mode server would use.
+#[poise::command(slash_command)]
+pub async fn uuid(
+    context: Context<'_>,
+    #[description = "Minecraft username"]
+    #[autocomplete = "autocomplete_username"]
+    #[min_length = 1]
+    #[max_length = 16]
+    name: String,
+    #[description = "Compute the offline-mode UUID instead of querying Mojang"]
+    offline: Option<bool>,
+) -> Result<(), Error> {
+    context.defer().await?;
+
+    uuid_response(context.data(), context.author().id.get(), &name, offline.unwrap_or(false))
+        .await?
+        .send(context)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup_error_message() {
+        let limited = OxideVaultError::RateLimited { retry_after: Some(Duration::from_secs(42)) };
+        assert_eq!(lookup_error_message(&limited), "⏳ Rate limited by Mojang, try again in 42s");
+
+        let limited = OxideVaultError::RateLimited { retry_after: None };
+        assert_eq!(lookup_error_message(&limited), "⏳ Rate limited by Mojang, try again later");
+
+        let other = OxideVaultError::MojangApi("API returned error: 500".to_string());
+        assert_eq!(
+            lookup_error_message(&other),
+            "❌ Failed to connect to Mojang API: Mojang API error: API returned error: 500"
+        );
     }
 
-    Ok(())
+    #[test]
+    fn test_offline_uuid_message() {
+        assert_eq!(
+            offline_uuid_message("Notch"),
+            "✅ **Player:** Notch\n**Offline UUID:** `b50ad385-829d-3141-a216-7e7d7539ba7f`"
+        );
+    }
 }
@@ -0,0 +1,61 @@
+//! Linked account command.
+//!
+//! Shows the Minecraft account linked to the caller's Discord account.
+
+use crate::types::{Context, Data, Error, ResponseMessage};
+use crate::database::AccountLink;
+use crate::utils::validation::format_uuid;
+
+/// Format the `/whoami` reply from the caller's link, if any.
+fn format_whoami(link: Option<&AccountLink>) -> ResponseMessage {
+    match link {
+        Some(link) => ResponseMessage::Success(format!(
+            "🔗 You are linked to **{}**\n**UUID:** `{}`\n**Linked:** <t:{}:F>",
+            link.username,
+            format_uuid(&link.mc_uuid).unwrap_or_else(|| link.mc_uuid.clone()),
+            link.linked_at
+        )),
+        None => ResponseMessage::Failure(
+            "❌ Your Discord account isn't linked to a Minecraft account. Use `/link` to link one.".to_string()
+        ),
+    }
+}
+
+/// Build the `/whoami` reply for the given Discord user.
+pub async fn whoami_response(data: &Data, discord_id: u64) -> Result<ResponseMessage, Error> {
+    let link = data.player_repository().get_link(discord_id).await?;
+    Ok(format_whoami(link.as_ref()))
+}
+
+/// Show the Minecraft account linked to your Discord account.
+#[poise::command(slash_command)]
+pub async fn whoami(context: Context<'_>) -> Result<(), Error> {
+    context.defer().await?;
+
+    whoami_response(context.data(), context.author().id.get())
+        .await?
+        .send(context)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_whoami() {
+        let link = AccountLink {
+            mc_uuid: "069a79f444e94726a5befca90e38aaf5".to_string(),
+            username: "Notch".to_string(),
+            linked_at: 1_700_000_000,
+        };
+        assert_eq!(
+            format_whoami(Some(&link)),
+            ResponseMessage::Success(
+                "🔗 You are linked to **Notch**\n**UUID:** `069a79f4-44e9-4726-a5be-fca90e38aaf5`\n**Linked:** <t:1700000000:F>"
+                    .to_string()
+            )
+        );
+        assert!(matches!(format_whoami(None), ResponseMessage::Failure(message) if message.contains("/link")));
+    }
+}
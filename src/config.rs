@@ -3,14 +3,81 @@
 //! This module handles loading and validating environment variables and application settings.
 
 use crate::error::{OxideVaultError, Result};
+use crate::mc_server::{split_host_port, Socks5Proxy, DEFAULT_MAX_PLAYER_SAMPLE, DEFAULT_PING_RETRIES, DEFAULT_PING_TIMEOUT};
+use crate::secrets::{CommandSecretProvider, SecretProvider};
+use crate::database::DEFAULT_POOL_TIMEOUT;
+use crate::http_server::{RATE_LIMIT_BURST, RATE_LIMIT_PER_SEC};
+use crate::mojang::{DEFAULT_CACHE_TTL, MOJANG_API_BASE, SESSION_SERVER_BASE};
+use crate::pl3xmap::RadiusLimits;
+use std::collections::HashMap;
 use std::env;
 use std::fs;
+use std::net::SocketAddr;
 use std::path::Path;
+use std::time::Duration;
 use url::Url;
 
 #[cfg(unix)]
 use std::os::unix::fs::MetadataExt;
 
+/// Default cooldown between backup publishes by anyone.
+pub const DEFAULT_BACKUP_GLOBAL_COOLDOWN: Duration = Duration::from_secs(2 * 60 * 60);
+
+/// Default cooldown between backup publishes by the same user.
+pub const DEFAULT_BACKUP_USER_COOLDOWN: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Default lifetime of published backup links.
+pub const DEFAULT_BACKUP_LINK_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Default file extensions considered backups, matched case-insensitively.
+pub const DEFAULT_BACKUP_EXTENSIONS: &[&str] = &["tgz", "tar.gz", "zip", "gz"];
+
+/// Default time `/online` serves a recent status instead of pinging again.
+pub const DEFAULT_STATUS_CACHE_TTL: Duration = Duration::from_secs(10);
+
+/// Default interval between background server status polls.
+pub const DEFAULT_STATUS_POLL_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Settings that may be given in a config file, by environment variable name.
+const CONFIG_FILE_KEYS: &[&str] = &[
+    "DISCORD_TOKEN",
+    "DISCORD_TOKEN_COMMAND",
+    "DB_PATH",
+    "DB_POOL_TIMEOUT_SECS",
+    "MC_SERVER_ADDRESS",
+    "MC_SERVERS",
+    "MC_SOCKS5_PROXY",
+    "MC_QUERY_ENABLED",
+    "BACKUP_FOLDER",
+    "BACKUP_PUBLISH_ROOT",
+    "BACKUP_PUBLIC_BASE_URL",
+    "PL3XMAP_MARKERS_PATH",
+    "ADMIN_USER_IDS",
+    "DISABLED_COMMANDS",
+    "MAX_PLAYER_SAMPLE",
+    "PING_LOG_PATH",
+    "MC_PING_TIMEOUT_SECS",
+    "PING_TIMEOUT_SECS",
+    "BACKUP_GLOBAL_COOLDOWN_SECS",
+    "BACKUP_USER_COOLDOWN_SECS",
+    "BACKUP_LINK_TTL_SECS",
+    "BACKUP_EXTENSIONS",
+    "PING_RETRIES",
+    "STATUS_POLL_INTERVAL_SECS",
+    "STATUS_CACHE_TTL_SECS",
+    "MOJANG_CACHE_TTL_SECS",
+    "MOJANG_API_URL",
+    "MOJANG_SESSION_URL",
+    "PLAYERDB_FALLBACK",
+    "MINECRAFT_STATS_DIR",
+    "DRAW_MAX_RADIUS",
+    "DRAW_SCALE_MAX_RADIUS",
+    "HTTP_BIND_ADDRESS",
+    "HTTP_RATE_LIMIT_BURST",
+    "HTTP_RATE_LIMIT_PER_SEC",
+    "HTTP_RATE_LIMIT_EXEMPT_LOOPBACK",
+];
+
 /// Configuration for the application, loaded from environment variables.
 #[derive(Debug, Clone)]
 pub struct Config {
@@ -18,14 +85,70 @@ pub struct Config {
     pub discord_token: String,
     /// Path to SQLite database file
     pub db_path: String,
-    /// Minecraft server address (host:port)
+    /// How long database operations wait for a free pooled connection
+    pub db_pool_timeout: Duration,
+    /// Default Minecraft server address (host:port)
     pub mc_server_address: String,
+    /// Named Minecraft server addresses (host:port), keyed by lowercase name
+    pub mc_servers: HashMap<String, String>,
+    /// Optional SOCKS5 proxy used to reach the Minecraft server
+    pub mc_socks5_proxy: Option<Socks5Proxy>,
+    /// Whether `/online` asks the server for its full player list over the
+    /// UDP Query protocol when the status only carries part of it
+    pub mc_query_enabled: bool,
     /// Path to the directory containing backup files
     pub backup_folder: String,
     /// Directory where backups are published for download (served by reverse proxy)
     pub backup_publish_root: String,
     /// Public URL base where published backups are served (must match reverse proxy)
     pub backup_public_base_url: String,
+    /// Optional Pl3xmap marker file that `/draw` appends markers to and
+    /// `/layer` collects them from
+    pub pl3xmap_markers_path: Option<String>,
+    /// Minimum time between backup publishes by anyone
+    pub backup_global_cooldown: Duration,
+    /// Minimum time between backup publishes by the same user
+    pub backup_user_cooldown: Duration,
+    /// How long published backup links are kept before being removed
+    pub backup_link_ttl: Duration,
+    /// File extensions (without leading dot, lowercase) considered backups
+    pub backup_extensions: Vec<String>,
+    /// Discord user IDs allowed to run admin commands without the ADMINISTRATOR permission
+    pub admin_user_ids: Vec<u64>,
+    /// Names of commands that should not be registered
+    pub disabled_commands: Vec<String>,
+    /// Maximum number of player sample entries kept from a server status
+    pub max_player_sample: usize,
+    /// Optional JSON Lines file that every server ping is appended to
+    pub ping_log_path: Option<String>,
+    /// Timeout for connecting to the Minecraft server and for each read and write
+    pub ping_timeout: Duration,
+    /// Number of times a failed ping is retried when the failure looks transient
+    pub ping_retries: u32,
+    /// Interval between background pings of `mc_server_address` recorded to the status history
+    pub status_poll_interval: Duration,
+    /// How long `/online` serves a recent status instead of pinging again (zero disables)
+    pub status_cache_ttl: Duration,
+    /// How long Mojang username lookups are cached
+    pub mojang_cache_ttl: Duration,
+    /// Base URL of the Mojang profile API (overridable for caching proxies)
+    pub mojang_api_url: String,
+    /// Base URL of the Mojang session server (overridable for caching proxies)
+    pub mojang_session_url: String,
+    /// Whether username lookups fall back to PlayerDB when the Mojang API fails
+    pub playerdb_fallback: bool,
+    /// Optional vanilla stats directory (`world/stats`) read by `/importstats`
+    pub minecraft_stats_dir: Option<String>,
+    /// Limits on the radius of shapes drawn with `/draw`
+    pub draw_limits: RadiusLimits,
+    /// Optional address of the HTTP server exposing `/health` and `/metrics`
+    pub http_bind_address: Option<SocketAddr>,
+    /// Requests a client may burst to the HTTP server before being rate limited
+    pub http_rate_limit_burst: u32,
+    /// Requests per second a client may make once its burst is used up
+    pub http_rate_limit_per_sec: f64,
+    /// Whether loopback clients bypass the HTTP rate limit
+    pub http_rate_limit_exempt_loopback: bool,
 }
 
 impl Config {
@@ -50,54 +173,272 @@ impl Config {
         // Load .env file if present (ignore errors - it's optional)
         dotenv::dotenv().ok();
 
-        let discord_token = env::var("DISCORD_TOKEN")
-            .map_err(|_| OxideVaultError::Config(
-                "Missing DISCORD_TOKEN environment variable. Set it in your environment or create a .env file (never commit this file).".to_string()
-            ))?;
+        Self::from_lookup(|name| env::var(name).ok())
+    }
 
-        let db_path = Self::get_db_path()?;
+    /// Load configuration from a TOML file, with environment variables taking
+    /// precedence over values from the file.
+    ///
+    /// Keys are the lowercase names of the environment variables (e.g.
+    /// `discord_token`, `mc_server_address`, `backup_folder`). Lists such as
+    /// `admin_user_ids` may be given as arrays, and `mc_servers` as a table of
+    /// names to addresses.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read or parsed, contains unknown
+    /// keys, or the resulting configuration is invalid.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        dotenv::dotenv().ok();
 
-        let mc_server_address = env::var("MC_SERVER_ADDRESS")
-            .map_err(|_| OxideVaultError::Config(
-                "Missing MC_SERVER_ADDRESS environment variable. Set it in your environment or .env file (e.g., MC_SERVER_ADDRESS=localhost:25565).".to_string()
-            ))?;
+        let contents = fs::read_to_string(path).map_err(|e| OxideVaultError::Config(
+            format!("Failed to read config file '{}': {}", path.display(), e)
+        ))?;
+        let file_values = Self::parse_file_values(&contents).map_err(|e| match e {
+            OxideVaultError::Config(msg) => OxideVaultError::Config(format!("{}: {}", path.display(), msg)),
+            other => other,
+        })?;
+
+        Self::from_lookup(|name| env::var(name).ok().or_else(|| file_values.get(name).cloned()))
+    }
+
+    /// Load configuration from the TOML file named by `OXIDEVAULT_CONFIG`,
+    /// or from the environment alone when it isn't set.
+    pub fn load() -> Result<Self> {
+        dotenv::dotenv().ok();
+
+        match env::var("OXIDEVAULT_CONFIG") {
+            Ok(path) if !path.trim().is_empty() => Self::from_file(Path::new(path.trim())),
+            _ => Self::from_env(),
+        }
+    }
+
+    /// Build the configuration from a lookup of setting values by environment
+    /// variable name.
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let var = |name: &str| lookup(name).ok_or(env::VarError::NotPresent);
+
+        // Prefer fetching the token from a secret store command (e.g. `pass show discord`)
+        let discord_token = match var("DISCORD_TOKEN_COMMAND") {
+            Ok(command) if !command.trim().is_empty() => {
+                CommandSecretProvider::new(command).fetch_secret()?
+            }
+            _ => var("DISCORD_TOKEN")
+                .map_err(|_| OxideVaultError::Config(
+                    "Missing DISCORD_TOKEN environment variable. Set it (or DISCORD_TOKEN_COMMAND) in your environment or create a .env file (never commit this file).".to_string()
+                ))?,
+        };
+        Self::validate_discord_token(&discord_token)?;
+
+        let db_path = Self::get_db_path(var("DB_PATH").ok())?;
 
-        // Validate server address format
-        Self::validate_server_address(&mc_server_address)?;
+        // Wait for a free database connection, in seconds
+        let db_pool_timeout = match var("DB_POOL_TIMEOUT_SECS") {
+            Ok(value) => Self::parse_positive_secs("DB_POOL_TIMEOUT_SECS", &value)?,
+            Err(_) => DEFAULT_POOL_TIMEOUT,
+        };
+
+        // A single MC_SERVER_ADDRESS and/or named servers in MC_SERVERS
+        let (mc_server_address, mc_servers) = Self::resolve_servers(
+            var("MC_SERVER_ADDRESS").ok().as_deref(),
+            var("MC_SERVERS").ok().as_deref(),
+        )?;
+
+        // Optional SOCKS5 proxy for server pings ([user:password@]host:port)
+        let mc_socks5_proxy = match var("MC_SOCKS5_PROXY") {
+            Ok(spec) if !spec.trim().is_empty() => Some(Socks5Proxy::parse(spec.trim())?),
+            _ => None,
+        };
+
+        // Full player lists over UDP Query (needs enable-query=true, disabled unless enabled)
+        let mc_query_enabled = match var("MC_QUERY_ENABLED") {
+            Ok(value) => Self::parse_bool("MC_QUERY_ENABLED", &value)?,
+            Err(_) => false,
+        };
 
         // Use /backups as the default when running in Docker unless overridden
-        let backup_folder = env::var("BACKUP_FOLDER").unwrap_or_else(|_| "/backups".to_string());
+        let backup_folder = var("BACKUP_FOLDER").unwrap_or_else(|_| "/backups".to_string());
 
         // Validate backup folder path (will error if the path is not absolute, missing, or not a directory)
         Self::validate_backup_folder(&backup_folder)?;
 
         // Where we publish downloadable backups (defaults to /backups/public)
-        let backup_publish_root = env::var("BACKUP_PUBLISH_ROOT").unwrap_or_else(|_| "/backups/public".to_string());
+        let backup_publish_root = var("BACKUP_PUBLISH_ROOT").unwrap_or_else(|_| "/backups/public".to_string());
         Self::validate_publish_root(&backup_publish_root)?;
         
         // Check if backup_folder and backup_publish_root are on different filesystems and warn if so
         Self::check_filesystem_compatibility(&backup_folder, &backup_publish_root);
 
         // Public URL base (must match your reverse proxy, e.g., https://drop.example.com/backups)
-        let backup_public_base_url = env::var("BACKUP_PUBLIC_BASE_URL")
+        let backup_public_base_url = var("BACKUP_PUBLIC_BASE_URL")
             .unwrap_or_else(|_| "http://localhost/backups".to_string());
         Self::validate_public_base_url(&backup_public_base_url)?;
 
+        // Optional Pl3xmap marker file written by /draw and collected into layers by /layer
+        let pl3xmap_markers_path = var("PL3XMAP_MARKERS_PATH")
+            .ok()
+            .map(|path| path.trim().to_string())
+            .filter(|path| !path.is_empty());
+
+        // Optional allowlist of Discord user IDs with admin access (comma-separated)
+        let admin_user_ids = match var("ADMIN_USER_IDS") {
+            Ok(ids) => Self::parse_admin_user_ids(&ids)?,
+            Err(_) => Vec::new(),
+        };
+
+        // Optional list of commands to leave unregistered (comma-separated)
+        let disabled_commands = var("DISABLED_COMMANDS")
+            .map(|names| Self::parse_command_list(&names))
+            .unwrap_or_default();
+
+        // Cap on player sample entries, guarding against servers reporting huge samples
+        let max_player_sample = match var("MAX_PLAYER_SAMPLE") {
+            Ok(value) => Self::parse_max_player_sample(&value)?,
+            Err(_) => DEFAULT_MAX_PLAYER_SAMPLE,
+        };
+
+        // Optional JSON Lines log of every server ping
+        let ping_log_path = var("PING_LOG_PATH")
+            .ok()
+            .map(|path| path.trim().to_string())
+            .filter(|path| !path.is_empty());
+
+        // Backup cooldowns, in seconds
+        let backup_global_cooldown = match var("BACKUP_GLOBAL_COOLDOWN_SECS") {
+            Ok(value) => Self::parse_positive_secs("BACKUP_GLOBAL_COOLDOWN_SECS", &value)?,
+            Err(_) => DEFAULT_BACKUP_GLOBAL_COOLDOWN,
+        };
+        let backup_user_cooldown = match var("BACKUP_USER_COOLDOWN_SECS") {
+            Ok(value) => Self::parse_positive_secs("BACKUP_USER_COOLDOWN_SECS", &value)?,
+            Err(_) => DEFAULT_BACKUP_USER_COOLDOWN,
+        };
+
+        // Lifetime of published backup links, in seconds
+        let backup_link_ttl = match var("BACKUP_LINK_TTL_SECS") {
+            Ok(value) => Self::parse_positive_secs("BACKUP_LINK_TTL_SECS", &value)?,
+            Err(_) => DEFAULT_BACKUP_LINK_TTL,
+        };
+
+        // File extensions considered backups (comma-separated)
+        let backup_extensions = match var("BACKUP_EXTENSIONS") {
+            Ok(value) => Self::parse_backup_extensions(&value)?,
+            Err(_) => DEFAULT_BACKUP_EXTENSIONS.iter().map(|ext| ext.to_string()).collect(),
+        };
+
+        // Timeout for server pings, in seconds (PING_TIMEOUT_SECS is also accepted)
+        let ping_timeout = Self::parse_ping_timeout(
+            var("MC_PING_TIMEOUT_SECS").ok(),
+            var("PING_TIMEOUT_SECS").ok(),
+        )?;
+
+        // Retries for pings failing with connection errors or timeouts
+        let ping_retries = match var("PING_RETRIES") {
+            Ok(value) => Self::parse_ping_retries(&value)?,
+            Err(_) => DEFAULT_PING_RETRIES,
+        };
+
+        // Interval between background status polls, in seconds
+        let status_poll_interval = match var("STATUS_POLL_INTERVAL_SECS") {
+            Ok(value) => Self::parse_positive_secs("STATUS_POLL_INTERVAL_SECS", &value)?,
+            Err(_) => DEFAULT_STATUS_POLL_INTERVAL,
+        };
+
+        // Time /online reuses a recent status, in seconds (0 disables caching)
+        let status_cache_ttl = match var("STATUS_CACHE_TTL_SECS") {
+            Ok(value) => Self::parse_non_negative_secs("STATUS_CACHE_TTL_SECS", &value)?,
+            Err(_) => DEFAULT_STATUS_CACHE_TTL,
+        };
+
+        // Lifetime of cached Mojang lookups, in seconds (0 disables caching)
+        let mojang_cache_ttl = match var("MOJANG_CACHE_TTL_SECS") {
+            Ok(value) => Self::parse_mojang_cache_ttl(&value)?,
+            Err(_) => DEFAULT_CACHE_TTL,
+        };
+
+        // Mojang endpoints, e.g. to go through a caching proxy
+        let mojang_api_url = Self::get_base_url("MOJANG_API_URL", var("MOJANG_API_URL").ok(), MOJANG_API_BASE)?;
+        let mojang_session_url = Self::get_base_url("MOJANG_SESSION_URL", var("MOJANG_SESSION_URL").ok(), SESSION_SERVER_BASE)?;
+
+        // Third-party fallback for username lookups (disabled unless enabled)
+        let playerdb_fallback = match var("PLAYERDB_FALLBACK") {
+            Ok(value) => Self::parse_bool("PLAYERDB_FALLBACK", &value)?,
+            Err(_) => false,
+        };
+
+        // Optional vanilla stats directory read by /importstats
+        let minecraft_stats_dir = var("MINECRAFT_STATS_DIR")
+            .ok()
+            .map(|path| path.trim().to_string())
+            .filter(|path| !path.is_empty());
+
+        // Radius caps for /draw (global and per map scale)
+        let draw_limits = RadiusLimits::from_values(
+            var("DRAW_MAX_RADIUS").ok().as_deref(),
+            var("DRAW_SCALE_MAX_RADIUS").ok().as_deref(),
+        )?;
+
+        // Optional HTTP server for health checks and metrics (disabled unless set)
+        let http_bind_address = match var("HTTP_BIND_ADDRESS") {
+            Ok(value) if !value.trim().is_empty() => Some(Self::parse_http_bind_address(&value)?),
+            _ => None,
+        };
+
+        // Per-IP token bucket for the HTTP server (loopback exempt unless disabled)
+        let http_rate_limit_burst = match var("HTTP_RATE_LIMIT_BURST") {
+            Ok(value) => Self::parse_rate_limit_burst(&value)?,
+            Err(_) => RATE_LIMIT_BURST,
+        };
+        let http_rate_limit_per_sec = match var("HTTP_RATE_LIMIT_PER_SEC") {
+            Ok(value) => Self::parse_rate_limit_per_sec(&value)?,
+            Err(_) => RATE_LIMIT_PER_SEC,
+        };
+        let http_rate_limit_exempt_loopback = match var("HTTP_RATE_LIMIT_EXEMPT_LOOPBACK") {
+            Ok(value) => Self::parse_bool("HTTP_RATE_LIMIT_EXEMPT_LOOPBACK", &value)?,
+            Err(_) => true,
+        };
+
         Ok(Self {
             discord_token,
             db_path,
+            db_pool_timeout,
             mc_server_address,
+            mc_servers,
+            mc_socks5_proxy,
+            mc_query_enabled,
             backup_folder,
             backup_publish_root,
             backup_public_base_url,
+            pl3xmap_markers_path,
+            backup_global_cooldown,
+            backup_user_cooldown,
+            backup_link_ttl,
+            backup_extensions,
+            admin_user_ids,
+            disabled_commands,
+            max_player_sample,
+            ping_log_path,
+            ping_timeout,
+            ping_retries,
+            status_poll_interval,
+            status_cache_ttl,
+            mojang_cache_ttl,
+            mojang_api_url,
+            mojang_session_url,
+            playerdb_fallback,
+            minecraft_stats_dir,
+            draw_limits,
+            http_bind_address,
+            http_rate_limit_burst,
+            http_rate_limit_per_sec,
+            http_rate_limit_exempt_loopback,
         })
     }
 
-    /// Get the database path from environment or use default.
-    fn get_db_path() -> Result<String> {
-        match env::var("DB_PATH") {
-            Ok(path) => Ok(path),
-            Err(_) => {
+    /// Use the configured database path, or the default under the current directory.
+    fn get_db_path(configured: Option<String>) -> Result<String> {
+        match configured {
+            Some(path) => Ok(path),
+            None => {
                 let mut path = env::current_dir()
                     .map_err(|e| OxideVaultError::Config(
                         format!("Failed to determine current directory: {}", e)
@@ -115,25 +456,309 @@ impl Config {
         }
     }
 
-    /// Validate that the server address has a valid format.
-    fn validate_server_address(address: &str) -> Result<()> {
-        if !address.contains(':') {
-            return Err(OxideVaultError::Config(
-                format!("Invalid MC_SERVER_ADDRESS format: '{}'. Expected 'host:port' format.", address)
-            ));
+    /// Validate that a Discord bot token has the expected structure: three
+    /// non-empty base64url segments separated by dots.
+    ///
+    /// Error messages never include the token itself.
+    pub(crate) fn validate_discord_token(token: &str) -> Result<()> {
+        let invalid = |reason: &str| {
+            Err(OxideVaultError::Config(format!(
+                "Invalid DISCORD_TOKEN: {}. Copy the bot token from the Discord developer portal (Bot → Reset Token).",
+                reason
+            )))
+        };
+
+        if token.starts_with("Bot ") {
+            return invalid("remove the 'Bot ' prefix");
+        }
+        if token.chars().any(char::is_whitespace) {
+            return invalid("it contains whitespace");
         }
 
-        // Try to parse port
-        if let Some((_, port_str)) = address.rsplit_once(':') {
-            port_str.parse::<u16>()
-                .map_err(|_| OxideVaultError::Config(
-                    format!("Invalid port in MC_SERVER_ADDRESS: '{}'", port_str)
-                ))?;
+        let segments: Vec<&str> = token.split('.').collect();
+        if segments.len() != 3 {
+            return invalid(&format!("expected 3 dot-separated segments, found {}", segments.len()));
         }
+        if segments.iter().any(|segment| segment.is_empty()) {
+            return invalid("a segment is empty (the token may be truncated)");
+        }
+        if !segments
+            .iter()
+            .all(|segment| segment.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_'))
+        {
+            return invalid("it contains characters outside the base64url alphabet");
+        }
+
+        Ok(())
+    }
+
+    /// Validate that the server address has a valid format.
+    pub fn validate_server_address(address: &str) -> Result<()> {
+        split_host_port(address).map_err(|e| OxideVaultError::Config(
+            format!("Invalid MC_SERVER_ADDRESS: {}", e)
+        ))?;
 
         Ok(())
     }
 
+    /// Work out the default server address and the named servers.
+    ///
+    /// `MC_SERVER_ADDRESS` is registered as the `default` server (unless
+    /// `MC_SERVERS` names one) and is the default address. Without it, the
+    /// `default` entry of `MC_SERVERS` is used, or failing that the first one.
+    fn resolve_servers(
+        address: Option<&str>,
+        servers: Option<&str>,
+    ) -> Result<(String, HashMap<String, String>)> {
+        let address = address.map(str::trim).filter(|address| !address.is_empty());
+        let servers = match servers.map(str::trim).filter(|servers| !servers.is_empty()) {
+            Some(servers) => Self::parse_mc_servers(servers)?,
+            None => Vec::new(),
+        };
+
+        let default_address = match address {
+            Some(address) => {
+                Self::validate_server_address(address)?;
+                address.to_string()
+            }
+            None => servers
+                .iter()
+                .find(|(name, _)| name == "default")
+                .or_else(|| servers.first())
+                .map(|(_, address)| address.clone())
+                .ok_or_else(|| OxideVaultError::Config(
+                    "Missing MC_SERVER_ADDRESS environment variable. Set it (or MC_SERVERS) in your environment or .env file (e.g., MC_SERVER_ADDRESS=localhost:25565).".to_string()
+                ))?,
+        };
+
+        let mut map: HashMap<String, String> = servers.into_iter().collect();
+        map.entry("default".to_string()).or_insert_with(|| default_address.clone());
+
+        Ok((default_address, map))
+    }
+
+    /// Parse named servers formatted as `name=host:port;name2=host2:port2`.
+    ///
+    /// Names are case-insensitive and stored lowercase; order is preserved.
+    fn parse_mc_servers(value: &str) -> Result<Vec<(String, String)>> {
+        let mut servers: Vec<(String, String)> = Vec::new();
+
+        for entry in value.split(';').map(str::trim).filter(|entry| !entry.is_empty()) {
+            let (name, address) = entry.split_once('=').ok_or_else(|| OxideVaultError::Config(
+                format!("Invalid MC_SERVERS entry: '{}'. Expected 'name=host:port'.", entry)
+            ))?;
+            let name = name.trim().to_lowercase();
+            let address = address.trim();
+
+            if name.is_empty() {
+                return Err(OxideVaultError::Config(
+                    format!("Missing server name in MC_SERVERS entry: '{}'", entry)
+                ));
+            }
+            if servers.iter().any(|(existing, _)| *existing == name) {
+                return Err(OxideVaultError::Config(
+                    format!("Duplicate server name in MC_SERVERS: '{}'", name)
+                ));
+            }
+            Self::validate_server_address(address)?;
+
+            servers.push((name, address.to_string()));
+        }
+
+        Ok(servers)
+    }
+
+    /// Parse a TOML config file into setting values keyed by environment
+    /// variable name.
+    ///
+    /// Arrays are joined with commas and the `mc_servers` table into the
+    /// `name=host:port;...` form, so the usual parsing applies afterwards.
+    fn parse_file_values(contents: &str) -> Result<HashMap<String, String>> {
+        let table: toml::Table = contents.parse().map_err(|e: toml::de::Error| OxideVaultError::Config(
+            format!("Invalid TOML: {}", e.message())
+        ))?;
+
+        let mut values = HashMap::new();
+        for (key, value) in table {
+            let name = key.to_uppercase();
+            if !CONFIG_FILE_KEYS.contains(&name.as_str()) {
+                return Err(OxideVaultError::Config(format!("Unknown config file key '{}'", key)));
+            }
+
+            let value = match (name.as_str(), value) {
+                ("MC_SERVERS", toml::Value::Table(servers)) => servers
+                    .into_iter()
+                    .map(|(server, address)| Ok(format!("{}={}", server, Self::toml_scalar(&key, address)?)))
+                    .collect::<Result<Vec<_>>>()?
+                    .join(";"),
+                (_, toml::Value::Array(items)) => items
+                    .into_iter()
+                    .map(|item| Self::toml_scalar(&key, item))
+                    .collect::<Result<Vec<_>>>()?
+                    .join(","),
+                (_, value) => Self::toml_scalar(&key, value)?,
+            };
+            values.insert(name, value);
+        }
+
+        Ok(values)
+    }
+
+    /// Convert a TOML string, integer, or boolean to its string form.
+    fn toml_scalar(key: &str, value: toml::Value) -> Result<String> {
+        match value {
+            toml::Value::String(value) => Ok(value),
+            toml::Value::Integer(value) => Ok(value.to_string()),
+            toml::Value::Boolean(value) => Ok(value.to_string()),
+            other => Err(OxideVaultError::Config(
+                format!("Unsupported value for '{}': expected a string or number, got {}", key, other.type_str())
+            )),
+        }
+    }
+
+    /// Parse a comma-separated list of Discord user IDs.
+    fn parse_admin_user_ids(ids: &str) -> Result<Vec<u64>> {
+        ids.split(',')
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .map(|id| id.parse::<u64>().map_err(|_| OxideVaultError::Config(
+                format!("Invalid Discord user ID in ADMIN_USER_IDS: '{}'", id)
+            )))
+            .collect()
+    }
+
+    /// Parse the maximum player sample size (must be at least 1).
+    fn parse_max_player_sample(value: &str) -> Result<usize> {
+        match value.trim().parse::<usize>() {
+            Ok(max) if max > 0 => Ok(max),
+            _ => Err(OxideVaultError::Config(
+                format!("MAX_PLAYER_SAMPLE must be a positive integer, got: '{}'", value)
+            )),
+        }
+    }
+
+    /// Parse the ping timeout in seconds (must be at least 1) from
+    /// `MC_PING_TIMEOUT_SECS`, falling back to the older `PING_TIMEOUT_SECS`.
+    fn parse_ping_timeout(value: Option<String>, fallback: Option<String>) -> Result<Duration> {
+        match (value, fallback) {
+            (Some(value), _) => Self::parse_positive_secs("MC_PING_TIMEOUT_SECS", &value),
+            (None, Some(value)) => Self::parse_positive_secs("PING_TIMEOUT_SECS", &value),
+            (None, None) => Ok(DEFAULT_PING_TIMEOUT),
+        }
+    }
+
+    /// Parse a duration in whole seconds (must be at least 1).
+    fn parse_positive_secs(var: &str, value: &str) -> Result<Duration> {
+        match value.trim().parse::<u64>() {
+            Ok(secs) if secs > 0 => Ok(Duration::from_secs(secs)),
+            _ => Err(OxideVaultError::Config(
+                format!("{} must be a positive integer, got: '{}'", var, value)
+            )),
+        }
+    }
+
+    /// Parse the number of ping retries.
+    fn parse_ping_retries(value: &str) -> Result<u32> {
+        value.trim().parse::<u32>().map_err(|_| OxideVaultError::Config(
+            format!("PING_RETRIES must be a non-negative integer, got: '{}'", value)
+        ))
+    }
+
+    /// Validate an optional HTTP(S) base URL, falling back to `default`.
+    fn get_base_url(var: &str, value: Option<String>, default: &str) -> Result<String> {
+        match value {
+            Some(value) if !value.trim().is_empty() => Self::parse_base_url(var, &value),
+            _ => Ok(default.to_string()),
+        }
+    }
+
+    /// Validate an HTTP(S) base URL, removing any trailing slash.
+    fn parse_base_url(var: &str, value: &str) -> Result<String> {
+        let value = value.trim();
+        match Url::parse(value) {
+            Ok(url) if matches!(url.scheme(), "http" | "https") => Ok(value.trim_end_matches('/').to_string()),
+            _ => Err(OxideVaultError::Config(
+                format!("{} must be an http(s) URL, got: '{}'", var, value)
+            )),
+        }
+    }
+
+    /// Parse the Mojang lookup cache TTL in seconds.
+    fn parse_mojang_cache_ttl(value: &str) -> Result<Duration> {
+        Self::parse_non_negative_secs("MOJANG_CACHE_TTL_SECS", value)
+    }
+
+    /// Parse a duration in whole seconds (zero allowed).
+    fn parse_non_negative_secs(var: &str, value: &str) -> Result<Duration> {
+        value.trim().parse::<u64>().map(Duration::from_secs).map_err(|_| OxideVaultError::Config(
+            format!("{} must be a non-negative integer, got: '{}'", var, value)
+        ))
+    }
+
+    /// Parse a comma-separated list of command names (case-insensitive).
+    fn parse_command_list(names: &str) -> Vec<String> {
+        names.split(',')
+            .map(|name| name.trim().trim_start_matches('/').to_lowercase())
+            .filter(|name| !name.is_empty())
+            .collect()
+    }
+
+    /// Parse a boolean flag (`true`/`false`, `1`/`0`, `yes`/`no`, `on`/`off`).
+    fn parse_bool(var: &str, value: &str) -> Result<bool> {
+        match value.trim().to_lowercase().as_str() {
+            "true" | "1" | "yes" | "on" => Ok(true),
+            "false" | "0" | "no" | "off" | "" => Ok(false),
+            _ => Err(OxideVaultError::Config(
+                format!("{} must be true or false, got: '{}'", var, value)
+            )),
+        }
+    }
+
+    /// Parse the socket address the HTTP server listens on (e.g. `127.0.0.1:9100`).
+    fn parse_http_bind_address(value: &str) -> Result<SocketAddr> {
+        value.trim().parse::<SocketAddr>().map_err(|_| OxideVaultError::Config(
+            format!("HTTP_BIND_ADDRESS must be an IP address and port (e.g. 127.0.0.1:9100), got: '{}'", value)
+        ))
+    }
+
+    /// Parse the HTTP rate limit burst (must be at least 1).
+    fn parse_rate_limit_burst(value: &str) -> Result<u32> {
+        match value.trim().parse::<u32>() {
+            Ok(burst) if burst > 0 => Ok(burst),
+            _ => Err(OxideVaultError::Config(
+                format!("HTTP_RATE_LIMIT_BURST must be a positive integer, got: '{}'", value)
+            )),
+        }
+    }
+
+    /// Parse the HTTP rate limit refill rate in requests per second (must be positive).
+    fn parse_rate_limit_per_sec(value: &str) -> Result<f64> {
+        match value.trim().parse::<f64>() {
+            Ok(rate) if rate.is_finite() && rate > 0.0 => Ok(rate),
+            _ => Err(OxideVaultError::Config(
+                format!("HTTP_RATE_LIMIT_PER_SEC must be a positive number, got: '{}'", value)
+            )),
+        }
+    }
+
+    /// Parse a comma-separated list of backup file extensions (case-insensitive).
+    ///
+    /// Leading dots are optional, so `.tgz` and `tgz` are equivalent.
+    fn parse_backup_extensions(value: &str) -> Result<Vec<String>> {
+        let extensions: Vec<String> = value.split(',')
+            .map(|ext| ext.trim().trim_start_matches('.').to_lowercase())
+            .filter(|ext| !ext.is_empty())
+            .collect();
+
+        if extensions.is_empty() {
+            return Err(OxideVaultError::Config(
+                format!("BACKUP_EXTENSIONS must list at least one extension, got: '{}'", value)
+            ));
+        }
+
+        Ok(extensions)
+    }
+
     /// Validate that the backup folder path exists and is a directory.
     fn validate_backup_folder(path: &str) -> Result<()> {
         let backup_path = Path::new(path);
@@ -229,10 +854,11 @@ impl Config {
                 std::fs::metadata(publish_path),
             ) {
                 if backup_meta.dev() != publish_meta.dev() {
-                    eprintln!(
-                        "Warning: BACKUP_FOLDER ('{}') and BACKUP_PUBLISH_ROOT ('{}') appear to be on different filesystems. \
-                        Hard linking will fail and backups will be copied instead, which may be slower for large files.",
-                        backup_folder, publish_root
+                    tracing::warn!(
+                        backup_folder,
+                        publish_root,
+                        "BACKUP_FOLDER and BACKUP_PUBLISH_ROOT appear to be on different filesystems. \
+                        Hard linking will fail and backups will be copied instead, which may be slower for large files."
                     );
                 }
             }
@@ -241,10 +867,11 @@ impl Config {
         // On non-Unix systems, just warn if they're not in a parent-child relationship
         #[cfg(not(unix))]
         {
-            eprintln!(
-                "Note: BACKUP_FOLDER ('{}') and BACKUP_PUBLISH_ROOT ('{}') are in different directories. \
-                If they're on different filesystems, hard linking will fail and backups will be copied instead.",
-                backup_folder, publish_root
+            tracing::info!(
+                backup_folder,
+                publish_root,
+                "BACKUP_FOLDER and BACKUP_PUBLISH_ROOT are in different directories. \
+                If they're on different filesystems, hard linking will fail and backups will be copied instead."
             );
         }
     }
@@ -264,6 +891,294 @@ mod tests {
         assert!(Config::validate_server_address("localhost").is_err());
         assert!(Config::validate_server_address("localhost:abc").is_err());
         assert!(Config::validate_server_address("localhost:99999").is_err());
+        assert!(Config::validate_server_address(":25565").is_err());
+        assert!(Config::validate_server_address("[::1]:25565").is_ok());
+    }
+
+    #[test]
+    fn test_parse_mc_servers() {
+        let servers = Config::parse_mc_servers("Survival=play.example.com:25565; creative=localhost:25566;").unwrap();
+        assert_eq!(servers, vec![
+            ("survival".to_string(), "play.example.com:25565".to_string()),
+            ("creative".to_string(), "localhost:25566".to_string()),
+        ]);
+
+        assert!(Config::parse_mc_servers("survival").is_err());
+        assert!(Config::parse_mc_servers("=localhost:25565").is_err());
+        assert!(Config::parse_mc_servers("survival=localhost").is_err());
+        assert!(Config::parse_mc_servers("a=localhost:1;A=localhost:2").is_err());
+    }
+
+    #[test]
+    fn test_resolve_servers() {
+        // Only MC_SERVER_ADDRESS: a single default entry
+        let (default, servers) = Config::resolve_servers(Some("localhost:25565"), None).unwrap();
+        assert_eq!(default, "localhost:25565");
+        assert_eq!(servers, HashMap::from([("default".to_string(), "localhost:25565".to_string())]));
+
+        // Only MC_SERVERS: the first entry is the default
+        let (default, servers) = Config::resolve_servers(None, Some("lobby=lobby:25565;smp=smp:25565")).unwrap();
+        assert_eq!(default, "lobby:25565");
+        assert_eq!(servers.len(), 3);
+        assert_eq!(servers["default"], "lobby:25565");
+
+        // An explicit default entry wins over the first one
+        let (default, _) = Config::resolve_servers(None, Some("lobby=lobby:25565;default=main:25565")).unwrap();
+        assert_eq!(default, "main:25565");
+
+        // Both: MC_SERVER_ADDRESS is the default
+        let (default, servers) = Config::resolve_servers(Some("main:25565"), Some("smp=smp:25565")).unwrap();
+        assert_eq!(default, "main:25565");
+        assert_eq!(servers["default"], "main:25565");
+        assert_eq!(servers["smp"], "smp:25565");
+
+        assert!(Config::resolve_servers(None, None).is_err());
+        assert!(Config::resolve_servers(Some(" "), Some("")).is_err());
+        assert!(Config::resolve_servers(Some("localhost"), None).is_err());
+    }
+
+    #[test]
+    fn test_parse_file_values() {
+        let values = Config::parse_file_values(r#"
+            discord_token = "file.token.value"
+            ping_retries = 3
+            admin_user_ids = [123, 456]
+            disabled_commands = ["backup", "sync"]
+
+            [mc_servers]
+            survival = "play.example.com:25565"
+        "#).unwrap();
+
+        assert_eq!(values["DISCORD_TOKEN"], "file.token.value");
+        assert_eq!(values["PING_RETRIES"], "3");
+        assert_eq!(values["ADMIN_USER_IDS"], "123,456");
+        assert_eq!(values["DISABLED_COMMANDS"], "backup,sync");
+        assert_eq!(values["MC_SERVERS"], "survival=play.example.com:25565");
+
+        assert!(Config::parse_file_values("discord_tokn = \"typo\"").is_err());
+        assert!(Config::parse_file_values("not toml").is_err());
+        assert!(Config::parse_file_values("ping_timeout_secs = 1.5").is_err());
+    }
+
+    #[test]
+    fn test_from_file_env_overrides() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let backups = temp_dir.path().join("backups");
+        fs::create_dir(&backups).unwrap();
+        let config_path = temp_dir.path().join("oxidevault.toml");
+        fs::write(&config_path, format!(
+            "discord_token = \"{1}\"\n\
+             mc_server_address = \"localhost:25565\"\n\
+             backup_folder = \"{0}\"\n\
+             backup_publish_root = \"{0}/public\"\n\
+             mojang_cache_ttl_secs = 30\n",
+            backups.display(),
+            FAKE_TOKEN
+        )).unwrap();
+
+        let config = Config::from_file(&config_path).unwrap();
+        assert_eq!(config.discord_token, FAKE_TOKEN);
+        assert_eq!(config.mc_server_address, "localhost:25565");
+        assert_eq!(config.backup_folder, backups.to_str().unwrap());
+        assert_eq!(config.mojang_cache_ttl, Duration::from_secs(30));
+
+        env::set_var("MOJANG_CACHE_TTL_SECS", "5");
+        let config = Config::from_file(&config_path);
+        env::remove_var("MOJANG_CACHE_TTL_SECS");
+        assert_eq!(config.unwrap().mojang_cache_ttl, Duration::from_secs(5));
+
+        assert!(Config::from_file(&temp_dir.path().join("missing.toml")).is_err());
+    }
+
+    #[test]
+    fn test_invalid_draw_limits_fail_at_load() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("oxidevault.toml");
+        let write_config = |draw: &str| fs::write(&config_path, format!(
+            "discord_token = \"{}\"\n\
+             mc_server_address = \"localhost:25565\"\n\
+             backup_folder = \"{}\"\n\
+             {}\n",
+            FAKE_TOKEN,
+            temp_dir.path().display(),
+            draw
+        )).unwrap();
+
+        write_config("draw_max_radius = 500\ndraw_scale_max_radius = \"4=2000\"");
+        let config = Config::from_file(&config_path).unwrap();
+        assert!(config.draw_limits.validate(500, 1).is_ok());
+        assert!(config.draw_limits.validate(2000, 4).is_ok());
+        assert!(config.draw_limits.validate(501, 1).is_err());
+
+        write_config("draw_max_radius = \"huge\"");
+        assert!(matches!(Config::from_file(&config_path), Err(OxideVaultError::Config(_))));
+
+        write_config("draw_scale_max_radius = \"4:2000\"");
+        assert!(matches!(Config::from_file(&config_path), Err(OxideVaultError::Config(_))));
+    }
+
+    /// Well-formed but fake bot token.
+    const FAKE_TOKEN: &str = "MTIzNDU2Nzg5MDEyMzQ1Njc4OQ.GaBcDe.abcdefghijklmnopqrstuvwxyz_-0123456789";
+
+    #[test]
+    fn test_validate_discord_token() {
+        assert!(Config::validate_discord_token(FAKE_TOKEN).is_ok());
+
+        let secret = "MTIzNDU2Nzg5MDEyMzQ1Njc4OQ.GaBcDe";
+        for token in [
+            "",
+            "file-token",
+            secret,
+            "MTIz.GaBcDe.abc.def",
+            "MTIz..abcdef",
+            "MTIz.GaBcDe.",
+            "MTIz.GaB/De.abc+def",
+            "MTIz.GaBcDe.abc def",
+            "MTIz.GaBcDe.abcdef\n",
+            "Bot MTIz.GaBcDe.abcdef",
+        ] {
+            let err = Config::validate_discord_token(token).unwrap_err();
+            assert!(matches!(err, OxideVaultError::Config(_)), "{:?}", token);
+            if !token.is_empty() {
+                assert!(!err.to_string().contains(token), "token leaked in error message");
+            }
+        }
+        assert!(Config::validate_discord_token(secret).unwrap_err().to_string().contains("found 2"));
+    }
+
+    #[test]
+    fn test_parse_admin_user_ids() {
+        assert_eq!(Config::parse_admin_user_ids("").unwrap(), Vec::<u64>::new());
+        assert_eq!(Config::parse_admin_user_ids("123").unwrap(), vec![123]);
+        assert_eq!(
+            Config::parse_admin_user_ids(" 123 , 456,").unwrap(),
+            vec![123, 456]
+        );
+
+        assert!(Config::parse_admin_user_ids("123,abc").is_err());
+        assert!(Config::parse_admin_user_ids("-1").is_err());
+    }
+
+    #[test]
+    fn test_parse_command_list() {
+        assert!(Config::parse_command_list("").is_empty());
+        assert_eq!(Config::parse_command_list("backup"), vec!["backup"]);
+        assert_eq!(
+            Config::parse_command_list(" Backup, /sync ,,"),
+            vec!["backup", "sync"]
+        );
+    }
+
+    #[test]
+    fn test_parse_bool() {
+        assert!(Config::parse_bool("PLAYERDB_FALLBACK", "true").unwrap());
+        assert!(Config::parse_bool("PLAYERDB_FALLBACK", " Yes ").unwrap());
+        assert!(Config::parse_bool("PLAYERDB_FALLBACK", "1").unwrap());
+        assert!(!Config::parse_bool("PLAYERDB_FALLBACK", "off").unwrap());
+        assert!(!Config::parse_bool("PLAYERDB_FALLBACK", "").unwrap());
+        assert!(Config::parse_bool("PLAYERDB_FALLBACK", "maybe").is_err());
+    }
+
+    #[test]
+    fn test_parse_http_bind_address() {
+        assert_eq!(
+            Config::parse_http_bind_address(" 127.0.0.1:9100 ").unwrap(),
+            "127.0.0.1:9100".parse::<SocketAddr>().unwrap()
+        );
+        assert!(Config::parse_http_bind_address("[::]:9100").is_ok());
+        assert!(Config::parse_http_bind_address("localhost:9100").is_err());
+        assert!(Config::parse_http_bind_address("127.0.0.1").is_err());
+    }
+
+    #[test]
+    fn test_parse_rate_limit() {
+        assert_eq!(Config::parse_rate_limit_burst(" 50 ").unwrap(), 50);
+        assert!(Config::parse_rate_limit_burst("0").is_err());
+        assert!(Config::parse_rate_limit_burst("-1").is_err());
+
+        assert_eq!(Config::parse_rate_limit_per_sec("0.5").unwrap(), 0.5);
+        assert_eq!(Config::parse_rate_limit_per_sec("10").unwrap(), 10.0);
+        assert!(Config::parse_rate_limit_per_sec("0").is_err());
+        assert!(Config::parse_rate_limit_per_sec("inf").is_err());
+        assert!(Config::parse_rate_limit_per_sec("fast").is_err());
+    }
+
+    #[test]
+    fn test_parse_backup_extensions() {
+        assert_eq!(Config::parse_backup_extensions("tgz").unwrap(), vec!["tgz"]);
+        assert_eq!(
+            Config::parse_backup_extensions(" .TAR.GZ, zip ,,").unwrap(),
+            vec!["tar.gz", "zip"]
+        );
+        assert!(Config::parse_backup_extensions("").is_err());
+        assert!(Config::parse_backup_extensions(" , .").is_err());
+    }
+
+    #[test]
+    fn test_parse_ping_timeout() {
+        let parse = |value: Option<&str>, fallback: Option<&str>| {
+            Config::parse_ping_timeout(value.map(String::from), fallback.map(String::from))
+        };
+        assert_eq!(parse(Some("5"), None).unwrap(), Duration::from_secs(5));
+        assert!(parse(Some("0"), None).is_err());
+        assert!(parse(Some("soon"), None).is_err());
+        assert_eq!(parse(None, None).unwrap(), DEFAULT_PING_TIMEOUT);
+
+        // PING_TIMEOUT_SECS is only used when MC_PING_TIMEOUT_SECS is unset
+        assert_eq!(parse(None, Some("7")).unwrap(), Duration::from_secs(7));
+        assert_eq!(parse(Some("5"), Some("7")).unwrap(), Duration::from_secs(5));
+        assert!(parse(Some("0"), None).unwrap_err().to_string().contains("MC_PING_TIMEOUT_SECS"));
+        let error = parse(None, Some("0")).unwrap_err().to_string();
+        assert!(error.contains("PING_TIMEOUT_SECS") && !error.contains("MC_PING_TIMEOUT_SECS"));
+    }
+
+    #[test]
+    fn test_parse_positive_secs() {
+        assert_eq!(
+            Config::parse_positive_secs("BACKUP_USER_COOLDOWN_SECS", " 3600 ").unwrap(),
+            Duration::from_secs(3600)
+        );
+
+        let err = Config::parse_positive_secs("BACKUP_USER_COOLDOWN_SECS", "0").unwrap_err();
+        assert!(err.to_string().contains("BACKUP_USER_COOLDOWN_SECS must be a positive integer"));
+        assert!(Config::parse_positive_secs("BACKUP_GLOBAL_COOLDOWN_SECS", "-5").is_err());
+        assert!(Config::parse_positive_secs("MC_PING_TIMEOUT_SECS", "1h").is_err());
+    }
+
+    #[test]
+    fn test_parse_ping_retries() {
+        assert_eq!(Config::parse_ping_retries("0").unwrap(), 0);
+        assert_eq!(Config::parse_ping_retries(" 3 ").unwrap(), 3);
+        assert!(Config::parse_ping_retries("-1").is_err());
+        assert!(Config::parse_ping_retries("many").is_err());
+    }
+
+    #[test]
+    fn test_parse_mojang_cache_ttl() {
+        assert_eq!(Config::parse_mojang_cache_ttl("600").unwrap(), Duration::from_secs(600));
+        assert_eq!(Config::parse_mojang_cache_ttl("0").unwrap(), Duration::ZERO);
+        assert!(Config::parse_mojang_cache_ttl("ten minutes").is_err());
+        assert_eq!(Config::parse_non_negative_secs("STATUS_CACHE_TTL_SECS", "0").unwrap(), Duration::ZERO);
+        assert!(Config::parse_non_negative_secs("STATUS_CACHE_TTL_SECS", "-1").is_err());
+    }
+
+    #[test]
+    fn test_parse_base_url() {
+        assert_eq!(
+            Config::parse_base_url("MOJANG_API_URL", " http://proxy.local:8080/ ").unwrap(),
+            "http://proxy.local:8080"
+        );
+        assert!(Config::parse_base_url("MOJANG_API_URL", "proxy.local").is_err());
+        assert!(Config::parse_base_url("MOJANG_API_URL", "ftp://proxy.local").is_err());
+    }
+
+    #[test]
+    fn test_parse_max_player_sample() {
+        assert_eq!(Config::parse_max_player_sample("100").unwrap(), 100);
+        assert_eq!(Config::parse_max_player_sample(" 25 ").unwrap(), 25);
+        assert!(Config::parse_max_player_sample("0").is_err());
+        assert!(Config::parse_max_player_sample("-5").is_err());
+        assert!(Config::parse_max_player_sample("lots").is_err());
     }
 
     #[test]
@@ -275,7 +1190,7 @@ mod tests {
         let custom_path = "/custom/path/to/database.db";
         env::set_var("DB_PATH", custom_path);
 
-        let result = Config::get_db_path();
+        let result = Config::get_db_path(env::var("DB_PATH").ok());
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), custom_path);
 
@@ -294,7 +1209,7 @@ mod tests {
         // Remove DB_PATH env var to test default behavior
         env::remove_var("DB_PATH");
 
-        let result = Config::get_db_path();
+        let result = Config::get_db_path(env::var("DB_PATH").ok());
         assert!(result.is_ok());
 
         let path = result.unwrap();
@@ -304,9 +1219,8 @@ mod tests {
         assert!(path.ends_with("data/oxidevault.db") || path.ends_with("data\\oxidevault.db"));
 
         // Restore original value
-        match original_value {
-            Some(val) => env::set_var("DB_PATH", val),
-            None => {}, // Already removed
+        if let Some(val) = original_value {
+            env::set_var("DB_PATH", val);
         }
     }
 }
@@ -0,0 +1,166 @@
+//! Interactive confirmation for destructive commands.
+//!
+//! Posts a prompt with Yes/No buttons and waits for the invoking user to
+//! answer. Anything other than an explicit "Yes" (including a timeout) counts
+//! as "No".
+
+use crate::types::{Context, Error};
+use poise::serenity_prelude as serenity;
+use std::future::Future;
+use std::time::Duration;
+
+/// How long to wait for an answer before treating it as "No".
+pub const DEFAULT_CONFIRM_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// What the invoker did with a confirmation prompt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfirmOutcome {
+    /// A button with the given custom ID was pressed
+    Pressed(String),
+    /// No button was pressed before the timeout
+    TimedOut,
+}
+
+/// Waits for a button press on a confirmation prompt.
+///
+/// Abstracted so that the decision logic can be tested without Discord.
+pub trait ButtonAwaiter {
+    /// Wait for one of `custom_ids` to be pressed, up to `timeout`.
+    fn await_button(
+        &self,
+        custom_ids: Vec<String>,
+        timeout: Duration,
+    ) -> impl Future<Output = ConfirmOutcome> + Send;
+}
+
+/// Awaits button presses from the command's author through a serenity collector.
+struct CollectorAwaiter {
+    context: serenity::Context,
+    author_id: serenity::UserId,
+    channel_id: serenity::ChannelId,
+}
+
+impl ButtonAwaiter for CollectorAwaiter {
+    async fn await_button(&self, custom_ids: Vec<String>, timeout: Duration) -> ConfirmOutcome {
+        let interaction = serenity::ComponentInteractionCollector::new(&self.context)
+            .author_id(self.author_id)
+            .channel_id(self.channel_id)
+            .custom_ids(custom_ids)
+            .timeout(timeout)
+            .await;
+
+        match interaction {
+            Some(interaction) => {
+                // Acknowledge the press; the prompt is edited by the caller
+                let _ = interaction
+                    .create_response(&self.context.http, serenity::CreateInteractionResponse::Acknowledge)
+                    .await;
+                ConfirmOutcome::Pressed(interaction.data.custom_id)
+            }
+            None => ConfirmOutcome::TimedOut,
+        }
+    }
+}
+
+/// Map a confirmation outcome to a decision: only pressing "Yes" confirms.
+pub fn decide(outcome: &ConfirmOutcome, confirm_id: &str) -> bool {
+    matches!(outcome, ConfirmOutcome::Pressed(id) if id == confirm_id)
+}
+
+/// Wait for an answer with `awaiter` and decide whether it confirmed.
+pub async fn await_decision<A: ButtonAwaiter>(
+    awaiter: &A,
+    confirm_id: &str,
+    cancel_id: &str,
+    timeout: Duration,
+) -> (bool, ConfirmOutcome) {
+    let outcome = awaiter
+        .await_button(vec![confirm_id.to_string(), cancel_id.to_string()], timeout)
+        .await;
+    (decide(&outcome, confirm_id), outcome)
+}
+
+/// Ask the invoking user to confirm an action with Yes/No buttons.
+///
+/// Returns `true` only if the user pressed "Yes" within
+/// [`DEFAULT_CONFIRM_TIMEOUT`]. The prompt's buttons are removed once answered.
+pub async fn confirm(context: Context<'_>, prompt: &str) -> Result<bool, Error> {
+    let confirm_id = format!("{}-confirm", context.id());
+    let cancel_id = format!("{}-cancel", context.id());
+
+    let buttons = serenity::CreateActionRow::Buttons(vec![
+        serenity::CreateButton::new(&confirm_id)
+            .label("Yes")
+            .style(serenity::ButtonStyle::Danger),
+        serenity::CreateButton::new(&cancel_id)
+            .label("No")
+            .style(serenity::ButtonStyle::Secondary),
+    ]);
+
+    let handle = context
+        .send(poise::CreateReply::default().content(prompt).components(vec![buttons]))
+        .await?;
+
+    let awaiter = CollectorAwaiter {
+        context: context.serenity_context().clone(),
+        author_id: context.author().id,
+        channel_id: context.channel_id(),
+    };
+    let (confirmed, outcome) =
+        await_decision(&awaiter, &confirm_id, &cancel_id, DEFAULT_CONFIRM_TIMEOUT).await;
+
+    let status = match (confirmed, outcome) {
+        (true, _) => "✅ Confirmed.",
+        (false, ConfirmOutcome::TimedOut) => "⌛ No answer received, cancelled.",
+        (false, ConfirmOutcome::Pressed(_)) => "❌ Cancelled.",
+    };
+
+    handle
+        .edit(
+            context,
+            poise::CreateReply::default()
+                .content(format!("{}\n{}", prompt, status))
+                .components(Vec::new()),
+        )
+        .await?;
+
+    Ok(confirmed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Awaiter returning a fixed outcome.
+    struct MockAwaiter(ConfirmOutcome);
+
+    impl ButtonAwaiter for MockAwaiter {
+        async fn await_button(&self, _custom_ids: Vec<String>, _timeout: Duration) -> ConfirmOutcome {
+            self.0.clone()
+        }
+    }
+
+    #[test]
+    fn test_decide() {
+        assert!(decide(&ConfirmOutcome::Pressed("1-confirm".to_string()), "1-confirm"));
+        assert!(!decide(&ConfirmOutcome::Pressed("1-cancel".to_string()), "1-confirm"));
+        assert!(!decide(&ConfirmOutcome::Pressed("2-confirm".to_string()), "1-confirm"));
+        assert!(!decide(&ConfirmOutcome::TimedOut, "1-confirm"));
+    }
+
+    #[tokio::test]
+    async fn test_await_decision_outcomes() {
+        let timeout = Duration::from_secs(1);
+
+        let confirm = MockAwaiter(ConfirmOutcome::Pressed("yes".to_string()));
+        assert!(await_decision(&confirm, "yes", "no", timeout).await.0);
+
+        let deny = MockAwaiter(ConfirmOutcome::Pressed("no".to_string()));
+        assert!(!await_decision(&deny, "yes", "no", timeout).await.0);
+
+        let timed_out = MockAwaiter(ConfirmOutcome::TimedOut);
+        let (confirmed, outcome) = await_decision(&timed_out, "yes", "no", timeout).await;
+        assert!(!confirmed);
+        assert_eq!(outcome, ConfirmOutcome::TimedOut);
+    }
+}
@@ -3,9 +3,18 @@
 //! This module provides a repository pattern for database operations,
 //! separating database concerns from business logic.
 
-use rusqlite::Connection;
+use r2d2::{Pool, PooledConnection};
+use r2d2_sqlite::SqliteConnectionManager;
+use rusqlite::{Connection, OptionalExtension};
 use crate::error::{OxideVaultError, Result};
+use crate::metrics::PoolStats;
+use crate::utils::validation::normalize_uuid;
+use serde::{Deserialize, Serialize};
 use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Default time to wait for a free pooled connection before giving up.
+pub const DEFAULT_POOL_TIMEOUT: Duration = Duration::from_secs(5);
 
 /// Minecraft player information.
 #[derive(Debug, Clone)]
@@ -15,8 +24,7 @@ pub struct MinecraftPlayer {
 }
 
 /// Player statistics entry.
-#[derive(Debug, Clone)]
-#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PlayerStat {
     pub mc_uuid: String,
     pub stat_name: String,
@@ -24,10 +32,171 @@ pub struct PlayerStat {
     pub timestamp: i64,
 }
 
+/// A player and their statistics, as written by
+/// [`PlayerRepository::export_json`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlayerExport {
+    pub uuid: String,
+    pub username: String,
+    #[serde(default)]
+    pub stats: Vec<ExportedStat>,
+}
+
+/// A single statistic within a [`PlayerExport`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportedStat {
+    pub name: String,
+    pub value: i64,
+    /// Unix timestamp (seconds) of the last update
+    pub timestamp: i64,
+}
+
+/// Player counts recorded from a server status at a point in time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatusSample {
+    /// Unix timestamp (seconds) of the sample
+    pub timestamp: i64,
+    pub online: u16,
+    pub max: u16,
+}
+
+/// A group of rows whose `mc_uuid` has no matching `minecraft_users` row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrphanRow {
+    /// Table containing the orphaned rows
+    pub table: &'static str,
+    /// UUID referenced by the orphaned rows
+    pub mc_uuid: String,
+    /// Number of orphaned rows referencing this UUID
+    pub count: u64,
+}
+
+/// Result of linking a Discord account to a Minecraft account.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkOutcome {
+    /// The accounts are now linked
+    Linked,
+    /// The Discord account is already linked to this Minecraft UUID
+    AlreadyLinked(String),
+    /// The Minecraft account is already linked to this other Discord user
+    TakenBy(u64),
+}
+
+/// A Discord account's link to a Minecraft account.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountLink {
+    /// Linked player UUID (undashed)
+    pub mc_uuid: String,
+    /// Last known username of the linked player
+    pub username: String,
+    /// Unix timestamp (seconds) of when the link was made
+    pub linked_at: i64,
+}
+
+/// Tables whose `mc_uuid` column references `minecraft_users`.
+const PLAYER_CHILD_TABLES: &[&str] = &["player_stats", "username_history", "discord_links"];
+
+/// Per-guild settings that override the global configuration.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GuildSettings {
+    pub guild_id: u64,
+    /// Channel used for status announcements
+    pub status_channel: Option<u64>,
+    /// Minecraft server address (host:port) monitored for this guild
+    pub server_address: Option<String>,
+    /// Preferred locale for bot replies
+    pub locale: Option<String>,
+    /// Backup folder for this guild, relative to the global backup folder
+    pub backup_folder: Option<String>,
+}
+
+/// A guild setting that can be cleared back to the global default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GuildSettingKey {
+    StatusChannel,
+    ServerAddress,
+    Locale,
+    BackupFolder,
+}
+
+impl GuildSettingKey {
+    /// Column of `guild_settings` storing the setting.
+    fn column(self) -> &'static str {
+        match self {
+            Self::StatusChannel => "status_channel",
+            Self::ServerAddress => "server_address",
+            Self::Locale => "locale",
+            Self::BackupFolder => "backup_folder",
+        }
+    }
+}
+
+/// Schema migrations applied in order on top of the base schema.
+///
+/// `PRAGMA user_version` records how many have been applied. Only ever append
+/// to this list; released entries must not change.
+const MIGRATIONS: &[&str] = &[
+    // 1: when each player was last seen online (Unix seconds)
+    "ALTER TABLE minecraft_users ADD COLUMN last_seen INTEGER",
+    // 2: previous usernames, recorded when a player is renamed
+    "CREATE TABLE username_history (
+        mc_uuid TEXT NOT NULL,
+        old_username TEXT NOT NULL,
+        changed_at INTEGER NOT NULL,
+        FOREIGN KEY (mc_uuid) REFERENCES minecraft_users(mc_uuid) ON DELETE CASCADE
+    );
+    CREATE INDEX idx_username_history_uuid ON username_history(mc_uuid);",
+    // 3: Discord accounts linked to Minecraft accounts (one to one)
+    "CREATE TABLE discord_links (
+        discord_id INTEGER PRIMARY KEY,
+        mc_uuid TEXT NOT NULL UNIQUE,
+        linked_at INTEGER NOT NULL,
+        FOREIGN KEY (mc_uuid) REFERENCES minecraft_users(mc_uuid) ON DELETE CASCADE
+    );",
+    // 4: store UUIDs undashed and lowercase; rows whose canonical form already
+    // exists are dropped in favor of it
+    "INSERT OR IGNORE INTO minecraft_users (mc_uuid, mc_username, last_seen)
+        SELECT lower(replace(mc_uuid, '-', '')), mc_username, last_seen FROM minecraft_users
+        WHERE (length(mc_uuid) = 36 AND mc_uuid LIKE '%-%')
+        OR (length(mc_uuid) = 32 AND mc_uuid <> lower(mc_uuid));
+    UPDATE OR IGNORE player_stats SET mc_uuid = lower(replace(mc_uuid, '-', ''))
+        WHERE (length(mc_uuid) = 36 AND mc_uuid LIKE '%-%')
+        OR (length(mc_uuid) = 32 AND mc_uuid <> lower(mc_uuid));
+    UPDATE username_history SET mc_uuid = lower(replace(mc_uuid, '-', ''))
+        WHERE (length(mc_uuid) = 36 AND mc_uuid LIKE '%-%')
+        OR (length(mc_uuid) = 32 AND mc_uuid <> lower(mc_uuid));
+    UPDATE OR IGNORE discord_links SET mc_uuid = lower(replace(mc_uuid, '-', ''))
+        WHERE (length(mc_uuid) = 36 AND mc_uuid LIKE '%-%')
+        OR (length(mc_uuid) = 32 AND mc_uuid <> lower(mc_uuid));
+    DELETE FROM minecraft_users
+        WHERE (length(mc_uuid) = 36 AND mc_uuid LIKE '%-%')
+        OR (length(mc_uuid) = 32 AND mc_uuid <> lower(mc_uuid));",
+    // 5: per-guild backup folder, relative to the global BACKUP_FOLDER
+    "ALTER TABLE guild_settings ADD COLUMN backup_folder TEXT",
+    // 6: player counts keyed by server address; earlier samples keep an empty
+    // server until claimed with `StatusHistoryRepository::claim_unkeyed_samples`
+    "CREATE TABLE IF NOT EXISTS status_history (
+        timestamp INTEGER NOT NULL,
+        online INTEGER NOT NULL,
+        max INTEGER NOT NULL
+    );
+    CREATE TABLE player_count_history (
+        server TEXT NOT NULL,
+        timestamp INTEGER NOT NULL,
+        online INTEGER NOT NULL,
+        max INTEGER NOT NULL
+    );
+    CREATE INDEX idx_player_count_history_server_timestamp
+        ON player_count_history(server, timestamp);
+    INSERT INTO player_count_history (server, timestamp, online, max)
+        SELECT '', timestamp, online, max FROM status_history;
+    DROP TABLE status_history;",
+];
+
 /// Initialize the database schema.
 ///
-/// Creates the necessary tables and indices if they don't already exist.
-/// Also creates the parent directory if needed.
+/// Creates the necessary tables and indices if they don't already exist, then
+/// applies any pending [`MIGRATIONS`]. Also creates the parent directory if needed.
 ///
 /// # Arguments
 ///
@@ -50,7 +219,7 @@ fn init_db_sync(path: &str) -> Result<()> {
         std::fs::create_dir_all(parent)?;
     }
 
-    let conn = Connection::open(path)?;
+    let mut conn = Connection::open(path)?;
 
     // Enable foreign keys
     conn.execute("PRAGMA foreign_keys = ON", [])?;
@@ -83,25 +252,186 @@ fn init_db_sync(path: &str) -> Result<()> {
         [],
     )?;
 
+    // Per-guild settings (NULL columns fall back to the global configuration)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS guild_settings (
+            guild_id INTEGER NOT NULL PRIMARY KEY,
+            status_channel INTEGER,
+            server_address TEXT,
+            locale TEXT
+        )",
+        [],
+    )?;
+
+    run_migrations(&mut conn)
+}
+
+/// Apply the migrations that haven't run on this database yet.
+fn run_migrations(conn: &mut Connection) -> Result<()> {
+    let applied: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(applied.max(0) as usize) {
+        let tx = conn.transaction()?;
+        tx.execute_batch(migration)
+            .map_err(|e| OxideVaultError::Database(format!("Migration {} failed: {}", index + 1, e)))?;
+        tx.pragma_update(None, "user_version", (index + 1) as i64)?;
+        tx.commit()?;
+    }
+
     Ok(())
 }
 
+/// Escape `LIKE` wildcards so `value` matches literally (with `ESCAPE '\'`).
+fn escape_like(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Canonical storage form of a player UUID: undashed and lowercase.
+///
+/// Input that isn't a UUID is kept as-is, so it simply matches nothing.
+fn canonical_uuid(uuid: &str) -> String {
+    normalize_uuid(uuid).unwrap_or_else(|| uuid.to_string())
+}
+
+/// Upsert `players` on `conn`, recording renames in the username history.
+///
+/// Callers are expected to wrap this in a transaction.
+fn upsert_players_in(conn: &Connection, players: &[MinecraftPlayer]) -> Result<()> {
+    let changed_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() as i64);
+
+    let mut select = conn.prepare("SELECT mc_username FROM minecraft_users WHERE mc_uuid = ?1")?;
+    let mut record_rename = conn.prepare(
+        "INSERT INTO username_history (mc_uuid, old_username, changed_at) VALUES (?1, ?2, ?3)"
+    )?;
+    let mut upsert = conn.prepare(
+        "INSERT INTO minecraft_users (mc_uuid, mc_username)
+         VALUES (?1, ?2)
+         ON CONFLICT(mc_uuid) DO UPDATE SET mc_username = ?2"
+    )?;
+
+    for player in players {
+        let uuid = canonical_uuid(&player.uuid);
+        let previous: Option<String> = select
+            .query_row(rusqlite::params![uuid], |row| row.get(0))
+            .optional()?;
+
+        if let Some(old_username) = previous.filter(|old| *old != player.username) {
+            record_rename.execute(rusqlite::params![uuid, old_username, changed_at])?;
+        }
+
+        upsert.execute(rusqlite::params![uuid, player.username])?;
+    }
+
+    Ok(())
+}
+
+/// Upsert `stats` on `conn`, replacing the value and timestamp of existing ones.
+///
+/// Callers are expected to wrap this in a transaction.
+fn upsert_stats_in(conn: &Connection, stats: &[PlayerStat]) -> Result<()> {
+    let mut upsert = conn.prepare(
+        "INSERT INTO player_stats (mc_uuid, stat_name, stat_value, timestamp)
+         VALUES (?1, ?2, ?3, ?4)
+         ON CONFLICT(mc_uuid, stat_name) DO UPDATE SET stat_value = ?3, timestamp = ?4"
+    )?;
+
+    for stat in stats {
+        upsert.execute(rusqlite::params![canonical_uuid(&stat.mc_uuid), stat.stat_name, stat.stat_value, stat.timestamp])?;
+    }
+
+    Ok(())
+}
+
+/// Get a connection from `pool`.
+///
+/// Timing out while every connection is in use is reported as the pool being
+/// exhausted rather than as a connection failure.
+fn connection(pool: &Pool<SqliteConnectionManager>) -> Result<PooledConnection<SqliteConnectionManager>> {
+    pool.get().map_err(|e| {
+        let state = pool.state();
+        if state.connections >= pool.max_size() && state.idle_connections == 0 {
+            tracing::warn!(connections = state.connections, "Database connection pool exhausted");
+            OxideVaultError::Database("connection pool exhausted".to_string())
+        } else {
+            OxideVaultError::Pool(e)
+        }
+    })
+}
+
+/// Open a lazily connecting pool for the database at `db_path`.
+///
+/// Connections are opened in WAL mode with foreign keys enforced, and waiting
+/// for a free connection gives up after `timeout`.
+fn open_pool(db_path: String, timeout: Duration) -> Pool<SqliteConnectionManager> {
+    let manager = SqliteConnectionManager::file(db_path).with_init(|conn| {
+        conn.pragma_update(None, "journal_mode", "WAL")?;
+        conn.pragma_update(None, "foreign_keys", "ON")
+    });
+    Pool::builder().connection_timeout(timeout).build_unchecked(manager)
+}
+
 /// Repository for player database operations.
+#[derive(Clone)]
 pub struct PlayerRepository {
-    db_path: String,
+    pool: Pool<SqliteConnectionManager>,
 }
 
 impl PlayerRepository {
-    /// Create a new player repository.
+    /// Create a new player repository backed by a connection pool.
+    ///
+    /// Connections are opened lazily, in WAL mode with foreign keys enforced.
+    /// Waiting for a free connection gives up after [`DEFAULT_POOL_TIMEOUT`].
     pub fn new(db_path: String) -> Self {
-        Self { db_path }
+        Self::with_pool_timeout(db_path, DEFAULT_POOL_TIMEOUT)
+    }
+
+    /// Create a new player repository whose operations wait at most `timeout`
+    /// for a free pooled connection.
+    pub fn with_pool_timeout(db_path: String, timeout: Duration) -> Self {
+        Self { pool: open_pool(db_path, timeout) }
+    }
+
+    /// Guild settings repository sharing this repository's connection pool.
+    pub fn guilds(&self) -> GuildRepository {
+        GuildRepository { pool: self.pool.clone() }
     }
 
-    /// Get a connection to the database.
-    #[allow(dead_code)]
-    fn connect(&self) -> Result<Connection> {
-        Connection::open(&self.db_path)
-            .map_err(|e| OxideVaultError::Database(format!("Failed to connect to database: {}", e)))
+    /// Status history repository sharing this repository's connection pool.
+    pub fn status_history(&self) -> StatusHistoryRepository {
+        StatusHistoryRepository { pool: self.pool.clone() }
+    }
+
+    /// Idle and in-use connection counts of the pool.
+    pub fn pool_stats(&self) -> PoolStats {
+        let state = self.pool.state();
+        PoolStats {
+            idle: state.idle_connections,
+            in_use: state.connections.saturating_sub(state.idle_connections),
+        }
+    }
+
+    /// Flush the write-ahead log into the main database file.
+    ///
+    /// Called on shutdown so the database file is self-contained afterwards.
+    pub async fn checkpoint(&self) -> Result<()> {
+        let pool = self.pool.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = connection(&pool)?;
+            conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))?;
+            Ok::<_, OxideVaultError>(())
+        })
+        .await
+        .map_err(|e| OxideVaultError::Database(format!("Task join error: {}", e)))??;
+        Ok(())
     }
 
     /// Insert or update a player in the database.
@@ -109,16 +439,26 @@ impl PlayerRepository {
     /// # Arguments
     ///
     /// * `player` - The player information to save
+    ///
+    /// When an existing player's username changes, the previous name is kept
+    /// in the username history.
     pub async fn upsert_player(&self, player: MinecraftPlayer) -> Result<()> {
-        let db_path = self.db_path.clone();
+        self.upsert_players(vec![player]).await
+    }
+
+    /// Insert or update many players in a single transaction.
+    ///
+    /// Renames are recorded in the username history as with
+    /// [`upsert_player`](Self::upsert_player). If any row fails, the whole
+    /// batch is rolled back.
+    pub async fn upsert_players(&self, players: Vec<MinecraftPlayer>) -> Result<()> {
+        let pool = self.pool.clone();
         tokio::task::spawn_blocking(move || {
-            let conn = Connection::open(&db_path)?;
-            conn.execute(
-                "INSERT INTO minecraft_users (mc_uuid, mc_username)
-                 VALUES (?1, ?2)
-                 ON CONFLICT(mc_uuid) DO UPDATE SET mc_username = ?2",
-                rusqlite::params![player.uuid, player.username],
-            )?;
+            let mut conn = connection(&pool)?;
+            let tx = conn.transaction()?;
+            upsert_players_in(&tx, &players)?;
+            // Dropping the transaction without committing rolls the batch back
+            tx.commit()?;
             Ok::<_, OxideVaultError>(())
         })
         .await
@@ -126,14 +466,110 @@ impl PlayerRepository {
         Ok(())
     }
 
+    /// Export all players and their statistics as a JSON array.
+    ///
+    /// Players are ordered by username. The output can be read back with
+    /// [`import_json`](Self::import_json).
+    pub async fn export_json(&self) -> Result<String> {
+        let pool = self.pool.clone();
+        let players = tokio::task::spawn_blocking(move || {
+            let conn = connection(&pool)?;
+            let mut players_stmt = conn.prepare(
+                "SELECT mc_uuid, mc_username FROM minecraft_users ORDER BY mc_username"
+            )?;
+            let mut stats_stmt = conn.prepare(
+                "SELECT stat_name, stat_value, timestamp FROM player_stats
+                 WHERE mc_uuid = ?1 ORDER BY stat_name"
+            )?;
+
+            let rows = players_stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get(1)?)))?;
+
+            let mut players = Vec::new();
+            for row in rows {
+                let (uuid, username) = row?;
+                let stats = stats_stmt
+                    .query_map(rusqlite::params![uuid], |row| {
+                        Ok(ExportedStat {
+                            name: row.get(0)?,
+                            value: row.get(1)?,
+                            timestamp: row.get(2)?,
+                        })
+                    })?
+                    .collect::<rusqlite::Result<Vec<_>>>()?;
+                players.push(PlayerExport { uuid, username, stats });
+            }
+            Ok::<_, OxideVaultError>(players)
+        })
+        .await
+        .map_err(|e| OxideVaultError::Database(format!("Task join error: {}", e)))??;
+
+        serde_json::to_string_pretty(&players)
+            .map_err(|e| OxideVaultError::Database(format!("Failed to serialize players: {}", e)))
+    }
+
+    /// Import players (and their statistics) from JSON written by
+    /// [`export_json`](Self::export_json).
+    ///
+    /// Everything is imported in one transaction; on error nothing is written.
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of players imported.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`OxideVaultError::Validation`] error if the JSON is malformed
+    /// or any entry's `uuid` is not a valid UUID.
+    pub async fn import_json(&self, json: &str) -> Result<usize> {
+        let mut exports: Vec<PlayerExport> = serde_json::from_str(json)
+            .map_err(|e| OxideVaultError::Validation(format!("Invalid player export: {}", e)))?;
+        for (index, export) in exports.iter_mut().enumerate() {
+            export.uuid = normalize_uuid(&export.uuid).ok_or_else(|| OxideVaultError::Validation(format!(
+                "Invalid UUID '{}' for player '{}' (entry {})",
+                export.uuid, export.username, index + 1
+            )))?;
+        }
+
+        let pool = self.pool.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = connection(&pool)?;
+            let tx = conn.transaction()?;
+
+            let players: Vec<MinecraftPlayer> = exports
+                .iter()
+                .map(|export| MinecraftPlayer {
+                    uuid: export.uuid.clone(),
+                    username: export.username.clone(),
+                })
+                .collect();
+            upsert_players_in(&tx, &players)?;
+
+            let stats: Vec<PlayerStat> = exports
+                .iter()
+                .flat_map(|export| export.stats.iter().map(|stat| PlayerStat {
+                    mc_uuid: export.uuid.clone(),
+                    stat_name: stat.name.clone(),
+                    stat_value: stat.value,
+                    timestamp: stat.timestamp,
+                }))
+                .collect();
+            upsert_stats_in(&tx, &stats)?;
+
+            tx.commit()?;
+            Ok(exports.len())
+        })
+        .await
+        .map_err(|e| OxideVaultError::Database(format!("Task join error: {}", e)))?
+    }
+
     /// Helper function to query a single player by a specific column.
     async fn get_player_by_column(&self, column: &str, value: &str) -> Result<Option<MinecraftPlayer>> {
-        let db_path = self.db_path.clone();
+        let pool = self.pool.clone();
         let query = format!("SELECT mc_uuid, mc_username FROM minecraft_users WHERE {} = ?1", column);
         let value = value.to_string();
 
         tokio::task::spawn_blocking(move || {
-            let conn = Connection::open(&db_path)?;
+            let conn = connection(&pool)?;
             let mut stmt = conn.prepare(&query)?;
             let mut rows = stmt.query(rusqlite::params![value])?;
 
@@ -147,84 +583,659 @@ impl PlayerRepository {
             }
         })
         .await
-        .map_err(|e| OxideVaultError::Database(format!("Task join error: {}", e)))?
+        .map_err(|e| OxideVaultError::Database(format!("Task join error: {}", e)))?
+    }
+
+    /// Get a player by UUID.
+    ///
+    /// # Arguments
+    ///
+    /// * `uuid` - The player's UUID
+    ///
+    /// # Returns
+    ///
+    /// Returns `Some(player)` if found, `None` otherwise.
+    pub async fn get_player_by_uuid(&self, uuid: &str) -> Result<Option<MinecraftPlayer>> {
+        self.get_player_by_column("mc_uuid", &canonical_uuid(uuid)).await
+    }
+
+    /// Get a player by username.
+    ///
+    /// # Arguments
+    ///
+    /// * `username` - The player's username
+    ///
+    /// # Returns
+    ///
+    /// Returns `Some(player)` if found, `None` otherwise.
+    pub async fn get_player_by_username(&self, username: &str) -> Result<Option<MinecraftPlayer>> {
+        self.get_player_by_column("mc_username", username).await
+    }
+
+    /// Search players whose username contains `query`, ordered by username.
+    ///
+    /// `%` and `_` in the query are matched literally. At most `limit`
+    /// players are returned.
+    pub async fn search_by_username(&self, query: &str, limit: usize) -> Result<Vec<MinecraftPlayer>> {
+        let pool = self.pool.clone();
+        let pattern = format!("%{}%", escape_like(query));
+        let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+        tokio::task::spawn_blocking(move || {
+            let conn = connection(&pool)?;
+            let mut stmt = conn.prepare(
+                "SELECT mc_uuid, mc_username FROM minecraft_users
+                 WHERE mc_username LIKE ?1 ESCAPE '\\'
+                 ORDER BY mc_username LIMIT ?2"
+            )?;
+
+            let rows = stmt.query_map(rusqlite::params![pattern, limit], |row| {
+                Ok(MinecraftPlayer {
+                    uuid: row.get(0)?,
+                    username: row.get(1)?,
+                })
+            })?;
+
+            let mut players = Vec::new();
+            for player in rows {
+                players.push(player?);
+            }
+            Ok(players)
+        })
+        .await
+        .map_err(|e| OxideVaultError::Database(format!("Task join error: {}", e)))?
+    }
+
+    /// Get all players from the database.
+    pub async fn get_all_players(&self) -> Result<Vec<MinecraftPlayer>> {
+        let pool = self.pool.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = connection(&pool)?;
+            let mut stmt = conn.prepare(
+                "SELECT mc_uuid, mc_username FROM minecraft_users ORDER BY mc_username"
+            )?;
+
+            let rows = stmt.query_map([], |row| {
+                Ok(MinecraftPlayer {
+                    uuid: row.get(0)?,
+                    username: row.get(1)?,
+                })
+            })?;
+
+            let mut players = Vec::new();
+            for player in rows {
+                players.push(player?);
+            }
+            Ok(players)
+        })
+        .await
+        .map_err(|e| OxideVaultError::Database(format!("Task join error: {}", e)))?
+    }
+
+    /// Delete a player from the database.
+    ///
+    /// # Arguments
+    ///
+    /// * `uuid` - The player's UUID
+    pub async fn delete_player(&self, uuid: &str) -> Result<()> {
+        let pool = self.pool.clone();
+        let uuid = canonical_uuid(uuid);
+        tokio::task::spawn_blocking(move || {
+            let conn = connection(&pool)?;
+            conn.execute(
+                "DELETE FROM minecraft_users WHERE mc_uuid = ?1",
+                rusqlite::params![uuid],
+            )?;
+            Ok::<_, OxideVaultError>(())
+        })
+        .await
+        .map_err(|e| OxideVaultError::Database(format!("Task join error: {}", e)))??;
+        Ok(())
+    }
+
+    /// Find rows referencing players that no longer exist.
+    ///
+    /// Foreign keys are only enforced on connections that enable them, so rows
+    /// can be orphaned when a player is deleted without cascading. This check
+    /// complements `PRAGMA integrity_check`, which doesn't look at references.
+    pub async fn find_orphans(&self) -> Result<Vec<OrphanRow>> {
+        let pool = self.pool.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = connection(&pool)?;
+            let mut orphans = Vec::new();
+
+            for &table in PLAYER_CHILD_TABLES {
+                let mut stmt = conn.prepare(&format!(
+                    "SELECT t.mc_uuid, COUNT(*) FROM {} t
+                     LEFT JOIN minecraft_users u ON u.mc_uuid = t.mc_uuid
+                     WHERE u.mc_uuid IS NULL
+                     GROUP BY t.mc_uuid
+                     ORDER BY t.mc_uuid",
+                    table
+                ))?;
+
+                let rows = stmt.query_map([], |row| {
+                    Ok(OrphanRow {
+                        table,
+                        mc_uuid: row.get(0)?,
+                        count: row.get::<_, i64>(1)? as u64,
+                    })
+                })?;
+
+                for orphan in rows {
+                    orphans.push(orphan?);
+                }
+            }
+
+            Ok(orphans)
+        })
+        .await
+        .map_err(|e| OxideVaultError::Database(format!("Task join error: {}", e)))?
+    }
+
+    /// Delete rows referencing players that no longer exist.
+    ///
+    /// # Returns
+    ///
+    /// Returns the total number of rows deleted.
+    pub async fn delete_orphans(&self) -> Result<u64> {
+        let pool = self.pool.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = connection(&pool)?;
+            let tx = conn.transaction()?;
+            let mut deleted = 0;
+
+            for table in PLAYER_CHILD_TABLES {
+                deleted += tx.execute(
+                    &format!(
+                        "DELETE FROM {} WHERE mc_uuid NOT IN (SELECT mc_uuid FROM minecraft_users)",
+                        table
+                    ),
+                    [],
+                )? as u64;
+            }
+
+            tx.commit()?;
+            Ok(deleted)
+        })
+        .await
+        .map_err(|e| OxideVaultError::Database(format!("Task join error: {}", e)))?
+    }
+
+    /// Link a Discord account to a Minecraft account.
+    ///
+    /// Existing links are never replaced: each Discord account can be linked
+    /// to one Minecraft account and vice versa. The player must already be
+    /// stored.
+    pub async fn link_account(&self, discord_id: u64, mc_uuid: &str) -> Result<LinkOutcome> {
+        let pool = self.pool.clone();
+        let mc_uuid = canonical_uuid(mc_uuid);
+        tokio::task::spawn_blocking(move || {
+            let mut conn = connection(&pool)?;
+            let tx = conn.transaction()?;
+
+            let existing: Option<String> = tx.query_row(
+                "SELECT mc_uuid FROM discord_links WHERE discord_id = ?1",
+                rusqlite::params![discord_id as i64],
+                |row| row.get(0),
+            ).optional()?;
+            if let Some(existing) = existing {
+                return Ok(LinkOutcome::AlreadyLinked(existing));
+            }
+
+            let owner: Option<i64> = tx.query_row(
+                "SELECT discord_id FROM discord_links WHERE mc_uuid = ?1",
+                rusqlite::params![mc_uuid],
+                |row| row.get(0),
+            ).optional()?;
+            if let Some(owner) = owner {
+                return Ok(LinkOutcome::TakenBy(owner as u64));
+            }
+
+            let linked_at = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs() as i64);
+            tx.execute(
+                "INSERT INTO discord_links (discord_id, mc_uuid, linked_at) VALUES (?1, ?2, ?3)",
+                rusqlite::params![discord_id as i64, mc_uuid, linked_at],
+            )?;
+            tx.commit()?;
+            Ok(LinkOutcome::Linked)
+        })
+        .await
+        .map_err(|e| OxideVaultError::Database(format!("Task join error: {}", e)))?
+    }
+
+    /// Get the Minecraft UUID linked to a Discord account, if any.
+    pub async fn get_linked_uuid(&self, discord_id: u64) -> Result<Option<String>> {
+        let pool = self.pool.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = connection(&pool)?;
+            let uuid = conn.query_row(
+                "SELECT mc_uuid FROM discord_links WHERE discord_id = ?1",
+                rusqlite::params![discord_id as i64],
+                |row| row.get(0),
+            ).optional()?;
+            Ok(uuid)
+        })
+        .await
+        .map_err(|e| OxideVaultError::Database(format!("Task join error: {}", e)))?
+    }
+
+    /// Get the Minecraft account linked to a Discord account, if any.
+    pub async fn get_link(&self, discord_id: u64) -> Result<Option<AccountLink>> {
+        let pool = self.pool.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = connection(&pool)?;
+            let link = conn.query_row(
+                "SELECT l.mc_uuid, u.mc_username, l.linked_at FROM discord_links l
+                    JOIN minecraft_users u ON u.mc_uuid = l.mc_uuid
+                    WHERE l.discord_id = ?1",
+                rusqlite::params![discord_id as i64],
+                |row| Ok(AccountLink {
+                    mc_uuid: row.get(0)?,
+                    username: row.get(1)?,
+                    linked_at: row.get(2)?,
+                }),
+            ).optional()?;
+            Ok(link)
+        })
+        .await
+        .map_err(|e| OxideVaultError::Database(format!("Task join error: {}", e)))?
+    }
+
+    /// Get a player's previous usernames, most recent change first.
+    ///
+    /// # Returns
+    ///
+    /// Returns `(old_username, changed_at)` pairs, with `changed_at` as a Unix
+    /// timestamp (seconds).
+    pub async fn get_username_history(&self, mc_uuid: &str) -> Result<Vec<(String, i64)>> {
+        let pool = self.pool.clone();
+        let mc_uuid = canonical_uuid(mc_uuid);
+        tokio::task::spawn_blocking(move || {
+            let conn = connection(&pool)?;
+            let mut stmt = conn.prepare(
+                "SELECT old_username, changed_at FROM username_history
+                 WHERE mc_uuid = ?1 ORDER BY changed_at DESC, rowid DESC"
+            )?;
+
+            let rows = stmt.query_map(rusqlite::params![mc_uuid], |row| Ok((row.get(0)?, row.get(1)?)))?;
+
+            let mut history = Vec::new();
+            for entry in rows {
+                history.push(entry?);
+            }
+            Ok(history)
+        })
+        .await
+        .map_err(|e| OxideVaultError::Database(format!("Task join error: {}", e)))?
+    }
+
+    /// Record when a player was last seen online.
+    ///
+    /// Players that aren't in the database are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `mc_uuid` - The player's UUID
+    /// * `timestamp` - Unix timestamp (seconds)
+    pub async fn update_last_seen(&self, mc_uuid: &str, timestamp: i64) -> Result<()> {
+        let pool = self.pool.clone();
+        let mc_uuid = canonical_uuid(mc_uuid);
+        tokio::task::spawn_blocking(move || {
+            let conn = connection(&pool)?;
+            conn.execute(
+                "UPDATE minecraft_users SET last_seen = ?2 WHERE mc_uuid = ?1",
+                rusqlite::params![mc_uuid, timestamp],
+            )?;
+            Ok::<_, OxideVaultError>(())
+        })
+        .await
+        .map_err(|e| OxideVaultError::Database(format!("Task join error: {}", e)))??;
+        Ok(())
+    }
+
+    /// Get when a player was last seen online.
+    ///
+    /// # Returns
+    ///
+    /// Returns the Unix timestamp (seconds), or `None` if the player is unknown
+    /// or has never been seen.
+    pub async fn get_last_seen(&self, mc_uuid: &str) -> Result<Option<i64>> {
+        let pool = self.pool.clone();
+        let mc_uuid = canonical_uuid(mc_uuid);
+        tokio::task::spawn_blocking(move || {
+            let conn = connection(&pool)?;
+            let mut stmt = conn.prepare("SELECT last_seen FROM minecraft_users WHERE mc_uuid = ?1")?;
+            let mut rows = stmt.query(rusqlite::params![mc_uuid])?;
+
+            match rows.next()? {
+                Some(row) => Ok(row.get::<_, Option<i64>>(0)?),
+                None => Ok(None),
+            }
+        })
+        .await
+        .map_err(|e| OxideVaultError::Database(format!("Task join error: {}", e)))?
+    }
+
+    /// Insert or update a player statistic.
+    ///
+    /// An existing entry for the same player and statistic name has its value
+    /// and timestamp replaced.
+    pub async fn upsert_stat(&self, stat: PlayerStat) -> Result<()> {
+        let pool = self.pool.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = connection(&pool)?;
+            conn.execute(
+                "INSERT INTO player_stats (mc_uuid, stat_name, stat_value, timestamp)
+                 VALUES (?1, ?2, ?3, ?4)
+                 ON CONFLICT(mc_uuid, stat_name) DO UPDATE SET stat_value = ?3, timestamp = ?4",
+                rusqlite::params![canonical_uuid(&stat.mc_uuid), stat.stat_name, stat.stat_value, stat.timestamp],
+            )?;
+            Ok::<_, OxideVaultError>(())
+        })
+        .await
+        .map_err(|e| OxideVaultError::Database(format!("Task join error: {}", e)))??;
+        Ok(())
+    }
+
+    /// Insert or update many statistics in a single transaction.
+    ///
+    /// Either every stat is written or, on error, none are. Returns the number
+    /// of stats written.
+    pub async fn upsert_stats(&self, stats: Vec<PlayerStat>) -> Result<usize> {
+        let pool = self.pool.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = connection(&pool)?;
+            let tx = conn.transaction()?;
+            upsert_stats_in(&tx, &stats)?;
+            tx.commit()?;
+            Ok(stats.len())
+        })
+        .await
+        .map_err(|e| OxideVaultError::Database(format!("Task join error: {}", e)))?
+    }
+
+    /// Get all statistics recorded for a player, ordered by name.
+    pub async fn get_stats_for_player(&self, mc_uuid: &str) -> Result<Vec<PlayerStat>> {
+        let pool = self.pool.clone();
+        let mc_uuid = canonical_uuid(mc_uuid);
+        tokio::task::spawn_blocking(move || {
+            let conn = connection(&pool)?;
+            let mut stmt = conn.prepare(
+                "SELECT mc_uuid, stat_name, stat_value, timestamp FROM player_stats
+                 WHERE mc_uuid = ?1 ORDER BY stat_name"
+            )?;
+
+            let rows = stmt.query_map(rusqlite::params![mc_uuid], |row| {
+                Ok(PlayerStat {
+                    mc_uuid: row.get(0)?,
+                    stat_name: row.get(1)?,
+                    stat_value: row.get(2)?,
+                    timestamp: row.get(3)?,
+                })
+            })?;
+
+            let mut stats = Vec::new();
+            for stat in rows {
+                stats.push(stat?);
+            }
+            Ok(stats)
+        })
+        .await
+        .map_err(|e| OxideVaultError::Database(format!("Task join error: {}", e)))?
+    }
+
+    /// Get a single statistic for a player.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Some(stat)` if recorded, `None` otherwise.
+    pub async fn get_stat(&self, mc_uuid: &str, stat_name: &str) -> Result<Option<PlayerStat>> {
+        let pool = self.pool.clone();
+        let mc_uuid = canonical_uuid(mc_uuid);
+        let stat_name = stat_name.to_string();
+        tokio::task::spawn_blocking(move || {
+            let conn = connection(&pool)?;
+            let mut stmt = conn.prepare(
+                "SELECT mc_uuid, stat_name, stat_value, timestamp FROM player_stats
+                 WHERE mc_uuid = ?1 AND stat_name = ?2"
+            )?;
+            let mut rows = stmt.query(rusqlite::params![mc_uuid, stat_name])?;
+
+            if let Some(row) = rows.next()? {
+                Ok(Some(PlayerStat {
+                    mc_uuid: row.get(0)?,
+                    stat_name: row.get(1)?,
+                    stat_value: row.get(2)?,
+                    timestamp: row.get(3)?,
+                }))
+            } else {
+                Ok(None)
+            }
+        })
+        .await
+        .map_err(|e| OxideVaultError::Database(format!("Task join error: {}", e)))?
+    }
+}
+
+/// Repository for per-guild settings.
+#[derive(Clone)]
+pub struct GuildRepository {
+    pool: Pool<SqliteConnectionManager>,
+}
+
+impl GuildRepository {
+    /// Create a new guild settings repository with its own connection pool.
+    ///
+    /// Use [`PlayerRepository::guilds`] to share an existing pool instead.
+    pub fn new(db_path: String) -> Self {
+        Self { pool: open_pool(db_path, DEFAULT_POOL_TIMEOUT) }
+    }
+
+    /// Get the settings stored for a guild.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Some(settings)` if the guild has stored settings, `None` otherwise.
+    pub async fn get_guild_settings(&self, guild_id: u64) -> Result<Option<GuildSettings>> {
+        let pool = self.pool.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = connection(&pool)?;
+            let mut stmt = conn.prepare(
+                "SELECT guild_id, status_channel, server_address, locale, backup_folder
+                 FROM guild_settings WHERE guild_id = ?1"
+            )?;
+            let mut rows = stmt.query(rusqlite::params![guild_id as i64])?;
+
+            if let Some(row) = rows.next()? {
+                Ok(Some(GuildSettings {
+                    guild_id: row.get::<_, i64>(0)? as u64,
+                    status_channel: row.get::<_, Option<i64>>(1)?.map(|id| id as u64),
+                    server_address: row.get(2)?,
+                    locale: row.get(3)?,
+                    backup_folder: row.get(4)?,
+                }))
+            } else {
+                Ok(None)
+            }
+        })
+        .await
+        .map_err(|e| OxideVaultError::Database(format!("Task join error: {}", e)))?
+    }
+
+    /// Insert or update a guild's settings.
+    ///
+    /// Fields set to `None` keep their currently stored value, so callers can
+    /// update a single setting without clearing the others; use
+    /// [`GuildRepository::clear_guild_setting`] to clear one.
+    ///
+    /// # Arguments
+    ///
+    /// * `settings` - The settings to save
+    pub async fn upsert_guild_settings(&self, settings: GuildSettings) -> Result<()> {
+        let pool = self.pool.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = connection(&pool)?;
+            conn.execute(
+                "INSERT INTO guild_settings (guild_id, status_channel, server_address, locale, backup_folder)
+                 VALUES (?1, ?2, ?3, ?4, ?5)
+                 ON CONFLICT(guild_id) DO UPDATE SET
+                    status_channel = COALESCE(excluded.status_channel, status_channel),
+                    server_address = COALESCE(excluded.server_address, server_address),
+                    locale = COALESCE(excluded.locale, locale),
+                    backup_folder = COALESCE(excluded.backup_folder, backup_folder)",
+                rusqlite::params![
+                    settings.guild_id as i64,
+                    settings.status_channel.map(|id| id as i64),
+                    settings.server_address,
+                    settings.locale,
+                    settings.backup_folder,
+                ],
+            )?;
+            Ok::<_, OxideVaultError>(())
+        })
+        .await
+        .map_err(|e| OxideVaultError::Database(format!("Task join error: {}", e)))??;
+        Ok(())
+    }
+
+    /// Clear one of a guild's settings so it falls back to the global default.
+    ///
+    /// The guild's other settings are kept; clearing a guild without stored
+    /// settings does nothing.
+    pub async fn clear_guild_setting(&self, guild_id: u64, key: GuildSettingKey) -> Result<()> {
+        let pool = self.pool.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = connection(&pool)?;
+            conn.execute(
+                &format!("UPDATE guild_settings SET {} = NULL WHERE guild_id = ?1", key.column()),
+                rusqlite::params![guild_id as i64],
+            )?;
+            Ok::<_, OxideVaultError>(())
+        })
+        .await
+        .map_err(|e| OxideVaultError::Database(format!("Task join error: {}", e)))??;
+        Ok(())
+    }
+
+    /// Set or clear the Minecraft server address of a guild.
+    ///
+    /// Passing `None` clears the address so the guild falls back to the global
+    /// default; the guild's other settings are kept.
+    pub async fn set_server_address(&self, guild_id: u64, server_address: Option<String>) -> Result<()> {
+        let pool = self.pool.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = connection(&pool)?;
+            conn.execute(
+                "INSERT INTO guild_settings (guild_id, server_address)
+                 VALUES (?1, ?2)
+                 ON CONFLICT(guild_id) DO UPDATE SET server_address = excluded.server_address",
+                rusqlite::params![guild_id as i64, server_address],
+            )?;
+            Ok::<_, OxideVaultError>(())
+        })
+        .await
+        .map_err(|e| OxideVaultError::Database(format!("Task join error: {}", e)))??;
+        Ok(())
     }
+}
 
-    /// Get a player by UUID.
-    ///
-    /// # Arguments
-    ///
-    /// * `uuid` - The player's UUID
-    ///
-    /// # Returns
+/// Repository for server status history.
+#[derive(Clone)]
+pub struct StatusHistoryRepository {
+    pool: Pool<SqliteConnectionManager>,
+}
+
+impl StatusHistoryRepository {
+    /// Create a new status history repository with its own connection pool.
     ///
-    /// Returns `Some(player)` if found, `None` otherwise.
-    #[allow(dead_code)]
-    pub async fn get_player_by_uuid(&self, uuid: &str) -> Result<Option<MinecraftPlayer>> {
-        self.get_player_by_column("mc_uuid", uuid).await
+    /// Use [`PlayerRepository::status_history`] to share an existing pool instead.
+    pub fn new(db_path: String) -> Self {
+        Self { pool: open_pool(db_path, DEFAULT_POOL_TIMEOUT) }
     }
 
-    /// Get a player by username.
+    /// Record a status sample of the server at `server` (its address).
+    pub async fn record_status(&self, server: &str, sample: StatusSample) -> Result<()> {
+        let pool = self.pool.clone();
+        let server = server.to_string();
+        tokio::task::spawn_blocking(move || {
+            let conn = connection(&pool)?;
+            conn.execute(
+                "INSERT INTO player_count_history (server, timestamp, online, max) VALUES (?1, ?2, ?3, ?4)",
+                rusqlite::params![server, sample.timestamp, sample.online, sample.max],
+            )?;
+            Ok::<_, OxideVaultError>(())
+        })
+        .await
+        .map_err(|e| OxideVaultError::Database(format!("Task join error: {}", e)))??;
+        Ok(())
+    }
+
+    /// Get the status samples of a server recorded between `since` and
+    /// `until` (inclusive), oldest first.
     ///
     /// # Arguments
     ///
-    /// * `username` - The player's username
-    ///
-    /// # Returns
-    ///
-    /// Returns `Some(player)` if found, `None` otherwise.
-    #[allow(dead_code)]
-    pub async fn get_player_by_username(&self, username: &str) -> Result<Option<MinecraftPlayer>> {
-        self.get_player_by_column("mc_username", username).await
-    }
-
-    /// Get all players from the database.
-    #[allow(dead_code)]
-    pub async fn get_all_players(&self) -> Result<Vec<MinecraftPlayer>> {
-        let db_path = self.db_path.clone();
+    /// * `server` - Address the samples were recorded under
+    /// * `since` - Start of the range (Unix timestamp, seconds)
+    /// * `until` - End of the range (Unix timestamp, seconds)
+    pub async fn get_status_history(&self, server: &str, since: i64, until: i64) -> Result<Vec<StatusSample>> {
+        let pool = self.pool.clone();
+        let server = server.to_string();
         tokio::task::spawn_blocking(move || {
-            let conn = Connection::open(&db_path)?;
+            let conn = connection(&pool)?;
             let mut stmt = conn.prepare(
-                "SELECT mc_uuid, mc_username FROM minecraft_users ORDER BY mc_username"
+                "SELECT timestamp, online, max FROM player_count_history
+                 WHERE server = ?1 AND timestamp BETWEEN ?2 AND ?3
+                 ORDER BY timestamp"
             )?;
 
-            let rows = stmt.query_map([], |row| {
-                Ok(MinecraftPlayer {
-                    uuid: row.get(0)?,
-                    username: row.get(1)?,
+            let rows = stmt.query_map(rusqlite::params![server, since, until], |row| {
+                Ok(StatusSample {
+                    timestamp: row.get(0)?,
+                    online: row.get(1)?,
+                    max: row.get(2)?,
                 })
             })?;
 
-            let mut players = Vec::new();
-            for player in rows {
-                players.push(player?);
+            let mut samples = Vec::new();
+            for sample in rows {
+                samples.push(sample?);
             }
-            Ok(players)
+            Ok(samples)
         })
         .await
         .map_err(|e| OxideVaultError::Database(format!("Task join error: {}", e)))?
     }
 
-    /// Delete a player from the database.
+    /// Get every status sample of a server recorded since `since` (Unix
+    /// timestamp, seconds), oldest first.
     ///
-    /// # Arguments
+    /// Failed background polls aren't recorded, so outages show up as gaps
+    /// between samples.
+    pub async fn get_count_history(&self, server: &str, since: i64) -> Result<Vec<StatusSample>> {
+        self.get_status_history(server, since, i64::MAX).await
+    }
+
+    /// Attribute samples recorded before history was keyed by server to
+    /// `server`, returning how many were claimed.
     ///
-    /// * `uuid` - The player's UUID
-    #[allow(dead_code)]
-    pub async fn delete_player(&self, uuid: &str) -> Result<()> {
-        let db_path = self.db_path.clone();
-        let uuid = uuid.to_string();
+    /// Those samples came from the background poller and `/online`, which
+    /// mostly pinged the default server.
+    pub async fn claim_unkeyed_samples(&self, server: &str) -> Result<usize> {
+        let pool = self.pool.clone();
+        let server = server.to_string();
         tokio::task::spawn_blocking(move || {
-            let conn = Connection::open(&db_path)?;
-            conn.execute(
-                "DELETE FROM minecraft_users WHERE mc_uuid = ?1",
-                rusqlite::params![uuid],
+            let conn = connection(&pool)?;
+            let claimed = conn.execute(
+                "UPDATE player_count_history SET server = ?1 WHERE server = ''",
+                rusqlite::params![server],
             )?;
-            Ok::<_, OxideVaultError>(())
+            Ok(claimed)
         })
         .await
-        .map_err(|e| OxideVaultError::Database(format!("Task join error: {}", e)))??;
-        Ok(())
+        .map_err(|e| OxideVaultError::Database(format!("Task join error: {}", e)))?
     }
 }
 
@@ -245,6 +1256,192 @@ mod tests {
         (temp_dir, repo)
     }
 
+    #[tokio::test]
+    async fn test_player_repository_pool_pragmas() {
+        let (_temp_dir, repo) = setup_test_db().await;
+
+        let conn = repo.pool.get().unwrap();
+        let journal_mode: String = conn.query_row("PRAGMA journal_mode", [], |row| row.get(0)).unwrap();
+        let foreign_keys: i64 = conn.query_row("PRAGMA foreign_keys", [], |row| row.get(0)).unwrap();
+        assert_eq!(journal_mode, "wal");
+        assert_eq!(foreign_keys, 1);
+    }
+
+    #[tokio::test]
+    async fn test_exhausted_pool_times_out() {
+        let (temp_dir, _) = setup_test_db().await;
+        let db_path = temp_dir.path().join("test.db").to_str().unwrap().to_string();
+        let repo = PlayerRepository::with_pool_timeout(db_path, Duration::from_millis(200));
+
+        // Hold every connection the pool can open
+        let held: Vec<_> = (0..repo.pool.max_size()).map(|_| repo.pool.get().unwrap()).collect();
+        assert_eq!(repo.pool_stats(), PoolStats { idle: 0, in_use: repo.pool.max_size() });
+
+        let started = std::time::Instant::now();
+        let err = repo.get_player_by_username("Steve").await.unwrap_err();
+        assert!(started.elapsed() < Duration::from_secs(2));
+        assert!(matches!(&err, OxideVaultError::Database(msg) if msg == "connection pool exhausted"));
+
+        // Connections are usable again once returned
+        drop(held);
+        assert!(repo.get_player_by_username("Steve").await.unwrap().is_none());
+        assert_eq!(repo.pool_stats().in_use, 0);
+    }
+
+    #[tokio::test]
+    async fn test_guild_and_history_repositories_share_the_pool() {
+        let (temp_dir, _) = setup_test_db().await;
+        let db_path = temp_dir.path().join("test.db").to_str().unwrap().to_string();
+        let repo = PlayerRepository::with_pool_timeout(db_path, Duration::from_millis(200));
+        let (guilds, history) = (repo.guilds(), repo.status_history());
+
+        let held: Vec<_> = (0..repo.pool.max_size()).map(|_| repo.pool.get().unwrap()).collect();
+        assert!(guilds.get_guild_settings(1).await.is_err());
+        assert!(history.get_count_history("mc.example.com", 0).await.is_err());
+
+        drop(held);
+        assert!(guilds.get_guild_settings(1).await.unwrap().is_none());
+        assert!(history.get_count_history("mc.example.com", 0).await.unwrap().is_empty());
+        assert_eq!(repo.pool_stats().in_use, 0);
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_empties_wal() {
+        let (temp_dir, repo) = setup_test_db().await;
+        repo.upsert_player(MinecraftPlayer { uuid: "c".repeat(32), username: "Wal".to_string() })
+            .await
+            .unwrap();
+
+        repo.checkpoint().await.unwrap();
+        let wal = temp_dir.path().join("test.db-wal");
+        assert!(!wal.exists() || std::fs::metadata(&wal).unwrap().len() == 0);
+        assert!(repo.get_player_by_username("Wal").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_search_by_username() {
+        let (_temp_dir, repo) = setup_test_db().await;
+
+        for (i, username) in ["Steve", "steve_2", "Alex", "SteveXYZ", "Notch"].iter().enumerate() {
+            repo.upsert_player(MinecraftPlayer {
+                uuid: format!("550e8400-e29b-41d4-a716-4466554400{:02}", 40 + i),
+                username: username.to_string(),
+            }).await.unwrap();
+        }
+
+        let names = |players: Vec<MinecraftPlayer>| -> Vec<String> {
+            players.into_iter().map(|p| p.username).collect()
+        };
+
+        // Case-insensitive substring match, ordered by username
+        let found = repo.search_by_username("teve", 10).await.unwrap();
+        assert_eq!(names(found), vec!["Steve", "SteveXYZ", "steve_2"]);
+
+        let found = repo.search_by_username("steve", 2).await.unwrap();
+        assert_eq!(found.len(), 2);
+
+        // Wildcards are matched literally
+        let found = repo.search_by_username("_", 10).await.unwrap();
+        assert_eq!(names(found), vec!["steve_2"]);
+        assert!(repo.search_by_username("%", 10).await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_escape_like() {
+        assert_eq!(escape_like("Steve"), "Steve");
+        assert_eq!(escape_like("a_b%c\\d"), "a\\_b\\%c\\\\d");
+    }
+
+    #[tokio::test]
+    async fn test_upsert_players_bulk() {
+        let (_temp_dir, repo) = setup_test_db().await;
+
+        let players: Vec<MinecraftPlayer> = (0..1000)
+            .rev()
+            .map(|i| MinecraftPlayer {
+                uuid: format!("{:032x}", i),
+                username: format!("Player{:04}", i),
+            })
+            .collect();
+        repo.upsert_players(players).await.unwrap();
+
+        let stored = repo.get_all_players().await.unwrap();
+        assert_eq!(stored.len(), 1000);
+        assert_eq!(stored[0].username, "Player0000");
+        assert_eq!(stored[999].username, "Player0999");
+        assert!(stored.windows(2).all(|pair| pair[0].username < pair[1].username));
+    }
+
+    #[tokio::test]
+    async fn test_upsert_players_rolls_back_on_error() {
+        let (_temp_dir, repo) = setup_test_db().await;
+
+        // The trigger makes the second row fail, after the first was written
+        repo.pool.get().unwrap().execute_batch(
+            "CREATE TRIGGER reject_bad BEFORE INSERT ON minecraft_users
+             WHEN NEW.mc_username = 'Bad' BEGIN SELECT RAISE(ABORT, 'rejected'); END;"
+        ).unwrap();
+
+        let result = repo.upsert_players(vec![
+            MinecraftPlayer { uuid: "a".repeat(32), username: "Good".to_string() },
+            MinecraftPlayer { uuid: "b".repeat(32), username: "Bad".to_string() },
+        ]).await;
+
+        assert!(result.is_err());
+        assert!(repo.get_all_players().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_export_import_json_round_trip() {
+        let (_temp_dir, repo) = setup_test_db().await;
+
+        let uuid = "550e8400e29b41d4a716446655440050";
+        repo.upsert_player(MinecraftPlayer { uuid: uuid.to_string(), username: "Steve".to_string() })
+            .await.unwrap();
+        repo.upsert_player(MinecraftPlayer { uuid: "b".repeat(32), username: "Alex".to_string() })
+            .await.unwrap();
+        repo.upsert_stat(PlayerStat {
+            mc_uuid: uuid.to_string(),
+            stat_name: "deaths".to_string(),
+            stat_value: 3,
+            timestamp: 100,
+        }).await.unwrap();
+
+        let json = repo.export_json().await.unwrap();
+        let exported: Vec<PlayerExport> = serde_json::from_str(&json).unwrap();
+        assert_eq!(exported.len(), 2);
+        assert_eq!(exported[0].username, "Alex");
+        assert!(exported[0].stats.is_empty());
+        assert_eq!(exported[1].stats, vec![ExportedStat { name: "deaths".to_string(), value: 3, timestamp: 100 }]);
+
+        let (_other_dir, restored) = setup_test_db().await;
+        assert_eq!(restored.import_json(&json).await.unwrap(), 2);
+        assert_eq!(restored.export_json().await.unwrap(), json);
+    }
+
+    #[tokio::test]
+    async fn test_import_json_invalid() {
+        let (_temp_dir, repo) = setup_test_db().await;
+
+        assert!(matches!(repo.import_json("not json").await, Err(OxideVaultError::Validation(_))));
+
+        // An invalid UUID rejects the whole import, naming the entry
+        let json = r#"[{"uuid":"550e8400e29b41d4a716446655440051","username":"Alex"},{"uuid":"abc","username":"Steve"}]"#;
+        match repo.import_json(json).await {
+            Err(OxideVaultError::Validation(message)) => {
+                assert!(message.contains("'abc'") && message.contains("'Steve'") && message.contains("entry 2"));
+            }
+            other => panic!("expected a validation error, got {:?}", other),
+        }
+        assert!(repo.get_all_players().await.unwrap().is_empty());
+
+        // Stats default to empty when omitted
+        let imported = repo.import_json(r#"[{"uuid":"550E8400-E29B-41D4-A716-446655440051","username":"Alex"}]"#)
+            .await.unwrap();
+        assert_eq!(imported, 1);
+        assert!(repo.get_player_by_uuid("550e8400e29b41d4a716446655440051").await.unwrap().is_some());
+    }
+
     #[tokio::test]
     async fn test_upsert_player_insert() {
         let (_temp_dir, repo) = setup_test_db().await;
@@ -262,7 +1459,7 @@ mod tests {
         let retrieved = repo.get_player_by_uuid(&player.uuid).await.unwrap();
         assert!(retrieved.is_some());
         let retrieved = retrieved.unwrap();
-        assert_eq!(retrieved.uuid, player.uuid);
+        assert_eq!(retrieved.uuid, "550e8400e29b41d4a716446655440000");
         assert_eq!(retrieved.username, player.username);
     }
 
@@ -307,7 +1504,7 @@ mod tests {
         let result = repo.get_player_by_uuid(&player.uuid).await.unwrap();
         assert!(result.is_some());
         let retrieved = result.unwrap();
-        assert_eq!(retrieved.uuid, player.uuid);
+        assert_eq!(retrieved.uuid, "550e8400e29b41d4a716446655440002");
         assert_eq!(retrieved.username, player.username);
         
         // Test non-existent UUID
@@ -315,6 +1512,93 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[tokio::test]
+    async fn test_uuid_forms_hit_same_row() {
+        let (_temp_dir, repo) = setup_test_db().await;
+        let dashed = "069A79F4-44E9-4726-A5BE-FCA90E38AAF5";
+        let undashed = "069a79f444e94726a5befca90e38aaf5";
+
+        repo.upsert_player(MinecraftPlayer { uuid: dashed.to_string(), username: "Notch".to_string() })
+            .await
+            .unwrap();
+        repo.upsert_player(MinecraftPlayer { uuid: undashed.to_string(), username: "Notch2".to_string() })
+            .await
+            .unwrap();
+        assert_eq!(repo.get_all_players().await.unwrap().len(), 1);
+
+        let by_dashed = repo.get_player_by_uuid("069a79f4-44e9-4726-a5be-fca90e38aaf5").await.unwrap().unwrap();
+        let by_undashed = repo.get_player_by_uuid(undashed).await.unwrap().unwrap();
+        assert_eq!((by_dashed.uuid.as_str(), by_dashed.username.as_str()), (undashed, "Notch2"));
+        assert_eq!(by_undashed.username, "Notch2");
+
+        repo.delete_player(dashed).await.unwrap();
+        assert!(repo.get_player_by_uuid(undashed).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_migration_normalizes_stored_uuids() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db").to_str().unwrap().to_string();
+        init_db(&db_path).await.unwrap();
+
+        // Rows written in dashed form before UUIDs were normalized
+        {
+            let conn = Connection::open(&db_path).unwrap();
+            conn.execute_batch(
+                "PRAGMA foreign_keys = ON;
+                 INSERT INTO minecraft_users (mc_uuid, mc_username) VALUES
+                     ('069a79f4-44e9-4726-a5be-fca90e38aaf5', 'Notch'),
+                     ('8667ba71-b85a-4004-af54-457a9734eed7', 'SteveOld'),
+                     ('8667ba71b85a4004af54457a9734eed7', 'Steve');
+                 INSERT INTO player_stats (mc_uuid, stat_name, stat_value, timestamp) VALUES
+                     ('069a79f4-44e9-4726-a5be-fca90e38aaf5', 'deaths', 4, 1);
+                 ALTER TABLE guild_settings DROP COLUMN backup_folder;
+                 DROP TABLE player_count_history;
+                 PRAGMA user_version = 3;",
+            ).unwrap();
+        }
+        init_db(&db_path).await.unwrap();
+
+        let repo = PlayerRepository::new(db_path);
+        let players = repo.get_all_players().await.unwrap();
+        assert_eq!(
+            players.iter().map(|p| (p.uuid.as_str(), p.username.as_str())).collect::<Vec<_>>(),
+            vec![("069a79f444e94726a5befca90e38aaf5", "Notch"), ("8667ba71b85a4004af54457a9734eed7", "Steve")]
+        );
+        assert_eq!(repo.get_stat("069a79f444e94726a5befca90e38aaf5", "deaths").await.unwrap().unwrap().stat_value, 4);
+    }
+
+    #[tokio::test]
+    async fn test_migration_lowercases_undashed_uuids() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db").to_str().unwrap().to_string();
+        init_db(&db_path).await.unwrap();
+
+        // An undashed but uppercase UUID, as written by some older imports
+        {
+            let conn = Connection::open(&db_path).unwrap();
+            conn.execute_batch(
+                "PRAGMA foreign_keys = ON;
+                 INSERT INTO minecraft_users (mc_uuid, mc_username) VALUES
+                     ('069A79F444E94726A5BEFCA90E38AAF5', 'Notch');
+                 INSERT INTO player_stats (mc_uuid, stat_name, stat_value, timestamp) VALUES
+                     ('069A79F444E94726A5BEFCA90E38AAF5', 'deaths', 4, 1);
+                 ALTER TABLE guild_settings DROP COLUMN backup_folder;
+                 DROP TABLE player_count_history;
+                 PRAGMA user_version = 3;",
+            ).unwrap();
+        }
+        init_db(&db_path).await.unwrap();
+
+        let repo = PlayerRepository::new(db_path);
+        let players = repo.get_all_players().await.unwrap();
+        assert_eq!(
+            players.iter().map(|p| (p.uuid.as_str(), p.username.as_str())).collect::<Vec<_>>(),
+            vec![("069a79f444e94726a5befca90e38aaf5", "Notch")]
+        );
+        assert_eq!(repo.get_stat("069a79f444e94726a5befca90e38aaf5", "deaths").await.unwrap().unwrap().stat_value, 4);
+    }
+
     #[tokio::test]
     async fn test_get_player_by_username() {
         let (_temp_dir, repo) = setup_test_db().await;
@@ -329,7 +1613,7 @@ mod tests {
         let result = repo.get_player_by_username(&player.username).await.unwrap();
         assert!(result.is_some());
         let retrieved = result.unwrap();
-        assert_eq!(retrieved.uuid, player.uuid);
+        assert_eq!(retrieved.uuid, "550e8400e29b41d4a716446655440003");
         assert_eq!(retrieved.username, player.username);
         
         // Test non-existent username
@@ -399,4 +1683,410 @@ mod tests {
         let delete_result = repo.delete_player("non-existent-uuid").await;
         assert!(delete_result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_username_history_on_rename() {
+        let (_temp_dir, repo) = setup_test_db().await;
+
+        let uuid = "550e8400-e29b-41d4-a716-446655440030";
+        let upsert = |username: &str| repo.upsert_player(MinecraftPlayer {
+            uuid: uuid.to_string(),
+            username: username.to_string(),
+        });
+
+        // The initial insert has no previous name
+        upsert("FirstName").await.unwrap();
+        assert!(repo.get_username_history(uuid).await.unwrap().is_empty());
+
+        // Unchanged names aren't recorded
+        upsert("FirstName").await.unwrap();
+        assert!(repo.get_username_history(uuid).await.unwrap().is_empty());
+
+        upsert("SecondName").await.unwrap();
+        upsert("ThirdName").await.unwrap();
+
+        let history = repo.get_username_history(uuid).await.unwrap();
+        let names: Vec<_> = history.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["SecondName", "FirstName"]);
+        assert!(history.iter().all(|(_, changed_at)| *changed_at > 0));
+
+        let current = repo.get_player_by_uuid(uuid).await.unwrap().unwrap();
+        assert_eq!(current.username, "ThirdName");
+    }
+
+    #[tokio::test]
+    async fn test_link_account() {
+        let (_temp_dir, repo) = setup_test_db().await;
+
+        let steve = "a".repeat(32);
+        let alex = "b".repeat(32);
+        for (uuid, name) in [(&steve, "Steve"), (&alex, "Alex")] {
+            repo.upsert_player(MinecraftPlayer { uuid: uuid.clone(), username: name.to_string() })
+                .await.unwrap();
+        }
+
+        assert_eq!(repo.link_account(1, &steve).await.unwrap(), LinkOutcome::Linked);
+        assert_eq!(repo.get_linked_uuid(1).await.unwrap(), Some(steve.clone()));
+
+        // Existing links are kept
+        assert_eq!(repo.link_account(1, &alex).await.unwrap(), LinkOutcome::AlreadyLinked(steve.clone()));
+        assert_eq!(repo.link_account(2, &steve).await.unwrap(), LinkOutcome::TakenBy(1));
+        assert_eq!(repo.get_linked_uuid(2).await.unwrap(), None);
+
+        let link = repo.get_link(1).await.unwrap().unwrap();
+        assert_eq!(link.mc_uuid, steve);
+        assert_eq!(link.username, "Steve");
+        assert!(link.linked_at > 0);
+        assert_eq!(repo.get_link(2).await.unwrap(), None);
+
+        // Unknown players can't be linked
+        assert!(repo.link_account(3, &"c".repeat(32)).await.is_err());
+
+        // Discord IDs above i64::MAX round-trip
+        assert_eq!(repo.link_account(u64::MAX, &alex).await.unwrap(), LinkOutcome::Linked);
+        assert_eq!(repo.link_account(4, &alex).await.unwrap(), LinkOutcome::TakenBy(u64::MAX));
+    }
+
+    #[tokio::test]
+    async fn test_update_and_get_last_seen() {
+        let (_temp_dir, repo) = setup_test_db().await;
+
+        let uuid = "550e8400-e29b-41d4-a716-446655440020";
+        repo.upsert_player(MinecraftPlayer {
+            uuid: uuid.to_string(),
+            username: "SeenPlayer".to_string(),
+        }).await.unwrap();
+
+        // Known but never seen
+        assert_eq!(repo.get_last_seen(uuid).await.unwrap(), None);
+
+        repo.update_last_seen(uuid, 1_700_000_000).await.unwrap();
+        assert_eq!(repo.get_last_seen(uuid).await.unwrap(), Some(1_700_000_000));
+
+        // Upserting the player again keeps the timestamp
+        repo.upsert_player(MinecraftPlayer {
+            uuid: uuid.to_string(),
+            username: "RenamedPlayer".to_string(),
+        }).await.unwrap();
+        assert_eq!(repo.get_last_seen(uuid).await.unwrap(), Some(1_700_000_000));
+
+        // Unknown players are ignored
+        repo.update_last_seen("unknown", 1).await.unwrap();
+        assert_eq!(repo.get_last_seen("unknown").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_migrations_run_once() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db").to_str().unwrap().to_string();
+
+        // Running init twice must not re-apply migrations
+        init_db(&db_path).await.unwrap();
+        init_db(&db_path).await.unwrap();
+
+        let conn = Connection::open(&db_path).unwrap();
+        let version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0)).unwrap();
+        assert_eq!(version, MIGRATIONS.len() as i64);
+    }
+
+    #[tokio::test]
+    async fn test_upsert_stats_batch() {
+        let (_temp_dir, repo) = setup_test_db().await;
+        let uuid = "550e8400-e29b-41d4-a716-446655440011";
+        repo.upsert_player(MinecraftPlayer { uuid: uuid.to_string(), username: "Batch".to_string() })
+            .await
+            .unwrap();
+
+        let stat = |stat_name: &str, stat_value: i64| PlayerStat {
+            mc_uuid: uuid.to_string(),
+            stat_name: stat_name.to_string(),
+            stat_value,
+            timestamp: 1,
+        };
+        assert_eq!(repo.upsert_stats(vec![stat("deaths", 1), stat("kills", 2)]).await.unwrap(), 2);
+        assert_eq!(repo.upsert_stats(vec![stat("deaths", 3)]).await.unwrap(), 1);
+
+        let stats = repo.get_stats_for_player(uuid).await.unwrap();
+        assert_eq!(stats.iter().map(|s| s.stat_value).collect::<Vec<_>>(), vec![3, 2]);
+
+        // Stats for unknown players are rejected as a whole
+        let ghost = PlayerStat { mc_uuid: "ghost".to_string(), ..stat("deaths", 9) };
+        assert!(repo.upsert_stats(vec![stat("deaths", 5), ghost]).await.is_err());
+        assert_eq!(repo.get_stat(uuid, "deaths").await.unwrap().unwrap().stat_value, 3);
+    }
+
+    #[tokio::test]
+    async fn test_upsert_stat_insert_and_update() {
+        let (_temp_dir, repo) = setup_test_db().await;
+
+        let uuid = "550e8400-e29b-41d4-a716-446655440010".to_string();
+        repo.upsert_player(MinecraftPlayer {
+            uuid: uuid.clone(),
+            username: "StatPlayer".to_string(),
+        }).await.unwrap();
+
+        let stat = PlayerStat {
+            mc_uuid: uuid.clone(),
+            stat_name: "deaths".to_string(),
+            stat_value: 3,
+            timestamp: 1_000,
+        };
+        repo.upsert_stat(stat.clone()).await.unwrap();
+        assert_eq!(
+            repo.get_stat(&uuid, "deaths").await.unwrap(),
+            Some(PlayerStat { mc_uuid: "550e8400e29b41d4a716446655440010".to_string(), ..stat })
+        );
+
+        // Same player and name updates value and timestamp
+        repo.upsert_stat(PlayerStat {
+            mc_uuid: uuid.clone(),
+            stat_name: "deaths".to_string(),
+            stat_value: 5,
+            timestamp: 2_000,
+        }).await.unwrap();
+
+        let updated = repo.get_stat(&uuid, "deaths").await.unwrap().unwrap();
+        assert_eq!(updated.stat_value, 5);
+        assert_eq!(updated.timestamp, 2_000);
+        assert_eq!(repo.get_stats_for_player(&uuid).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_get_stats_for_player() {
+        let (_temp_dir, repo) = setup_test_db().await;
+
+        let uuid = "550e8400-e29b-41d4-a716-446655440011".to_string();
+        let other = "550e8400-e29b-41d4-a716-446655440012".to_string();
+        for (mc_uuid, username) in [(&uuid, "StatsA"), (&other, "StatsB")] {
+            repo.upsert_player(MinecraftPlayer {
+                uuid: mc_uuid.clone(),
+                username: username.to_string(),
+            }).await.unwrap();
+        }
+
+        for (mc_uuid, stat_name, stat_value) in [
+            (&uuid, "playtime", 3600),
+            (&uuid, "deaths", 2),
+            (&other, "deaths", 9),
+        ] {
+            repo.upsert_stat(PlayerStat {
+                mc_uuid: mc_uuid.clone(),
+                stat_name: stat_name.to_string(),
+                stat_value,
+                timestamp: 1_000,
+            }).await.unwrap();
+        }
+
+        let stats = repo.get_stats_for_player(&uuid).await.unwrap();
+        let names: Vec<_> = stats.iter().map(|stat| stat.stat_name.as_str()).collect();
+        assert_eq!(names, vec!["deaths", "playtime"]);
+
+        assert!(repo.get_stat(&uuid, "jumps").await.unwrap().is_none());
+        assert!(repo.get_stats_for_player("unknown").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_upsert_and_get_guild_settings() {
+        let (temp_dir, _repo) = setup_test_db().await;
+        let repo = GuildRepository::new(temp_dir.path().join("test.db").to_str().unwrap().to_string());
+
+        // Unknown guild has no settings
+        assert!(repo.get_guild_settings(42).await.unwrap().is_none());
+
+        let settings = GuildSettings {
+            guild_id: 1_234_567_890_123_456_789,
+            status_channel: Some(987_654_321_098_765_432),
+            server_address: Some("mc.example.com:25565".to_string()),
+            locale: Some("en-US".to_string()),
+            backup_folder: Some("creative".to_string()),
+        };
+        repo.upsert_guild_settings(settings.clone()).await.unwrap();
+
+        let retrieved = repo.get_guild_settings(settings.guild_id).await.unwrap();
+        assert_eq!(retrieved, Some(settings));
+    }
+
+    #[tokio::test]
+    async fn test_upsert_guild_settings_partial_update() {
+        let (temp_dir, _repo) = setup_test_db().await;
+        let repo = GuildRepository::new(temp_dir.path().join("test.db").to_str().unwrap().to_string());
+
+        repo.upsert_guild_settings(GuildSettings {
+            guild_id: 7,
+            status_channel: Some(100),
+            server_address: Some("old.example.com:25565".to_string()),
+            locale: None,
+            backup_folder: Some("survival".to_string()),
+        }).await.unwrap();
+
+        // Only change the server address and set a locale
+        repo.upsert_guild_settings(GuildSettings {
+            guild_id: 7,
+            server_address: Some("new.example.com:25565".to_string()),
+            locale: Some("fr".to_string()),
+            ..Default::default()
+        }).await.unwrap();
+
+        let retrieved = repo.get_guild_settings(7).await.unwrap().unwrap();
+        assert_eq!(retrieved.status_channel, Some(100));
+        assert_eq!(retrieved.server_address.as_deref(), Some("new.example.com:25565"));
+        assert_eq!(retrieved.locale.as_deref(), Some("fr"));
+        assert_eq!(retrieved.backup_folder.as_deref(), Some("survival"));
+    }
+
+    #[tokio::test]
+    async fn test_clear_guild_setting() {
+        let (temp_dir, _repo) = setup_test_db().await;
+        let repo = GuildRepository::new(temp_dir.path().join("test.db").to_str().unwrap().to_string());
+
+        let settings = GuildSettings {
+            guild_id: 7,
+            status_channel: Some(100),
+            server_address: Some("mc.example.com:25565".to_string()),
+            locale: Some("fr".to_string()),
+            backup_folder: Some("survival".to_string()),
+        };
+        repo.upsert_guild_settings(settings.clone()).await.unwrap();
+
+        repo.clear_guild_setting(7, GuildSettingKey::StatusChannel).await.unwrap();
+        repo.clear_guild_setting(7, GuildSettingKey::Locale).await.unwrap();
+        repo.clear_guild_setting(7, GuildSettingKey::BackupFolder).await.unwrap();
+
+        let retrieved = repo.get_guild_settings(7).await.unwrap().unwrap();
+        assert_eq!(retrieved, GuildSettings {
+            guild_id: 7,
+            server_address: settings.server_address,
+            ..Default::default()
+        });
+
+        // Guilds without settings are left alone
+        repo.clear_guild_setting(8, GuildSettingKey::Locale).await.unwrap();
+        assert!(repo.get_guild_settings(8).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_set_server_address() {
+        let (temp_dir, _repo) = setup_test_db().await;
+        let repo = GuildRepository::new(temp_dir.path().join("test.db").to_str().unwrap().to_string());
+
+        // Setting the address of an unknown guild creates its settings
+        repo.set_server_address(7, Some("mc.example.com:25565".to_string())).await.unwrap();
+        let retrieved = repo.get_guild_settings(7).await.unwrap().unwrap();
+        assert_eq!(retrieved.server_address.as_deref(), Some("mc.example.com:25565"));
+
+        // Clearing it keeps the other settings
+        repo.upsert_guild_settings(GuildSettings {
+            guild_id: 7,
+            locale: Some("fr".to_string()),
+            ..Default::default()
+        }).await.unwrap();
+        repo.set_server_address(7, None).await.unwrap();
+
+        let retrieved = repo.get_guild_settings(7).await.unwrap().unwrap();
+        assert_eq!(retrieved.server_address, None);
+        assert_eq!(retrieved.locale.as_deref(), Some("fr"));
+    }
+
+    #[tokio::test]
+    async fn test_find_and_delete_orphans() {
+        let (temp_dir, repo) = setup_test_db().await;
+        let db_path = temp_dir.path().join("test.db");
+
+        repo.upsert_player(MinecraftPlayer {
+            uuid: "present".to_string(),
+            username: "Present".to_string(),
+        }).await.unwrap();
+
+        {
+            let conn = Connection::open(&db_path).unwrap();
+            conn.execute("PRAGMA foreign_keys = OFF", []).unwrap();
+            conn.execute(
+                "INSERT INTO player_stats (mc_uuid, stat_name, stat_value, timestamp) VALUES
+                 ('present', 'deaths', 1, 0),
+                 ('ghost', 'deaths', 2, 0),
+                 ('ghost', 'kills', 3, 0)",
+                [],
+            ).unwrap();
+        }
+
+        let orphans = repo.find_orphans().await.unwrap();
+        assert_eq!(orphans, vec![OrphanRow {
+            table: "player_stats",
+            mc_uuid: "ghost".to_string(),
+            count: 2,
+        }]);
+
+        assert_eq!(repo.delete_orphans().await.unwrap(), 2);
+        assert!(repo.find_orphans().await.unwrap().is_empty());
+
+        // Stats for existing players are untouched
+        let conn = Connection::open(&db_path).unwrap();
+        let remaining: i64 = conn
+            .query_row("SELECT COUNT(*) FROM player_stats", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(remaining, 1);
+    }
+
+    #[tokio::test]
+    async fn test_status_history_range() {
+        let (temp_dir, _repo) = setup_test_db().await;
+        let repo = StatusHistoryRepository::new(temp_dir.path().join("test.db").to_str().unwrap().to_string());
+
+        for (timestamp, online) in [(300, 5), (100, 1), (200, 3), (400, 7)] {
+            repo.record_status("mc.example.com", StatusSample { timestamp, online, max: 20 }).await.unwrap();
+        }
+
+        let history = repo.get_status_history("mc.example.com", 100, 300).await.unwrap();
+        let timestamps: Vec<i64> = history.iter().map(|sample| sample.timestamp).collect();
+        assert_eq!(timestamps, vec![100, 200, 300]);
+        assert_eq!(history[1], StatusSample { timestamp: 200, online: 3, max: 20 });
+
+        assert!(repo.get_status_history("mc.example.com", 500, 600).await.unwrap().is_empty());
+
+        let recent = repo.get_count_history("mc.example.com", 200).await.unwrap();
+        assert_eq!(recent.iter().map(|sample| sample.timestamp).collect::<Vec<_>>(), vec![200, 300, 400]);
+    }
+
+    #[tokio::test]
+    async fn test_status_history_keyed_by_server() {
+        let (temp_dir, _repo) = setup_test_db().await;
+        let repo = StatusHistoryRepository::new(temp_dir.path().join("test.db").to_str().unwrap().to_string());
+
+        repo.record_status("survival.example.com", StatusSample { timestamp: 100, online: 5, max: 20 }).await.unwrap();
+        repo.record_status("creative.example.com", StatusSample { timestamp: 100, online: 2, max: 10 }).await.unwrap();
+
+        let survival = repo.get_count_history("survival.example.com", 0).await.unwrap();
+        assert_eq!(survival, vec![StatusSample { timestamp: 100, online: 5, max: 20 }]);
+        let creative = repo.get_count_history("creative.example.com", 0).await.unwrap();
+        assert_eq!(creative, vec![StatusSample { timestamp: 100, online: 2, max: 10 }]);
+        assert!(repo.get_count_history("other.example.com", 0).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_migration_moves_status_history_to_player_count_history() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db").to_str().unwrap().to_string();
+
+        // A database from before player counts were keyed by server
+        {
+            let conn = Connection::open(&db_path).unwrap();
+            conn.execute_batch(
+                "CREATE TABLE status_history (timestamp INTEGER NOT NULL, online INTEGER NOT NULL, max INTEGER NOT NULL);
+                 INSERT INTO status_history VALUES (100, 3, 20), (200, 4, 20);",
+            ).unwrap();
+        }
+        init_db(&db_path).await.unwrap();
+
+        let conn = Connection::open(&db_path).unwrap();
+        let legacy: i64 = conn
+            .query_row("SELECT COUNT(*) FROM sqlite_master WHERE name = 'status_history'", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(legacy, 0);
+
+        let repo = StatusHistoryRepository::new(db_path);
+        assert!(repo.get_count_history("mc.example.com", 0).await.unwrap().is_empty());
+        assert_eq!(repo.claim_unkeyed_samples("mc.example.com").await.unwrap(), 2);
+        assert_eq!(repo.get_count_history("mc.example.com", 0).await.unwrap().len(), 2);
+        assert_eq!(repo.claim_unkeyed_samples("mc.example.com").await.unwrap(), 0);
+    }
 }
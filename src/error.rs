@@ -4,6 +4,7 @@
 //! for different parts of the application.
 
 use std::fmt;
+use std::time::Duration;
 
 /// Main error type for OxideVault operations.
 #[derive(Debug)]
@@ -14,6 +15,8 @@ pub enum OxideVaultError {
     Database(String),
     /// Minecraft server protocol errors
     ServerProtocol(String),
+    /// A server hostname could not be resolved
+    DnsResolution(String),
     /// Mojang API errors
     MojangApi(String),
     /// Network/HTTP errors
@@ -22,8 +25,48 @@ pub enum OxideVaultError {
     Discord(String),
     /// Validation errors (invalid usernames, etc.)
     Validation(String),
+    /// Proxy connection or negotiation errors
+    Proxy(String),
+    /// Proxy authentication errors (missing or rejected credentials)
+    ProxyAuth(String),
+    /// An API rejected the request with HTTP 429, optionally saying when to retry
+    RateLimited { retry_after: Option<Duration> },
     /// Generic I/O errors
     Io(std::io::Error),
+    /// SQLite errors, kept as the error's source
+    Sqlite(rusqlite::Error),
+    /// Connection pool errors, kept as the error's source
+    Pool(r2d2::Error),
+    /// HTTP client errors, kept as the error's source
+    Http(reqwest::Error),
+    /// JSON parsing errors, kept as the error's source
+    Json(serde_json::Error),
+    /// Missing or invalid environment variables, kept as the error's source
+    Env(std::env::VarError),
+    /// Background task failures, kept as the error's source
+    Join(tokio::task::JoinError),
+}
+
+impl OxideVaultError {
+    /// Process exit code associated with this error kind.
+    ///
+    /// Each variant maps to a distinct code so scripts can tell failures apart.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::Config(_) | Self::Env(_) => 2,
+            Self::Database(_) | Self::Sqlite(_) | Self::Pool(_) => 3,
+            Self::ServerProtocol(_) | Self::Json(_) => 4,
+            Self::MojangApi(_) => 5,
+            Self::Network(_) | Self::Http(_) => 6,
+            Self::Discord(_) | Self::Join(_) => 7,
+            Self::Validation(_) => 8,
+            Self::Io(_) => 9,
+            Self::Proxy(_) => 10,
+            Self::ProxyAuth(_) => 11,
+            Self::RateLimited { .. } => 12,
+            Self::DnsResolution(_) => 13,
+        }
+    }
 }
 
 impl fmt::Display for OxideVaultError {
@@ -32,11 +75,24 @@ impl fmt::Display for OxideVaultError {
             Self::Config(msg) => write!(f, "Configuration error: {}", msg),
             Self::Database(msg) => write!(f, "Database error: {}", msg),
             Self::ServerProtocol(msg) => write!(f, "Server protocol error: {}", msg),
+            Self::DnsResolution(msg) => write!(f, "DNS resolution error: {}", msg),
             Self::MojangApi(msg) => write!(f, "Mojang API error: {}", msg),
             Self::Network(msg) => write!(f, "Network error: {}", msg),
             Self::Discord(msg) => write!(f, "Discord error: {}", msg),
             Self::Validation(msg) => write!(f, "Validation error: {}", msg),
+            Self::Proxy(msg) => write!(f, "Proxy error: {}", msg),
+            Self::ProxyAuth(msg) => write!(f, "Proxy authentication error: {}", msg),
+            Self::RateLimited { retry_after: Some(delay) } => {
+                write!(f, "Rate limited, retry after {}s", delay.as_secs())
+            }
+            Self::RateLimited { retry_after: None } => write!(f, "Rate limited"),
             Self::Io(err) => write!(f, "I/O error: {}", err),
+            Self::Sqlite(err) => write!(f, "Database error: {}", err),
+            Self::Pool(err) => write!(f, "Database error: Failed to get a pooled connection: {}", err),
+            Self::Http(err) => write!(f, "Network error: {}", err),
+            Self::Json(err) => write!(f, "Server protocol error: JSON parsing error: {}", err),
+            Self::Env(err) => write!(f, "Configuration error: {}", err),
+            Self::Join(err) => write!(f, "Discord error: Task join error: {}", err),
         }
     }
 }
@@ -45,6 +101,12 @@ impl std::error::Error for OxideVaultError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
             Self::Io(err) => Some(err),
+            Self::Sqlite(err) => Some(err),
+            Self::Pool(err) => Some(err),
+            Self::Http(err) => Some(err),
+            Self::Json(err) => Some(err),
+            Self::Env(err) => Some(err),
+            Self::Join(err) => Some(err),
             _ => None,
         }
     }
@@ -59,33 +121,65 @@ impl From<std::io::Error> for OxideVaultError {
 
 impl From<rusqlite::Error> for OxideVaultError {
     fn from(err: rusqlite::Error) -> Self {
-        Self::Database(err.to_string())
+        Self::Sqlite(err)
+    }
+}
+
+impl From<r2d2::Error> for OxideVaultError {
+    fn from(err: r2d2::Error) -> Self {
+        Self::Pool(err)
     }
 }
 
 impl From<reqwest::Error> for OxideVaultError {
     fn from(err: reqwest::Error) -> Self {
-        Self::Network(err.to_string())
+        Self::Http(err)
     }
 }
 
 impl From<serde_json::Error> for OxideVaultError {
     fn from(err: serde_json::Error) -> Self {
-        Self::ServerProtocol(format!("JSON parsing error: {}", err))
+        Self::Json(err)
     }
 }
 
 impl From<std::env::VarError> for OxideVaultError {
     fn from(err: std::env::VarError) -> Self {
-        Self::Config(err.to_string())
+        Self::Env(err)
     }
 }
 
 impl From<tokio::task::JoinError> for OxideVaultError {
     fn from(err: tokio::task::JoinError) -> Self {
-        Self::Discord(format!("Task join error: {}", err))
+        Self::Join(err)
     }
 }
 
 /// Result type alias for OxideVault operations.
 pub type Result<T> = std::result::Result<T, OxideVaultError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+
+    #[test]
+    fn test_wrapped_errors_keep_source() {
+        let json_err = serde_json::from_str::<serde_json::Value>("{").unwrap_err();
+        let message = json_err.to_string();
+        let err = OxideVaultError::from(json_err);
+        assert_eq!(err.to_string(), format!("Server protocol error: JSON parsing error: {}", message));
+        assert_eq!(err.source().unwrap().to_string(), message);
+        assert_eq!(err.exit_code(), OxideVaultError::ServerProtocol(String::new()).exit_code());
+
+        let err = OxideVaultError::from(rusqlite::Error::QueryReturnedNoRows);
+        assert_eq!(err.to_string(), "Database error: Query returned no rows");
+        assert!(err.source().unwrap().is::<rusqlite::Error>());
+
+        let err = OxideVaultError::from(std::env::VarError::NotPresent);
+        assert!(err.to_string().starts_with("Configuration error: "));
+        assert!(err.source().is_some());
+
+        assert!(OxideVaultError::Config("missing".to_string()).source().is_none());
+    }
+}
@@ -0,0 +1,218 @@
+//! Lightweight HTTP server support for operational endpoints.
+//!
+//! This module provides building blocks shared by the bot's HTTP endpoints,
+//! such as per-IP rate limiting and common responses, and the opt-in server
+//! exposing them:
+//!
+//! - `GET /health` answers `OK` while the bot is running
+//! - `GET /metrics` returns [`METRICS`] in the Prometheus text format
+
+pub mod rate_limit;
+
+use crate::metrics::{Metrics, METRICS};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+pub use rate_limit::RateLimiter;
+
+/// Default number of requests a client may burst before being rate limited.
+pub const RATE_LIMIT_BURST: u32 = 20;
+
+/// Default requests per second a client is allowed once its burst is used up.
+pub const RATE_LIMIT_PER_SEC: f64 = 2.0;
+
+/// Largest request head read from a client; the body, if any, is ignored.
+const MAX_REQUEST_HEAD: usize = 8 * 1024;
+
+/// Time a client has to send its request head.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Build a raw HTTP/1.1 response with a plain body.
+fn response(status: &str, content_type: &str, body: &str) -> String {
+    format!(
+        "HTTP/1.1 {}\r\n\
+        Content-Type: {}\r\n\
+        Content-Length: {}\r\n\
+        Connection: close\r\n\
+        \r\n\
+        {}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )
+}
+
+/// Build the response to a request line such as `GET /metrics HTTP/1.1`.
+pub fn route(request_line: &str, metrics: &Metrics) -> String {
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return response("400 Bad Request", "text/plain; charset=utf-8", "Bad Request\n");
+    };
+    let path = target.split('?').next().unwrap_or(target);
+
+    match (method, path) {
+        ("GET", "/health") => response("200 OK", "text/plain; charset=utf-8", "OK\n"),
+        ("GET", "/metrics") => response("200 OK", "text/plain; version=0.0.4; charset=utf-8", &metrics.render()),
+        (_, "/health" | "/metrics") => response("405 Method Not Allowed", "text/plain; charset=utf-8", "Method Not Allowed\n"),
+        _ => response("404 Not Found", "text/plain; charset=utf-8", "Not Found\n"),
+    }
+}
+
+/// Serve the operational endpoints on `listener` until `shutdown` becomes
+/// `true` (or its sender is dropped).
+///
+/// Each connection handles a single request and is then closed. Clients over
+/// the `limiter`'s rate get a `429 Too Many Requests` response.
+pub fn spawn_http_server(
+    listener: TcpListener,
+    limiter: RateLimiter,
+    mut shutdown: watch::Receiver<bool>,
+) -> JoinHandle<()> {
+    let limiter = std::sync::Arc::new(limiter);
+
+    tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                accepted = listener.accept() => {
+                    let (stream, peer) = match accepted {
+                        Ok(accepted) => accepted,
+                        Err(e) => {
+                            tracing::warn!(error = %e, "Failed to accept HTTP connection");
+                            continue;
+                        }
+                    };
+
+                    let limiter = limiter.clone();
+                    tokio::spawn(async move {
+                        let response = match limiter.check(peer.ip()) {
+                            Ok(()) => None,
+                            Err(retry_after) => Some(too_many_requests_response(retry_after)),
+                        };
+                        if let Err(e) = handle_connection(stream, response).await {
+                            tracing::debug!(peer = %peer, error = %e, "HTTP connection failed");
+                        }
+                    });
+                }
+                changed = shutdown.changed() => {
+                    if changed.is_err() || *shutdown.borrow() {
+                        break;
+                    }
+                }
+            }
+        }
+    })
+}
+
+/// Read one request from `stream` and write its response.
+///
+/// A precomputed `response` (e.g. a rate limit rejection) is sent without
+/// reading the request.
+async fn handle_connection(mut stream: TcpStream, response: Option<String>) -> std::io::Result<()> {
+    let response = match response {
+        Some(response) => response,
+        None => {
+            let head = tokio::time::timeout(REQUEST_TIMEOUT, read_request_head(&mut stream))
+                .await
+                .map_err(|_| std::io::Error::new(std::io::ErrorKind::TimedOut, "Request timed out"))??;
+            let request_line = head.lines().next().unwrap_or_default();
+            route(request_line, &METRICS)
+        }
+    };
+
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+/// Read the request head (request line and headers), up to [`MAX_REQUEST_HEAD`] bytes.
+async fn read_request_head(stream: &mut TcpStream) -> std::io::Result<String> {
+    let mut head = Vec::new();
+    let mut buffer = [0u8; 1024];
+
+    while !head.windows(4).any(|window| window == b"\r\n\r\n") && head.len() < MAX_REQUEST_HEAD {
+        let read = stream.read(&mut buffer).await?;
+        if read == 0 {
+            break;
+        }
+        head.extend_from_slice(&buffer[..read]);
+    }
+
+    Ok(String::from_utf8_lossy(&head).into_owned())
+}
+
+/// Build a raw HTTP/1.1 `429 Too Many Requests` response.
+///
+/// The `Retry-After` header is expressed in whole seconds, rounded up so clients
+/// never retry before a token is available.
+pub fn too_many_requests_response(retry_after: Duration) -> String {
+    let seconds = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+    let body = "Too Many Requests\n";
+    format!(
+        "HTTP/1.1 429 Too Many Requests\r\n\
+        Retry-After: {}\r\n\
+        Content-Type: text/plain; charset=utf-8\r\n\
+        Content-Length: {}\r\n\
+        Connection: close\r\n\
+        \r\n\
+        {}",
+        seconds.max(1),
+        body.len(),
+        body
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route() {
+        let metrics = Metrics::new();
+        metrics.record_command("online");
+
+        let response = route("GET /metrics HTTP/1.1", &metrics);
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("\r\nContent-Type: text/plain; version=0.0.4; charset=utf-8\r\n"));
+        assert!(response.ends_with(&metrics.render()));
+
+        assert!(route("GET /metrics?name[]=x HTTP/1.1", &metrics).starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(route("GET /health HTTP/1.1", &metrics).ends_with("\r\n\r\nOK\n"));
+        assert!(route("POST /metrics HTTP/1.1", &metrics).starts_with("HTTP/1.1 405 Method Not Allowed\r\n"));
+        assert!(route("GET / HTTP/1.1", &metrics).starts_with("HTTP/1.1 404 Not Found\r\n"));
+        assert!(route("", &metrics).starts_with("HTTP/1.1 400 Bad Request\r\n"));
+    }
+
+    #[tokio::test]
+    async fn test_http_server_serves_metrics() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let server = spawn_http_server(listener, RateLimiter::new(RATE_LIMIT_BURST, RATE_LIMIT_PER_SEC), shutdown_rx);
+
+        let mut stream = TcpStream::connect(address).await.unwrap();
+        stream.write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n").await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("# TYPE mc_ping_failures_total counter"));
+
+        shutdown_tx.send(true).unwrap();
+        server.await.unwrap();
+    }
+
+    #[test]
+    fn test_too_many_requests_response() {
+        let response = too_many_requests_response(Duration::from_millis(1500));
+        assert!(response.starts_with("HTTP/1.1 429 Too Many Requests\r\n"));
+        assert!(response.contains("\r\nRetry-After: 2\r\n"));
+        assert!(response.contains("\r\nContent-Length: 18\r\n"));
+        assert!(response.ends_with("\r\n\r\nToo Many Requests\n"));
+
+        // Never advertise a zero-second wait
+        let response = too_many_requests_response(Duration::ZERO);
+        assert!(response.contains("\r\nRetry-After: 1\r\n"));
+    }
+}
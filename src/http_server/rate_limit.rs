@@ -0,0 +1,157 @@
+//! Per-IP token bucket rate limiting for the HTTP server.
+//!
+//! Each client IP gets a bucket of tokens that refills at a steady rate. A request
+//! consumes one token; when the bucket is empty the request is rejected with the
+//! time until the next token becomes available.
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Number of tracked clients above which idle buckets are pruned.
+const PRUNE_THRESHOLD: usize = 10_000;
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// Token bucket rate limiter keyed by client IP address.
+#[derive(Debug)]
+pub struct RateLimiter {
+    capacity: f64,
+    refill_per_sec: f64,
+    exempt_loopback: bool,
+    buckets: Mutex<HashMap<IpAddr, Bucket>>,
+}
+
+impl RateLimiter {
+    /// Create a rate limiter.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Maximum burst of requests allowed from one IP
+    /// * `refill_per_sec` - Tokens added back per second
+    ///
+    /// Loopback addresses are exempt by default; see [`RateLimiter::exempt_loopback`].
+    pub fn new(capacity: u32, refill_per_sec: f64) -> Self {
+        Self {
+            capacity: f64::from(capacity.max(1)),
+            refill_per_sec: refill_per_sec.max(f64::MIN_POSITIVE),
+            exempt_loopback: true,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Set whether loopback clients bypass the limiter.
+    pub fn exempt_loopback(mut self, exempt: bool) -> Self {
+        self.exempt_loopback = exempt;
+        self
+    }
+
+    /// Check whether a request from `ip` is allowed right now.
+    ///
+    /// Returns `Err(retry_after)` if the client must wait before retrying.
+    pub fn check(&self, ip: IpAddr) -> Result<(), Duration> {
+        self.check_at(ip, Instant::now())
+    }
+
+    /// Check whether a request from `ip` is allowed at the given instant.
+    pub fn check_at(&self, ip: IpAddr, now: Instant) -> Result<(), Duration> {
+        if self.exempt_loopback && ip.is_loopback() {
+            return Ok(());
+        }
+
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+
+        if buckets.len() >= PRUNE_THRESHOLD {
+            buckets.retain(|_, bucket| self.refilled(bucket, now) < self.capacity);
+        }
+
+        let bucket = buckets.entry(ip).or_insert(Bucket {
+            tokens: self.capacity,
+            last_refill: now,
+        });
+
+        bucket.tokens = self.refilled(bucket, now);
+        bucket.last_refill = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            let missing = 1.0 - bucket.tokens;
+            Err(Duration::from_secs_f64(missing / self.refill_per_sec))
+        }
+    }
+
+    /// Token count of a bucket after refilling it up to `now`.
+    fn refilled(&self, bucket: &Bucket, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(bucket.last_refill).as_secs_f64();
+        (bucket.tokens + elapsed * self.refill_per_sec).min(self.capacity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    const CLIENT: IpAddr = IpAddr::V4(Ipv4Addr::new(203, 0, 113, 7));
+
+    #[test]
+    fn test_allows_burst_then_denies() {
+        let limiter = RateLimiter::new(3, 1.0);
+        let now = Instant::now();
+
+        assert!(limiter.check_at(CLIENT, now).is_ok());
+        assert!(limiter.check_at(CLIENT, now).is_ok());
+        assert!(limiter.check_at(CLIENT, now).is_ok());
+
+        let retry_after = limiter.check_at(CLIENT, now).unwrap_err();
+        assert!(retry_after > Duration::ZERO && retry_after <= Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_refills_over_time() {
+        let limiter = RateLimiter::new(1, 2.0);
+        let now = Instant::now();
+
+        assert!(limiter.check_at(CLIENT, now).is_ok());
+        assert!(limiter.check_at(CLIENT, now).is_err());
+
+        // Half a second at 2 tokens/sec refills exactly one token
+        assert!(limiter.check_at(CLIENT, now + Duration::from_millis(500)).is_ok());
+        assert!(limiter.check_at(CLIENT, now + Duration::from_millis(500)).is_err());
+    }
+
+    #[test]
+    fn test_clients_are_independent() {
+        let limiter = RateLimiter::new(1, 1.0);
+        let now = Instant::now();
+        let other = IpAddr::V4(Ipv4Addr::new(198, 51, 100, 1));
+
+        assert!(limiter.check_at(CLIENT, now).is_ok());
+        assert!(limiter.check_at(CLIENT, now).is_err());
+        assert!(limiter.check_at(other, now).is_ok());
+    }
+
+    #[test]
+    fn test_loopback_exemption() {
+        let now = Instant::now();
+        let loopback_v4 = IpAddr::V4(Ipv4Addr::LOCALHOST);
+        let loopback_v6 = IpAddr::V6(Ipv6Addr::LOCALHOST);
+
+        let limiter = RateLimiter::new(1, 1.0);
+        for _ in 0..10 {
+            assert!(limiter.check_at(loopback_v4, now).is_ok());
+            assert!(limiter.check_at(loopback_v6, now).is_ok());
+        }
+
+        let strict = RateLimiter::new(1, 1.0).exempt_loopback(false);
+        assert!(strict.check_at(loopback_v4, now).is_ok());
+        assert!(strict.check_at(loopback_v4, now).is_err());
+    }
+}
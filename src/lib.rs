@@ -8,6 +8,11 @@ pub mod config;
 pub mod database;
 pub mod mojang;
 pub mod mc_server;
+pub mod minecraft_stats;
+pub mod http_server;
+pub mod metrics;
+pub mod pl3xmap;
+pub mod secrets;
 pub mod utils;
 
 pub use error::{OxideVaultError, Result};
@@ -3,18 +3,42 @@
 //! OxideVault is a Discord bot for managing and monitoring Minecraft servers.
 //! It provides commands for checking server status, looking up player information,
 //! and more.
+//!
+//! Pass `--check` to validate the configuration, database, and server connectivity
+//! and exit without connecting to Discord.
+//!
+//! Diagnostics are written to stderr; set `RUST_LOG` (e.g. `RUST_LOG=oxidevault=debug`)
+//! to change the log level, which defaults to `info`.
 
-mod error;
-mod config;
 mod types;
-mod mojang;
-mod database;
 mod commands;
+mod checks;
+mod confirm;
 mod bot;
-mod mc_server;
-mod utils;
+mod self_check;
+mod shutdown;
+mod reload;
+mod status_poller;
+
+use oxidevault::{
+    config, database, error, http_server, mc_server, metrics, minecraft_stats, mojang, pl3xmap, utils,
+};
+
+/// Log filter used when `RUST_LOG` is unset or invalid.
+const DEFAULT_LOG_FILTER: &str = "info";
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let filter = tracing_subscriber::EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new(DEFAULT_LOG_FILTER));
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .init();
+
+    if std::env::args().skip(1).any(|arg| arg == "--check") {
+        std::process::exit(self_check::run().await);
+    }
+
     bot::run().await
 }
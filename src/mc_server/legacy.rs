@@ -0,0 +1,187 @@
+//! Legacy (pre-1.7) server list ping.
+//!
+//! Servers older than 1.7 don't understand the modern handshake. They answer a
+//! `0xFE 0x01` request with a kick packet whose reason is a UTF-16 string
+//! carrying the status fields.
+
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::time::Duration;
+use crate::error::{OxideVaultError, Result};
+use super::{connect, Description, PlayersInfo, ServerStatus, VersionInfo, DEFAULT_PING_TIMEOUT};
+
+/// Legacy server list ping request (packet 0xFE with payload 0x01).
+const LEGACY_PING_REQUEST: [u8; 2] = [0xFE, 0x01];
+/// Packet ID of the kick packet carrying the response.
+const LEGACY_KICK_PACKET: u8 = 0xFF;
+/// Prefix of the 1.4-1.6 response format.
+const MODERN_LEGACY_PREFIX: &str = "§1\0";
+
+/// Ping a pre-1.7 Minecraft server with the legacy protocol.
+///
+/// The result is mapped into a [`ServerStatus`]; legacy servers don't report
+/// a player sample or latency.
+///
+/// # Arguments
+///
+/// * `address` - Server address in "host:port" format
+///
+/// # Errors
+///
+/// Returns an error if the connection fails or the response isn't a legacy
+/// status response.
+pub fn ping_server_legacy(address: &str) -> Result<ServerStatus> {
+    let (mut stream, _, _) = connect(address, None, DEFAULT_PING_TIMEOUT)?;
+    legacy_status(&mut stream)
+}
+
+/// Ping an already resolved address with the legacy protocol.
+pub(super) fn ping_legacy_at(addr: SocketAddr, timeout: Duration) -> Result<ServerStatus> {
+    let mut stream = TcpStream::connect_timeout(&addr, timeout)
+        .map_err(|e| OxideVaultError::ServerProtocol(format!("Connection failed: {}", e)))?;
+    stream.set_read_timeout(Some(timeout))?;
+    stream.set_write_timeout(Some(timeout))?;
+    legacy_status(&mut stream)
+}
+
+/// Send the legacy ping over `stream` and parse the response.
+pub(super) fn legacy_status<S: Read + Write>(stream: &mut S) -> Result<ServerStatus> {
+    stream.write_all(&LEGACY_PING_REQUEST)?;
+    stream.flush()?;
+
+    let mut header = [0u8; 3];
+    stream.read_exact(&mut header)?;
+    if header[0] != LEGACY_KICK_PACKET {
+        return Err(OxideVaultError::ServerProtocol("Invalid legacy ping response".to_string()));
+    }
+
+    // Length is the number of UTF-16 code units
+    let len = u16::from_be_bytes([header[1], header[2]]) as usize;
+    let mut payload = vec![0u8; len * 2];
+    stream.read_exact(&mut payload)?;
+
+    let units: Vec<u16> = payload
+        .chunks_exact(2)
+        .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+        .collect();
+    let text = String::from_utf16(&units)
+        .map_err(|_| OxideVaultError::ServerProtocol("Invalid UTF-16 in legacy ping response".to_string()))?;
+
+    parse_legacy_response(&text)
+}
+
+/// Parse the text of a legacy ping response.
+///
+/// Supports the 1.4-1.6 format (`§1\0protocol\0version\0motd\0online\0max`)
+/// and the older beta format (`motd§online§max`).
+fn parse_legacy_response(text: &str) -> Result<ServerStatus> {
+    let invalid = || OxideVaultError::ServerProtocol("Invalid legacy ping response".to_string());
+
+    let (protocol, version, motd, online, max) = match text.strip_prefix(MODERN_LEGACY_PREFIX) {
+        Some(fields) => {
+            let fields: Vec<&str> = fields.split('\0').collect();
+            let [protocol, version, motd, online, max] = fields[..] else {
+                return Err(invalid());
+            };
+            (protocol.parse().map_err(|_| invalid())?, version.to_string(), motd, online, max)
+        }
+        None => {
+            // The MOTD may itself contain '§', so split from the right
+            let mut fields = text.rsplitn(3, '§');
+            let (max, online, motd) = match (fields.next(), fields.next(), fields.next()) {
+                (Some(max), Some(online), Some(motd)) => (max, online, motd),
+                _ => return Err(invalid()),
+            };
+            (0, "Beta 1.8 - 1.3".to_string(), motd, online, max)
+        }
+    };
+
+    Ok(ServerStatus {
+        version: VersionInfo { name: version, protocol },
+        players: PlayersInfo {
+            max: max.parse().map_err(|_| invalid())?,
+            online: online.parse().map_err(|_| invalid())?,
+            sample: Vec::new(),
+            omitted: 0,
+        },
+        description: Description::String(motd.to_string()),
+        favicon: None,
+        latency_ms: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// In-memory stream replaying a canned response and recording writes.
+    struct MockStream {
+        response: Cursor<Vec<u8>>,
+        written: Vec<u8>,
+    }
+
+    impl Read for MockStream {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.response.read(buf)
+        }
+    }
+
+    impl Write for MockStream {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.written.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Encode `text` as a legacy kick packet.
+    fn kick_packet(text: &str) -> Vec<u8> {
+        let units: Vec<u16> = text.encode_utf16().collect();
+        let mut packet = vec![LEGACY_KICK_PACKET];
+        packet.extend_from_slice(&(units.len() as u16).to_be_bytes());
+        for unit in units {
+            packet.extend_from_slice(&unit.to_be_bytes());
+        }
+        packet
+    }
+
+    #[test]
+    fn test_legacy_status_1_5_format() {
+        let mut stream = MockStream {
+            response: Cursor::new(kick_packet("§1\u{0}61\u{0}1.5.2\u{0}§6Old §rServer\u{0}3\u{0}20")),
+            written: Vec::new(),
+        };
+
+        let status = legacy_status(&mut stream).unwrap();
+        assert_eq!(stream.written, LEGACY_PING_REQUEST);
+        assert_eq!(status.version.protocol, 61);
+        assert_eq!(status.version.name, "1.5.2");
+        assert_eq!(status.description.text(), "§6Old §rServer");
+        assert_eq!((status.players.online, status.players.max), (3, 20));
+        assert!(status.players.sample.is_empty());
+    }
+
+    #[test]
+    fn test_parse_legacy_beta_format() {
+        let status = parse_legacy_response("A §cbeta§r server§0§10").unwrap();
+        assert_eq!(status.description.text(), "A §cbeta§r server");
+        assert_eq!((status.players.online, status.players.max), (0, 10));
+    }
+
+    #[test]
+    fn test_parse_legacy_invalid() {
+        assert!(parse_legacy_response("").is_err());
+        assert!(parse_legacy_response("§1\u{0}61\u{0}1.5.2").is_err());
+        assert!(parse_legacy_response("motd§many§20").is_err());
+
+        let mut stream = MockStream {
+            response: Cursor::new(vec![0x00, 0x00, 0x00]),
+            written: Vec::new(),
+        };
+        assert!(legacy_status(&mut stream).is_err());
+    }
+}
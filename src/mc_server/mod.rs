@@ -3,66 +3,470 @@
 //! This module provides high-level functions for querying Minecraft servers,
 //! including status checks and player information retrieval.
 
-mod protocol;
+mod legacy;
+mod ping_log;
+pub mod protocol;
+pub mod query;
+mod resolve;
+mod socks5;
 
-use protocol::{send_packet, read_packet, write_varint, write_string, read_string};
+pub use legacy::ping_server_legacy;
+pub use ping_log::append_ping_log;
+pub use query::{query_server, QueryResponse};
+pub use resolve::{resolve_server, AddressResolver, DnsResolver, ResolvedServer, SrvTarget};
+pub use socks5::Socks5Proxy;
+
+use protocol::{send_packet, read_packet, write_varint, write_string, read_string, read_varint_from_slice};
+use protocol::{send_packet_async, read_packet_async};
+use base64::Engine;
+use std::future::Future;
 use std::io::Write;
-use std::net::{TcpStream, ToSocketAddrs};
-use std::time::Duration;
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use serde::{Deserialize, Serialize};
 use crate::error::{OxideVaultError, Result};
+use crate::metrics::METRICS;
+
+/// Default timeout for connecting to a server and for each read and write.
+pub const DEFAULT_PING_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Default number of servers pinged at the same time by [`ping_servers`].
+pub const DEFAULT_PING_CONCURRENCY: usize = 8;
+
+/// Default number of retries after a transient ping failure.
+pub const DEFAULT_PING_RETRIES: u32 = 2;
+
+/// Default delay before the first retry; it doubles for each further retry.
+pub const DEFAULT_RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// Default maximum number of player sample entries kept from a status response.
+pub const DEFAULT_MAX_PLAYER_SAMPLE: usize = 100;
+
+/// Packet ID of the status response in the status protocol state.
+const STATUS_RESPONSE_PACKET_ID: i32 = 0x00;
 
 /// Server status information returned by a Minecraft server.
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ServerStatus {
     pub version: VersionInfo,
     pub players: PlayersInfo,
     pub description: Description,
+    /// Server icon as a `data:image/png;base64,...` URI, if the server has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub favicon: Option<String>,
+    /// Round-trip time of a ping/pong exchange, if the server answered the ping
+    #[serde(skip)]
+    pub latency_ms: Option<u64>,
+}
+
+/// Prefix of the data URI a server's favicon is sent as.
+const FAVICON_PREFIX: &str = "data:image/png;base64,";
+
+impl ServerStatus {
+    /// Decode the favicon into PNG bytes.
+    ///
+    /// Returns `None` if the server sent no favicon or it isn't a base64 PNG
+    /// data URI.
+    pub fn favicon_png(&self) -> Option<Vec<u8>> {
+        let encoded = self.favicon.as_deref()?.strip_prefix(FAVICON_PREFIX)?;
+        // Some servers wrap the base64 data across lines
+        let encoded: String = encoded.chars().filter(|c| !c.is_ascii_whitespace()).collect();
+        base64::engine::general_purpose::STANDARD.decode(encoded).ok()
+    }
+
+    /// Start building a `ServerStatus` with sensible defaults.
+    ///
+    /// Mainly useful for fabricating statuses in tests without going through JSON.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use oxidevault::mc_server::ServerStatus;
+    ///
+    /// let status = ServerStatus::builder()
+    ///     .players(1, 20)
+    ///     .player("Steve", "8667ba71-b85a-4004-af54-457a9734eed7")
+    ///     .description("Welcome!")
+    ///     .build();
+    ///
+    /// assert_eq!(status.players.online, 1);
+    /// assert_eq!(status.description.text(), "Welcome!");
+    /// ```
+    pub fn builder() -> ServerStatusBuilder {
+        ServerStatusBuilder::default()
+    }
+}
+
+/// Fluent builder for [`ServerStatus`].
+#[derive(Debug, Clone)]
+pub struct ServerStatusBuilder {
+    status: ServerStatus,
+}
+
+impl Default for ServerStatusBuilder {
+    fn default() -> Self {
+        Self {
+            status: ServerStatus {
+                version: VersionInfo {
+                    name: "1.21".to_string(),
+                    protocol: 767,
+                },
+                players: PlayersInfo {
+                    max: 20,
+                    online: 0,
+                    sample: Vec::new(),
+                    omitted: 0,
+                },
+                description: Description::String("A Minecraft Server".to_string()),
+                favicon: None,
+                latency_ms: None,
+            },
+        }
+    }
+}
+
+impl ServerStatusBuilder {
+    /// Set the version name and protocol number.
+    pub fn version(mut self, name: &str, protocol: i32) -> Self {
+        self.status.version = VersionInfo {
+            name: name.to_string(),
+            protocol,
+        };
+        self
+    }
+
+    /// Set the online and maximum player counts.
+    pub fn players(mut self, online: u16, max: u16) -> Self {
+        self.status.players.online = online;
+        self.status.players.max = max;
+        self
+    }
+
+    /// Replace the player sample.
+    pub fn sample(mut self, sample: Vec<PlayerSample>) -> Self {
+        self.status.players.sample = sample;
+        self
+    }
+
+    /// Append a single player to the sample.
+    pub fn player(mut self, name: &str, id: &str) -> Self {
+        self.status.players.sample.push(PlayerSample {
+            name: name.to_string(),
+            id: id.to_string(),
+        });
+        self
+    }
+
+    /// Set the description (MOTD).
+    pub fn description(mut self, text: &str) -> Self {
+        self.status.description = Description::String(text.to_string());
+        self
+    }
+
+    /// Set the favicon data URI.
+    pub fn favicon(mut self, favicon: &str) -> Self {
+        self.status.favicon = Some(favicon.to_string());
+        self
+    }
+
+    /// Set the measured ping latency in milliseconds.
+    pub fn latency(mut self, latency_ms: u64) -> Self {
+        self.status.latency_ms = Some(latency_ms);
+        self
+    }
+
+    /// Build the status.
+    pub fn build(self) -> ServerStatus {
+        self.status
+    }
 }
 
 /// Version information for the Minecraft server.
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct VersionInfo {
     pub name: String,
-    pub protocol: u16,
+    /// Protocol version number (may be negative, e.g. `-1` while a server is starting)
+    pub protocol: i32,
 }
 
 /// Player count and list information.
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct PlayersInfo {
     pub max: u16,
     pub online: u16,
     #[serde(default)]
     pub sample: Vec<PlayerSample>,
+    /// Number of sample entries dropped by [`PlayersInfo::cap_sample`]
+    #[serde(skip)]
+    pub omitted: usize,
+}
+
+impl PlayersInfo {
+    /// Limit the player sample to at most `max` entries.
+    ///
+    /// Extra entries are dropped and the sample's memory is released; the number
+    /// of dropped entries is added to `omitted` so it can still be reported.
+    pub fn cap_sample(&mut self, max: usize) {
+        if self.sample.len() > max {
+            self.omitted += self.sample.len() - max;
+            self.sample.truncate(max);
+            self.sample.shrink_to_fit();
+        }
+    }
 }
 
 /// Individual player information in the server list.
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct PlayerSample {
     pub name: String,
     pub id: String,
 }
 
+/// Placeholder names used by servers that hide their player list (lowercase).
+const ANONYMOUS_PLAYER_NAMES: &[&str] = &["anonymous player", "anonymous"];
+
+impl PlayerSample {
+    /// Whether this entry is a placeholder rather than a real player.
+    ///
+    /// Servers that hide their player list fill the sample with fake entries,
+    /// such as "Anonymous Player" with an all-zero UUID, or formatted text
+    /// lines starting with `§`.
+    pub fn is_anonymous(&self) -> bool {
+        let zero_uuid = !self.id.is_empty() && self.id.chars().all(|c| c == '0' || c == '-');
+        let placeholder_name = ANONYMOUS_PLAYER_NAMES.contains(&self.name.to_lowercase().as_str());
+
+        zero_uuid || placeholder_name || self.name.starts_with('§')
+    }
+}
+
 /// Server description/MOTD.
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(untagged)]
 pub enum Description {
     String(String),
-    Object { text: String },
+    /// Chat component, whose `extra` children are rendered after its own text
+    Object {
+        #[serde(default)]
+        text: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        extra: Vec<Description>,
+    },
 }
 
 impl Description {
-    /// Get the text content of the description.
+    /// Get the top-level text content of the description.
+    ///
+    /// Nested `extra` components are not included; see [`Description::to_plain`].
     pub fn text(&self) -> &str {
         match self {
             Description::String(s) => s,
-            Description::Object { text } => text,
+            Description::Object { text, .. } => text,
+        }
+    }
+
+    /// Flatten the description into plain text.
+    ///
+    /// `extra` components are concatenated recursively and `§` formatting
+    /// codes are stripped.
+    pub fn to_plain(&self) -> String {
+        let mut flattened = String::new();
+        self.flatten_into(&mut flattened);
+        strip_formatting_codes(&flattened)
+    }
+
+    fn flatten_into(&self, out: &mut String) {
+        match self {
+            Description::String(s) => out.push_str(s),
+            Description::Object { text, extra } => {
+                out.push_str(text);
+                for component in extra {
+                    component.flatten_into(out);
+                }
+            }
+        }
+    }
+}
+
+/// Remove legacy `§` formatting codes (the `§` and the character after it).
+fn strip_formatting_codes(text: &str) -> String {
+    let mut plain = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '§' {
+            chars.next();
+        } else {
+            plain.push(c);
+        }
+    }
+    plain
+}
+
+/// A source of Minecraft server status information.
+///
+/// Abstracts over how the status is obtained so that logic built on top of it
+/// can be exercised without a live server.
+pub trait StatusSource {
+    /// Fetch the current server status.
+    fn fetch_status(&self) -> impl Future<Output = Result<ServerStatus>> + Send;
+
+    /// Fetch the names of every online player, for when the status only
+    /// carries part of the list.
+    ///
+    /// Returns `None` when no full list is available, which is the default.
+    fn fetch_player_names(&self) -> impl Future<Output = Option<Vec<String>>> + Send {
+        std::future::ready(None)
+    }
+}
+
+/// Status source that pings a Minecraft server at a fixed address.
+#[derive(Debug, Clone)]
+pub struct PingStatusSource {
+    address: String,
+    proxy: Option<Socks5Proxy>,
+    query: bool,
+    max_sample: usize,
+    ping_log: Option<PathBuf>,
+    timeout: Duration,
+    retries: u32,
+    retry_base_delay: Duration,
+}
+
+impl PingStatusSource {
+    /// Create a status source for the given "host:port" address.
+    pub fn new(address: impl Into<String>) -> Self {
+        Self {
+            address: address.into(),
+            proxy: None,
+            query: false,
+            max_sample: DEFAULT_MAX_PLAYER_SAMPLE,
+            ping_log: None,
+            timeout: DEFAULT_PING_TIMEOUT,
+            retries: 0,
+            retry_base_delay: DEFAULT_RETRY_BASE_DELAY,
+        }
+    }
+
+    /// Point the source at another "host:port" address, keeping its other settings.
+    pub fn with_address(mut self, address: impl Into<String>) -> Self {
+        self.address = address.into();
+        self
+    }
+
+    /// Route pings through the given SOCKS5 proxy, if any.
+    pub fn with_proxy(mut self, proxy: Option<Socks5Proxy>) -> Self {
+        self.proxy = proxy;
+        self
+    }
+
+    /// Fetch full player lists with the UDP Query protocol, on the same
+    /// address as pings (the default `query.port`).
+    ///
+    /// Ignored with a SOCKS5 proxy, which only carries the TCP pings.
+    pub fn with_query(mut self, enabled: bool) -> Self {
+        self.query = enabled;
+        self
+    }
+
+    /// Keep at most `max_sample` player sample entries from each status.
+    pub fn with_sample_limit(mut self, max_sample: usize) -> Self {
+        self.max_sample = max_sample;
+        self
+    }
+
+    /// Append every ping result to the given JSON Lines file, if any.
+    pub fn with_ping_log(mut self, path: Option<PathBuf>) -> Self {
+        self.ping_log = path;
+        self
+    }
+
+    /// Set the connection and read/write timeout used for pings.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Retry transient failures up to `retries` times with exponential backoff.
+    pub fn with_retries(mut self, retries: u32, base_delay: Duration) -> Self {
+        self.retries = retries;
+        self.retry_base_delay = base_delay;
+        self
+    }
+
+    /// Ping the server once.
+    async fn ping_once(&self) -> Result<ServerStatus> {
+        match &self.proxy {
+            None => ping_server_async(&self.address, self.timeout).await,
+            // The SOCKS5 handshake is synchronous, so proxied pings run on a blocking thread
+            Some(proxy) => {
+                let address = self.address.clone();
+                let proxy = proxy.clone();
+                let timeout = self.timeout;
+                tokio::task::spawn_blocking(move || ping_server_via(&address, Some(&proxy), timeout))
+                    .await
+                    .map_err(|e| OxideVaultError::ServerProtocol(format!("Task join error: {}", e)))?
+            }
+        }
+    }
+}
+
+impl StatusSource for PingStatusSource {
+    async fn fetch_status(&self) -> Result<ServerStatus> {
+        let started = Instant::now();
+        let mut result = retry_with_backoff(self.retries, self.retry_base_delay, || self.ping_once()).await;
+        let latency = started.elapsed();
+
+        match &mut result {
+            Ok(status) => {
+                tracing::debug!(
+                    server = %self.address,
+                    latency_ms = latency.as_millis() as u64,
+                    online = status.players.online,
+                    "Server ping succeeded"
+                );
+                status.players.cap_sample(self.max_sample);
+                METRICS.set_players_online(&self.address, u64::from(status.players.online));
+            }
+            Err(e) => {
+                tracing::debug!(server = %self.address, error = %e, "Server ping failed");
+                METRICS.record_ping_failure();
+            }
+        }
+
+        // Logging is best-effort and must never fail the ping itself
+        if let Some(path) = &self.ping_log {
+            let timestamp = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs());
+            if let Err(e) = append_ping_log(path, &result, latency, timestamp) {
+                tracing::warn!(path = %path.display(), error = %e, "Failed to write ping log");
+            }
+        }
+
+        result
+    }
+
+    async fn fetch_player_names(&self) -> Option<Vec<String>> {
+        if !self.query || self.proxy.is_some() {
+            return None;
+        }
+
+        match query_server(&self.address, self.timeout).await {
+            Ok(response) => Some(response.players),
+            Err(e) => {
+                tracing::debug!(server = %self.address, error = %e, "Server query failed");
+                None
+            }
         }
     }
 }
 
 /// Ping a Minecraft server and retrieve its status.
 ///
+/// Uses [`DEFAULT_PING_TIMEOUT`] for the connection and each read and write.
+/// Servers that answer the modern handshake with malformed data are retried
+/// with [`ping_server_legacy`], so pre-1.7 servers work too.
+///
 /// # Arguments
 ///
 /// * `address` - Server address in "host:port" format (e.g., "localhost:25565")
@@ -90,49 +494,435 @@ impl Description {
 /// # }
 /// ```
 pub fn ping_server(address: &str) -> Result<ServerStatus> {
-    // Resolve address and connect with timeout
-    let mut addrs = address.to_socket_addrs()
-        .map_err(|e| OxideVaultError::ServerProtocol(format!("Failed to resolve address: {}", e)))?;
+    ping_server_via(address, None, DEFAULT_PING_TIMEOUT)
+}
+
+/// Ping a Minecraft server, optionally connecting through a SOCKS5 proxy.
+///
+/// When `proxy` is `None` this behaves like [`ping_server`] with a custom timeout.
+/// Otherwise the TCP connection is established through the proxy, which also
+/// resolves the hostname.
+///
+/// # Errors
+///
+/// In addition to the errors returned by [`ping_server`], returns `Proxy` or
+/// `ProxyAuth` errors if the proxy connection or authentication fails.
+pub fn ping_server_via(address: &str, proxy: Option<&Socks5Proxy>, timeout: Duration) -> Result<ServerStatus> {
+    let (mut stream, host_str, port) = connect(address, proxy, timeout)?;
+
+    // Send handshake, then the status request
+    send_packet(&mut stream, &build_handshake(&host_str, port)?)?;
+    send_packet(&mut stream, &build_status_request()?)?;
+
+    // Read response (an EOF here means the server accepted and then hung up)
+    let response = read_packet(&mut stream)
+        .map_err(map_status_read_error)
+        .and_then(|response| parse_status_response(&response));
+
+    let mut status = match response {
+        Ok(status) => status,
+        Err(e) if is_timeout(&e) => return Err(e),
+        // Pre-1.7 servers don't understand the modern handshake; report the
+        // original error if the legacy ping fails as well
+        Err(e) => {
+            return connect(address, proxy, timeout)
+                .and_then(|(mut stream, _, _)| legacy::legacy_status(&mut stream))
+                .map_err(|_| e);
+        }
+    };
+
+    // Measure latency with a ping/pong exchange (not every server answers)
+    let payload = ping_payload();
+    let started = Instant::now();
+    status.latency_ms = build_ping(payload)
+        .ok()
+        .and_then(|ping| send_packet(&mut stream, &ping).ok())
+        .and_then(|_| read_packet(&mut stream).ok())
+        .filter(|pong| is_pong(pong, payload))
+        .map(|_| started.elapsed().as_millis() as u64);
+
+    Ok(status)
+}
+
+/// Open a TCP connection to `address`, optionally through a SOCKS5 proxy.
+///
+/// Returns the stream with read and write timeouts applied, plus the host and
+/// port to announce in the handshake.
+fn connect(address: &str, proxy: Option<&Socks5Proxy>, timeout: Duration) -> Result<(TcpStream, String, u16)> {
+    let (stream, host_str, port) = match proxy {
+        Some(proxy) => {
+            let (host, port) = split_host_port(address)?;
+            (proxy.connect(&host, port, timeout)?, host, port)
+        }
+        None => {
+            let (host, _) = split_host_port(address)?;
+
+            // Resolve address and connect, each bounded by the timeout
+            let addr = resolve_blocking(address, timeout)?;
+
+            let stream = TcpStream::connect_timeout(&addr, timeout)
+                .map_err(|e| OxideVaultError::ServerProtocol(format!("Connection failed: {}", e)))?;
+
+            // Send the original hostname, which virtual-hosted servers route on
+            (stream, host, addr.port())
+        }
+    };
+
+    stream.set_read_timeout(Some(timeout))?;
+    stream.set_write_timeout(Some(timeout))?;
+
+    Ok((stream, host_str, port))
+}
+
+/// Resolve `address` to its first socket address, giving up after `timeout`.
+///
+/// The system lookup can't be cancelled, so it runs on its own thread which
+/// is left to finish in the background if it takes too long.
+fn resolve_blocking(address: &str, timeout: Duration) -> Result<SocketAddr> {
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let lookup = address.to_string();
+    std::thread::spawn(move || {
+        let _ = sender.send(lookup.to_socket_addrs().map(|mut addrs| addrs.next()));
+    });
+
+    match receiver.recv_timeout(timeout) {
+        Ok(Ok(Some(addr))) => Ok(addr),
+        Ok(Ok(None)) => Err(OxideVaultError::DnsResolution("Could not resolve address".to_string())),
+        Ok(Err(e)) => Err(OxideVaultError::DnsResolution(format!("Failed to resolve address: {}", e))),
+        Err(_) => Err(dns_timeout(address, timeout)),
+    }
+}
+
+/// Error for a DNS lookup that didn't finish within `timeout`.
+fn dns_timeout(address: &str, timeout: Duration) -> OxideVaultError {
+    OxideVaultError::DnsResolution(format!(
+        "Timed out resolving '{}' after {}s",
+        address,
+        timeout.as_secs_f32()
+    ))
+}
+
+/// Whether `e` is a read or write timeout on a blocking socket.
+fn is_timeout(e: &OxideVaultError) -> bool {
+    matches!(e, OxideVaultError::Io(io)
+        if matches!(io.kind(), std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut))
+}
+
+/// Ping a Minecraft server asynchronously and retrieve its status.
+///
+/// Non-blocking counterpart of [`ping_server`] built on `tokio::net::TcpStream`.
+/// `timeout` separately bounds DNS resolution, the connection attempt, and the
+/// status exchange.
+///
+/// Like Minecraft clients, a `_minecraft._tcp` SRV record for the host is
+/// honored when present; the handshake still carries the original hostname.
+///
+/// # Errors
+///
+/// Returns an error if the address can't be resolved, the connection fails or
+/// times out, or the server responds with invalid data.
+///
+/// # Examples
+///
+/// ```no_run
+/// use oxidevault::mc_server::{ping_server_async, DEFAULT_PING_TIMEOUT};
+///
+/// # async fn example() -> oxidevault::Result<()> {
+/// let status = ping_server_async("localhost:25565", DEFAULT_PING_TIMEOUT).await?;
+/// println!("Players: {}/{}", status.players.online, status.players.max);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn ping_server_async(address: &str, timeout: Duration) -> Result<ServerStatus> {
+    ping_server_async_with(&DnsResolver, address, timeout).await
+}
+
+/// Ping a Minecraft server, retrying transient failures with exponential backoff.
+///
+/// Connection failures and timeouts are retried up to `max_retries` times,
+/// waiting `base_delay` before the first retry and doubling it each time.
+/// Protocol and parse errors are returned immediately since retrying won't
+/// fix them.
+///
+/// # Errors
+///
+/// Returns the last error if every attempt fails.
+pub async fn ping_server_with_retry(address: &str, max_retries: u32, base_delay: Duration) -> Result<ServerStatus> {
+    retry_with_backoff(max_retries, base_delay, || ping_server_async(address, DEFAULT_PING_TIMEOUT)).await
+}
+
+/// Run `attempt`, retrying transient errors with exponential backoff.
+async fn retry_with_backoff<T, F, Fut>(max_retries: u32, base_delay: Duration, mut attempt: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut delay = base_delay;
+    let mut retries = 0;
+
+    loop {
+        match attempt().await {
+            Err(e) if retries < max_retries && is_transient(&e) => {
+                tokio::time::sleep(delay).await;
+                delay = delay.saturating_mul(2);
+                retries += 1;
+            }
+            result => return result,
+        }
+    }
+}
 
-    let addr = addrs.next()
-        .ok_or_else(|| OxideVaultError::ServerProtocol("Could not resolve address".to_string()))?;
+/// Whether a ping error is a connection problem or timeout worth retrying.
+fn is_transient(e: &OxideVaultError) -> bool {
+    use std::io::ErrorKind;
 
-    let mut stream = TcpStream::connect_timeout(&addr, Duration::from_secs(10))
+    match e {
+        OxideVaultError::Io(io) => matches!(io.kind(),
+            ErrorKind::ConnectionRefused
+            | ErrorKind::ConnectionReset
+            | ErrorKind::ConnectionAborted
+            | ErrorKind::NotConnected
+            | ErrorKind::BrokenPipe
+            | ErrorKind::TimedOut
+            | ErrorKind::WouldBlock),
+        OxideVaultError::ServerProtocol(msg) => msg.starts_with("Connection failed") || msg.starts_with("Timed out"),
+        OxideVaultError::Network(_) | OxideVaultError::Http(_) => true,
+        _ => false,
+    }
+}
+
+/// Ping several Minecraft servers concurrently.
+///
+/// Uses [`DEFAULT_PING_CONCURRENCY`] and [`DEFAULT_PING_TIMEOUT`]; see
+/// [`ping_servers_with`].
+pub async fn ping_servers(addresses: &[String]) -> Vec<(String, Result<ServerStatus>)> {
+    ping_servers_with(addresses, DEFAULT_PING_CONCURRENCY, DEFAULT_PING_TIMEOUT).await
+}
+
+/// Ping several Minecraft servers concurrently, at most `concurrency` at a time.
+///
+/// Every address gets its own result, in the same order as `addresses`, so one
+/// dead server doesn't fail the batch. Each ping is bounded by `timeout` as a
+/// whole, so a hanging server can't stall the rest.
+pub async fn ping_servers_with(
+    addresses: &[String],
+    concurrency: usize,
+    timeout: Duration,
+) -> Vec<(String, Result<ServerStatus>)> {
+    let permits = std::sync::Arc::new(tokio::sync::Semaphore::new(concurrency.max(1)));
+
+    let handles: Vec<_> = addresses
+        .iter()
+        .map(|address| {
+            let address = address.clone();
+            let permits = permits.clone();
+            tokio::spawn(async move {
+                let _permit = permits.acquire_owned().await;
+                tokio::time::timeout(timeout, ping_server_async(&address, timeout))
+                    .await
+                    .unwrap_or_else(|_| Err(OxideVaultError::ServerProtocol(
+                        "Timed out waiting for server status".to_string()
+                    )))
+            })
+        })
+        .collect();
+
+    let mut results = Vec::with_capacity(handles.len());
+    for (address, handle) in addresses.iter().zip(handles) {
+        let result = handle
+            .await
+            .unwrap_or_else(|e| Err(OxideVaultError::ServerProtocol(format!("Task join error: {}", e))));
+        results.push((address.clone(), result));
+    }
+    results
+}
+
+/// Ping a Minecraft server asynchronously, resolving its address with `resolver`.
+async fn ping_server_async_with<R: AddressResolver>(
+    resolver: &R,
+    address: &str,
+    timeout: Duration,
+) -> Result<ServerStatus> {
+    // The system resolver has no timeout of its own
+    let resolved = tokio::time::timeout(timeout, resolve_server(resolver, address))
+        .await
+        .map_err(|_| dns_timeout(address, timeout))??;
+    let addr = resolved
+        .connect_target()
+        .ok_or_else(|| OxideVaultError::DnsResolution("Could not resolve address".to_string()))?;
+
+    let mut stream = tokio::time::timeout(timeout, tokio::net::TcpStream::connect(addr))
+        .await
+        .map_err(|_| OxideVaultError::ServerProtocol("Connection failed: timed out".to_string()))?
         .map_err(|e| OxideVaultError::ServerProtocol(format!("Connection failed: {}", e)))?;
 
-    stream.set_read_timeout(Some(Duration::from_secs(10)))?;
-    stream.set_write_timeout(Some(Duration::from_secs(10)))?;
+    // Send the original hostname, which virtual-hosted servers route on
+    let handshake = build_handshake(&resolved.host, addr.port())?;
+    let status_request = build_status_request()?;
+
+    let exchange = async {
+        send_packet_async(&mut stream, &handshake).await?;
+        send_packet_async(&mut stream, &status_request).await?;
+        read_packet_async(&mut stream).await
+    };
+
+    let response = match tokio::time::timeout(timeout, exchange).await {
+        Ok(response) => response
+            .map_err(map_status_read_error)
+            .and_then(|response| parse_status_response(&response)),
+        // A server that stays silent won't answer the legacy ping either
+        Err(_) => return Err(OxideVaultError::ServerProtocol(
+            "Timed out waiting for server status".to_string()
+        )),
+    };
+
+    let mut status = match response {
+        Ok(status) => status,
+        Err(e) if is_timeout(&e) => return Err(e),
+        // Pre-1.7 servers don't understand the modern handshake (see `ping_server_via`)
+        Err(e) => {
+            return tokio::task::spawn_blocking(move || legacy::ping_legacy_at(addr, timeout))
+                .await
+                .map_err(|e| OxideVaultError::ServerProtocol(format!("Task join error: {}", e)))?
+                .map_err(|_| e);
+        }
+    };
+
+    // Measure latency with a ping/pong exchange (not every server answers)
+    let payload = ping_payload();
+    let ping = build_ping(payload)?;
+    let started = Instant::now();
+    let pong_exchange = async {
+        send_packet_async(&mut stream, &ping).await?;
+        read_packet_async(&mut stream).await
+    };
+    status.latency_ms = match tokio::time::timeout(timeout, pong_exchange).await {
+        Ok(Ok(pong)) if is_pong(&pong, payload) => Some(started.elapsed().as_millis() as u64),
+        _ => None,
+    };
 
-    // Build handshake packet
+    Ok(status)
+}
+
+/// Build the handshake packet announcing a status request.
+fn build_handshake(host: &str, port: u16) -> Result<Vec<u8>> {
     let mut handshake = Vec::new();
     write_varint(&mut handshake, 0)?; // Packet ID: handshake
     write_varint(&mut handshake, -1)?; // Protocol version (-1 for auto-detection)
 
-    // Use the resolved IP address and port
-    let host_str = addr.ip().to_string();
-    let port = addr.port();
-
-    write_string(&mut handshake, &host_str)?;
+    write_string(&mut handshake, host)?;
     handshake.write_all(&port.to_be_bytes())?; // Port
     write_varint(&mut handshake, 1)?; // Next state: status
+    Ok(handshake)
+}
 
-    // Send handshake
-    send_packet(&mut stream, &handshake)?;
-
-    // Send status request
+/// Build the status request packet.
+fn build_status_request() -> Result<Vec<u8>> {
     let mut status_request = Vec::new();
     write_varint(&mut status_request, 0)?; // Packet ID: request
-    send_packet(&mut stream, &status_request)?;
+    Ok(status_request)
+}
 
-    // Read response
-    let response = read_packet(&mut stream)?;
-    let json_str = read_string(&response[1..])?;
+/// Payload for a ping packet; servers echo it back unchanged.
+fn ping_payload() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as i64)
+}
 
-    // Parse JSON response
-    let status: ServerStatus = serde_json::from_str(&json_str)
-        .map_err(|e| OxideVaultError::ServerProtocol(format!("Failed to parse server response: {}", e)))?;
+/// Build a ping packet carrying `payload`.
+fn build_ping(payload: i64) -> Result<Vec<u8>> {
+    let mut ping = Vec::new();
+    write_varint(&mut ping, 1)?; // Packet ID: ping
+    ping.extend_from_slice(&payload.to_be_bytes());
+    Ok(ping)
+}
 
-    Ok(status)
+/// Whether `packet` is a pong echoing `payload`.
+fn is_pong(packet: &[u8], payload: i64) -> bool {
+    packet.len() == 9 && packet[0] == 0x01 && packet[1..] == payload.to_be_bytes()
+}
+
+/// Map an I/O error while waiting for the status response.
+///
+/// An unexpected EOF means the server accepted the connection and then hung up.
+fn map_status_read_error(e: std::io::Error) -> OxideVaultError {
+    match e.kind() {
+        std::io::ErrorKind::UnexpectedEof => OxideVaultError::ServerProtocol(
+            "Server closed connection before sending status".to_string()
+        ),
+        _ => OxideVaultError::from(e),
+    }
+}
+
+/// Parse a status response packet (packet ID followed by the JSON string).
+///
+/// This is the pure half of a status ping: callers do the I/O and hand over
+/// the packet read from the server.
+///
+/// # Errors
+///
+/// Returns a `ServerProtocol` error if the packet is empty, isn't a status
+/// response (packet ID `0x00`), its string is truncated, or the JSON is invalid.
+fn parse_status_response(packet: &[u8]) -> Result<ServerStatus> {
+    if packet.is_empty() {
+        return Err(OxideVaultError::ServerProtocol("Empty status response".to_string()));
+    }
+
+    let malformed = |e: std::io::Error| OxideVaultError::ServerProtocol(
+        format!("Malformed status response: {}", e)
+    );
+
+    let (packet_id, offset) = read_varint_from_slice(packet).map_err(malformed)?;
+    if packet_id != STATUS_RESPONSE_PACKET_ID {
+        return Err(OxideVaultError::ServerProtocol(
+            format!("Unexpected packet ID in status response: {:#04x}", packet_id)
+        ));
+    }
+
+    let json_str = read_string(&packet[offset..]).map_err(malformed)?;
+
+    serde_json::from_str(&json_str)
+        .map_err(|e| OxideVaultError::ServerProtocol(format!("Failed to parse server response: {}", e)))
+}
+
+/// Split a "host:port" address into its host and port parts.
+///
+/// This is the one place addresses are taken apart; configuration and the
+/// SOCKS5 proxy validate with it too. IPv6 hosts must be bracketed
+/// (e.g. `[::1]:25565`) and the brackets are removed.
+///
+/// # Errors
+///
+/// Returns an error if the port is missing or invalid, the host is empty,
+/// brackets are unbalanced, or an IPv6 host isn't bracketed.
+pub fn split_host_port(address: &str) -> Result<(String, u16)> {
+    let invalid = || OxideVaultError::ServerProtocol(
+        format!("Invalid server address '{}'. Expected 'host:port' format.", address)
+    );
+
+    let (host, port) = address.rsplit_once(':').ok_or_else(invalid)?;
+
+    let port = port.parse::<u16>().map_err(|_| OxideVaultError::ServerProtocol(
+        format!("Invalid port in server address: '{}'", port)
+    ))?;
+
+    let host = match host.strip_prefix('[') {
+        Some(inner) => inner
+            .strip_suffix(']')
+            .filter(|inner| inner.contains(':') && !inner.contains(['[', ']']))
+            .ok_or_else(invalid)?,
+        None if host.contains([':', '[', ']']) => return Err(invalid()),
+        None => host,
+    };
+
+    if host.is_empty() {
+        return Err(OxideVaultError::ServerProtocol(
+            format!("Missing host in server address: '{}'", address)
+        ));
+    }
+
+    Ok((host.to_string(), port))
 }
 
 #[cfg(test)]
@@ -167,6 +957,465 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_ping_server_closed_before_status() {
+        use std::io::Read;
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+
+        // Accept, consume the handshake and status request, then hang up without replying
+        let handle = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut received = Vec::new();
+            let mut buf = [0u8; 256];
+            while !received.ends_with(&[0x01, 0x00]) {
+                match stream.read(&mut buf) {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => received.extend_from_slice(&buf[..n]),
+                }
+            }
+        });
+
+        let result = ping_server(&address);
+        handle.join().unwrap();
+
+        match result {
+            Err(OxideVaultError::ServerProtocol(msg)) => {
+                assert_eq!(msg, "Server closed connection before sending status");
+            }
+            other => panic!("Expected ServerProtocol error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_ping_server_falls_back_to_legacy() {
+        use std::io::Read;
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+
+        let handle = std::thread::spawn(move || {
+            // Like 1.5.2, reject the modern handshake with a kick and hang up
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0u8; 256];
+            let _ = stream.read(&mut buf).unwrap();
+            stream.write_all(&[0xFF, 0x00, 0x00]).unwrap();
+            drop(stream);
+
+            // Then answer the legacy ping
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0u8; 2];
+            stream.read_exact(&mut request).unwrap();
+            assert_eq!(request, [0xFE, 0x01]);
+
+            let units: Vec<u16> = "§1\u{0}61\u{0}1.5.2\u{0}Old Server\u{0}4\u{0}20".encode_utf16().collect();
+            let mut response = vec![0xFF];
+            response.extend_from_slice(&(units.len() as u16).to_be_bytes());
+            for unit in units {
+                response.extend_from_slice(&unit.to_be_bytes());
+            }
+            stream.write_all(&response).unwrap();
+        });
+
+        let status = ping_server(&address).unwrap();
+        handle.join().unwrap();
+
+        assert_eq!(status.version.name, "1.5.2");
+        assert_eq!(status.description.text(), "Old Server");
+        assert_eq!((status.players.online, status.players.max), (4, 20));
+    }
+
+    /// Start a stub server that reads the handshake and status request, then
+    /// optionally replies with `status_json` and answers a ping when `answer_ping` is set.
+    async fn spawn_status_stub(
+        status_json: Option<&'static str>,
+        answer_ping: bool,
+    ) -> (String, tokio::task::JoinHandle<Vec<u8>>) {
+        use tokio::io::AsyncReadExt;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+
+        let handle = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let handshake = read_packet_async(&mut stream).await.unwrap();
+            let _request = read_packet_async(&mut stream).await.unwrap();
+
+            match status_json {
+                Some(json) => {
+                    let mut response = Vec::new();
+                    write_varint(&mut response, 0).unwrap();
+                    write_string(&mut response, json).unwrap();
+                    send_packet_async(&mut stream, &response).await.unwrap();
+
+                    if answer_ping {
+                        let ping = read_packet_async(&mut stream).await.unwrap();
+                        send_packet_async(&mut stream, &ping).await.unwrap();
+                    }
+                }
+                // Keep the connection open without replying until the client gives up
+                None => {
+                    let mut buf = [0u8; 1];
+                    let _ = stream.read(&mut buf).await;
+                }
+            }
+
+            handshake
+        });
+
+        (address, handle)
+    }
+
+    #[tokio::test]
+    async fn test_ping_server_async_success() {
+        let (address, handle) = spawn_status_stub(Some(
+            r#"{"version":{"name":"1.21","protocol":767},"players":{"max":20,"online":2,"sample":[{"name":"Alice","id":"id1"}]},"description":"Async!"}"#,
+        ), false).await;
+
+        let status = ping_server_async(&address, DEFAULT_PING_TIMEOUT).await.unwrap();
+        handle.await.unwrap();
+
+        assert_eq!(status.players.online, 2);
+        assert_eq!(status.players.sample[0].name, "Alice");
+        assert_eq!(status.description.text(), "Async!");
+
+        // The stub hung up instead of answering the ping
+        assert_eq!(status.latency_ms, None);
+    }
+
+    /// Resolver pointing every SRV lookup at a fixed local target.
+    struct SrvStubResolver {
+        target_port: u16,
+    }
+
+    impl AddressResolver for SrvStubResolver {
+        async fn lookup_srv(&self, name: &str) -> Result<Option<SrvTarget>> {
+            assert_eq!(name, "_minecraft._tcp.play.example.com");
+            Ok(Some(SrvTarget { host: "node.example.net".to_string(), port: self.target_port }))
+        }
+
+        async fn lookup_ip(&self, host: &str, port: u16) -> Result<Vec<std::net::SocketAddr>> {
+            assert_eq!(host, "node.example.net");
+            Ok(vec![std::net::SocketAddr::from(([127, 0, 0, 1], port))])
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ping_server_async_follows_srv() {
+        let (address, handle) = spawn_status_stub(Some(
+            r#"{"version":{"name":"1.21","protocol":767},"players":{"max":20,"online":1},"description":"SRV"}"#,
+        ), false).await;
+        let target_port = address.rsplit_once(':').unwrap().1.parse().unwrap();
+
+        let resolver = SrvStubResolver { target_port };
+        let status = ping_server_async_with(&resolver, "play.example.com:25565", DEFAULT_PING_TIMEOUT)
+            .await
+            .unwrap();
+        assert_eq!(status.description.text(), "SRV");
+
+        // The handshake carries the original hostname, not the SRV target or IP
+        let handshake = handle.await.unwrap();
+        // Packet ID (1 byte) and protocol version -1 (5-byte VarInt) precede the host
+        let host = read_string(&handshake[6..]).unwrap();
+        assert_eq!(host, "play.example.com");
+    }
+
+    /// Resolver whose lookups never complete, like a misbehaving DNS server.
+    struct HangingResolver;
+
+    impl AddressResolver for HangingResolver {
+        async fn lookup_srv(&self, _name: &str) -> Result<Option<SrvTarget>> {
+            std::future::pending().await
+        }
+
+        async fn lookup_ip(&self, _host: &str, _port: u16) -> Result<Vec<std::net::SocketAddr>> {
+            std::future::pending().await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ping_server_async_dns_timeout() {
+        let started = Instant::now();
+        let result = ping_server_async_with(&HangingResolver, "play.example.com:25565", Duration::from_millis(200)).await;
+
+        match result {
+            Err(OxideVaultError::DnsResolution(msg)) => assert!(msg.contains("Timed out resolving")),
+            other => panic!("Expected DNS timeout, got {:?}", other),
+        }
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_ping_server_async_measures_latency() {
+        let (address, handle) = spawn_status_stub(Some(
+            r#"{"version":{"name":"1.21","protocol":767},"players":{"max":20,"online":0},"description":"Pong"}"#,
+        ), true).await;
+
+        let status = ping_server_async(&address, DEFAULT_PING_TIMEOUT).await.unwrap();
+        handle.await.unwrap();
+
+        assert!(status.latency_ms.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_ping_servers_isolates_failures() {
+        let (up, up_handle) = spawn_status_stub(Some(
+            r#"{"version":{"name":"1.21","protocol":767},"players":{"max":20,"online":3},"description":"Up"}"#,
+        ), false).await;
+        let (hanging, _hanging_handle) = spawn_status_stub(None, false).await;
+        let addresses = vec![hanging.clone(), "127.0.0.1:1".to_string(), up.clone()];
+
+        let started = Instant::now();
+        let results = ping_servers_with(&addresses, 2, Duration::from_millis(500)).await;
+        up_handle.await.unwrap();
+
+        // Results keep the input order, and the hanging server only costs its own timeout
+        assert!(started.elapsed() < Duration::from_secs(5));
+        let order: Vec<_> = results.iter().map(|(address, _)| address.clone()).collect();
+        assert_eq!(order, addresses);
+
+        assert!(matches!(&results[0].1, Err(OxideVaultError::ServerProtocol(msg)) if msg.contains("Timed out")));
+        assert!(results[1].1.is_err());
+        assert_eq!(results[2].1.as_ref().unwrap().players.online, 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_with_backoff() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        let delay = Duration::from_millis(1);
+
+        // Transient failures are retried until an attempt succeeds
+        let attempts = AtomicU32::new(0);
+        let result = retry_with_backoff(3, delay, || async {
+            match attempts.fetch_add(1, Ordering::SeqCst) {
+                0 | 1 => Err(OxideVaultError::ServerProtocol("Connection failed: refused".to_string())),
+                _ => Ok(42),
+            }
+        }).await;
+        assert_eq!(result.unwrap(), 42);
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+
+        // Parse errors are not retried
+        let attempts = AtomicU32::new(0);
+        let result: Result<()> = retry_with_backoff(3, delay, || async {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            Err(OxideVaultError::ServerProtocol("Failed to parse server response: bad".to_string()))
+        }).await;
+        assert!(result.is_err());
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+
+        // The last error is returned once retries run out
+        let attempts = AtomicU32::new(0);
+        let result: Result<()> = retry_with_backoff(2, delay, || async {
+            let n = attempts.fetch_add(1, Ordering::SeqCst);
+            Err(OxideVaultError::ServerProtocol(format!("Timed out (attempt {})", n)))
+        }).await;
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+        assert!(matches!(result, Err(OxideVaultError::ServerProtocol(msg)) if msg == "Timed out (attempt 2)"));
+    }
+
+    #[test]
+    fn test_is_transient() {
+        let refused = std::io::Error::from(std::io::ErrorKind::ConnectionRefused);
+        assert!(is_transient(&OxideVaultError::Io(refused)));
+        assert!(is_transient(&OxideVaultError::ServerProtocol("Timed out waiting for server status".to_string())));
+
+        let invalid = std::io::Error::from(std::io::ErrorKind::InvalidData);
+        assert!(!is_transient(&OxideVaultError::Io(invalid)));
+        assert!(!is_transient(&OxideVaultError::ServerProtocol("Empty status response".to_string())));
+    }
+
+    #[test]
+    fn test_is_pong() {
+        let ping = build_ping(42).unwrap();
+        assert!(is_pong(&ping, 42));
+        assert!(!is_pong(&ping, 43));
+        assert!(!is_pong(&[0x00, 0, 0, 0, 0, 0, 0, 0, 42], 42));
+        assert!(!is_pong(&[0x01], 42));
+    }
+
+    #[tokio::test]
+    async fn test_ping_server_async_times_out() {
+        let (address, handle) = spawn_status_stub(None, false).await;
+
+        let result = ping_server_async(&address, Duration::from_millis(200)).await;
+        match result {
+            Err(OxideVaultError::ServerProtocol(msg)) => {
+                assert_eq!(msg, "Timed out waiting for server status");
+            }
+            other => panic!("Expected timeout error, got {:?}", other),
+        }
+        handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_ping_server_async_timeout_skips_legacy_fallback() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+
+        // Accept the modern ping and stay silent; a legacy retry would show up
+        // as a second connection
+        let handle = tokio::spawn(async move {
+            let (_stream, _) = listener.accept().await.unwrap();
+            tokio::time::timeout(Duration::from_millis(500), listener.accept()).await.is_ok()
+        });
+
+        let result = ping_server_async(&address, Duration::from_millis(200)).await;
+        assert!(matches!(
+            result,
+            Err(OxideVaultError::ServerProtocol(ref msg)) if msg == "Timed out waiting for server status"
+        ));
+        assert!(!handle.await.unwrap(), "timed-out ping should not fall back to the legacy ping");
+    }
+
+    #[tokio::test]
+    async fn test_ping_server_async_errors() {
+        assert!(ping_server_async("invalid-address-no-port", DEFAULT_PING_TIMEOUT).await.is_err());
+
+        match ping_server_async("127.0.0.1:1", DEFAULT_PING_TIMEOUT).await {
+            Err(OxideVaultError::ServerProtocol(msg)) => assert!(msg.contains("Connection failed")),
+            other => panic!("Expected connection error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_ping_server_unknown_host() {
+        match ping_server_via("unknown-host.invalid:25565", None, DEFAULT_PING_TIMEOUT) {
+            Err(OxideVaultError::DnsResolution(msg)) => assert!(msg.contains("resolve address")),
+            other => panic!("Expected DNS resolution error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_status_response_empty() {
+        assert!(matches!(parse_status_response(&[]), Err(OxideVaultError::ServerProtocol(_))));
+
+        // A packet ID with no string after it
+        assert!(matches!(parse_status_response(&[0x00]), Err(OxideVaultError::ServerProtocol(_))));
+    }
+
+    fn status_packet(packet_id: i32, json: &str) -> Vec<u8> {
+        let mut packet = Vec::new();
+        write_varint(&mut packet, packet_id).unwrap();
+        write_string(&mut packet, json).unwrap();
+        packet
+    }
+
+    #[test]
+    fn test_parse_status_response() {
+        let packet = status_packet(
+            0x00,
+            r#"{"version":{"name":"1.21","protocol":767},"players":{"max":20,"online":3},"description":"A server"}"#,
+        );
+        let status = parse_status_response(&packet).unwrap();
+        assert_eq!(status.version.name, "1.21");
+        assert_eq!(status.players.online, 3);
+        assert_eq!(status.players.max, 20);
+    }
+
+    #[test]
+    fn test_parse_status_response_invalid_json() {
+        let packet = status_packet(0x00, "{not json");
+        assert!(matches!(parse_status_response(&packet), Err(OxideVaultError::ServerProtocol(_))));
+    }
+
+    #[test]
+    fn test_parse_status_response_truncated_string() {
+        let mut packet = status_packet(0x00, r#"{"players":{"max":20,"online":3}}"#);
+        packet.truncate(packet.len() - 5);
+        assert!(matches!(parse_status_response(&packet), Err(OxideVaultError::ServerProtocol(_))));
+    }
+
+    #[test]
+    fn test_parse_status_response_wrong_packet_id() {
+        let packet = status_packet(0x01, "{}");
+        match parse_status_response(&packet) {
+            Err(OxideVaultError::ServerProtocol(msg)) => assert!(msg.contains("0x01")),
+            other => panic!("Expected protocol error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_split_host_port() {
+        assert_eq!(split_host_port("localhost:25565").unwrap(), ("localhost".to_string(), 25565));
+        assert_eq!(split_host_port("[::1]:25565").unwrap(), ("::1".to_string(), 25565));
+        assert!(split_host_port("localhost").is_err());
+        assert!(split_host_port("localhost:99999").is_err());
+
+        // Empty hosts, unbalanced brackets, and unbracketed IPv6 are rejected
+        assert!(split_host_port(":25565").is_err());
+        assert!(split_host_port("[]:25565").is_err());
+        assert!(split_host_port("[::1:25565").is_err());
+        assert!(split_host_port("::1]:25565").is_err());
+        assert!(split_host_port("[[::1]]:25565").is_err());
+        assert!(split_host_port("[localhost]:25565").is_err());
+        assert!(split_host_port("::1:25565").is_err());
+    }
+
+    #[test]
+    fn test_status_with_negative_protocol() {
+        let json = r#"{
+            "version": {"name": "Starting", "protocol": -1},
+            "players": {"max": 20, "online": 0},
+            "description": "Server is starting"
+        }"#;
+
+        let status: ServerStatus = serde_json::from_str(json).unwrap();
+        assert_eq!(status.version.protocol, -1);
+        assert_eq!(status.version.name, "Starting");
+    }
+
+    #[test]
+    fn test_status_builder() {
+        let status = ServerStatus::builder().build();
+        assert_eq!(status.players.online, 0);
+        assert!(status.players.sample.is_empty());
+        assert_eq!(status.description.text(), "A Minecraft Server");
+
+        let status = ServerStatus::builder()
+            .version("Paper 1.20.4", 765)
+            .players(2, 50)
+            .player("Alice", "550e8400-e29b-41d4-a716-446655440000")
+            .player("Bob", "550e8400-e29b-41d4-a716-446655440001")
+            .description("Hello there")
+            .build();
+
+        assert_eq!(status.version.name, "Paper 1.20.4");
+        assert_eq!(status.version.protocol, 765);
+        assert_eq!(status.players.online, 2);
+        assert_eq!(status.players.max, 50);
+        assert_eq!(status.players.sample.len(), 2);
+        assert_eq!(status.players.sample[1].name, "Bob");
+        assert_eq!(status.description.text(), "Hello there");
+
+        // Builder output round-trips through the wire format
+        let json = serde_json::to_string(&status).unwrap();
+        let parsed: ServerStatus = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.players.sample[0].name, "Alice");
+    }
+
+    #[test]
+    fn test_player_sample_is_anonymous() {
+        let sample = |name: &str, id: &str| PlayerSample { name: name.to_string(), id: id.to_string() };
+
+        // All-zero UUIDs, dashed or not
+        assert!(sample("Anonymous Player", "00000000-0000-0000-0000-000000000000").is_anonymous());
+        assert!(sample("Steve", "00000000000000000000000000000000").is_anonymous());
+
+        // Placeholder names regardless of UUID
+        assert!(sample("Anonymous Player", "8667ba71-b85a-4004-af54-457a9734eed7").is_anonymous());
+        assert!(sample("anonymous", "8667ba71-b85a-4004-af54-457a9734eed7").is_anonymous());
+        assert!(sample("§aJoin our Discord!", "8667ba71-b85a-4004-af54-457a9734eed7").is_anonymous());
+
+        // Real players
+        assert!(!sample("Steve", "8667ba71-b85a-4004-af54-457a9734eed7").is_anonymous());
+        assert!(!sample("Anonymous_Fan", "069a79f4-44e9-4726-a5be-fca90e38aaf5").is_anonymous());
+    }
+
     #[test]
     fn test_description_text() {
         let desc_string = Description::String("A Minecraft Server".to_string());
@@ -174,23 +1423,63 @@ mod tests {
 
         let desc_object = Description::Object {
             text: "Another Server".to_string(),
+            extra: Vec::new(),
         };
         assert_eq!(desc_object.text(), "Another Server");
     }
 
-    // Note: Testing successful ping_server connections requires a running Minecraft server
-    // In a real CI/CD environment, you would either:
-    // 1. Set up a test Minecraft server in your CI pipeline
-    // 2. Use integration tests that run separately from unit tests
-    // 3. Mock the TcpStream for more detailed testing
-    //
-    // Example test that would work with a real server:
-    // #[test]
-    // #[ignore] // Ignored by default, run with --ignored flag when server is available
-    // fn test_ping_server_success() {
-    //     let result = ping_server("localhost:25565");
-    //     assert!(result.is_ok());
-    //     let status = result.unwrap();
-    //     assert!(status.players.max > 0);
-    // }
+    #[test]
+    fn test_description_to_plain() {
+        let nested: Description = serde_json::from_str(
+            r#"{"text":"","extra":[{"text":"Hello","color":"red"},{"text":" World","extra":[" and ",{"text":"§lfriends"}]}]}"#,
+        ).unwrap();
+        assert_eq!(nested.text(), "");
+        assert_eq!(nested.to_plain(), "Hello World and friends");
+
+        let legacy = Description::String("§6Gold §r§kServer§".to_string());
+        assert_eq!(legacy.to_plain(), "Gold Server");
+
+        // Components without a text field still parse
+        let extra_only: Description = serde_json::from_str(r#"{"extra":["A","B"]}"#).unwrap();
+        assert_eq!(extra_only.to_plain(), "AB");
+    }
+
+    #[test]
+    fn test_favicon_png() {
+        let status: ServerStatus = serde_json::from_str(
+            r#"{"version":{"name":"1.21","protocol":767},"players":{"max":20,"online":0},
+                "description":"x","favicon":"data:image/png;base64,iVBO\nRw=="}"#,
+        ).unwrap();
+        assert_eq!(status.favicon_png(), Some(vec![0x89, b'P', b'N', b'G']));
+
+        assert_eq!(ServerStatus::builder().build().favicon_png(), None);
+        assert_eq!(ServerStatus::builder().favicon("data:image/gif;base64,AAAA").build().favicon_png(), None);
+        assert_eq!(ServerStatus::builder().favicon("data:image/png;base64,!!").build().favicon_png(), None);
+    }
+
+    #[test]
+    fn test_cap_large_player_sample() {
+        let sample: Vec<_> = (0..5000)
+            .map(|i| format!(r#"{{"name":"Griefer{}","id":"{:032x}"}}"#, i, i))
+            .collect();
+        let json = format!(
+            r#"{{"version":{{"name":"1.21","protocol":767}},"players":{{"max":5000,"online":5000,"sample":[{}]}},"description":"Huge"}}"#,
+            sample.join(",")
+        );
+
+        let mut status: ServerStatus = serde_json::from_str(&json).unwrap();
+        assert_eq!(status.players.sample.len(), 5000);
+
+        status.players.cap_sample(DEFAULT_MAX_PLAYER_SAMPLE);
+        assert_eq!(status.players.sample.len(), DEFAULT_MAX_PLAYER_SAMPLE);
+        assert!(status.players.sample.capacity() <= DEFAULT_MAX_PLAYER_SAMPLE);
+        assert_eq!(status.players.omitted, 4900);
+        assert_eq!(status.players.sample[99].name, "Griefer99");
+
+        // Capping a sample already within the limit is a no-op
+        status.players.cap_sample(DEFAULT_MAX_PLAYER_SAMPLE);
+        assert_eq!(status.players.omitted, 4900);
+    }
+
+    // Successful pings against a mock server are covered by tests/mc_server.rs
 }
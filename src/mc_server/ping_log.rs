@@ -0,0 +1,102 @@
+//! JSON Lines logging of ping results.
+//!
+//! Each ping is appended as one JSON object per line so the log can be
+//! analyzed offline with standard tools.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::time::Duration;
+use serde_json::json;
+use crate::error::Result;
+use super::ServerStatus;
+
+/// Append the result of a ping to a JSON Lines file.
+///
+/// The file is created if needed and opened in append mode, and each entry is
+/// written with a single `write` call so concurrent writers don't interleave
+/// partial lines.
+///
+/// # Arguments
+///
+/// * `path` - Log file path
+/// * `result` - Status returned by the ping, or the error it failed with
+/// * `latency` - Time the ping took
+/// * `timestamp` - Unix timestamp (seconds) of the ping
+pub fn append_ping_log(
+    path: &Path,
+    result: &Result<ServerStatus>,
+    latency: Duration,
+    timestamp: u64,
+) -> Result<()> {
+    let entry = match result {
+        Ok(status) => json!({
+            "timestamp": timestamp,
+            "ok": true,
+            "online": status.players.online,
+            "max": status.players.max,
+            "latency_ms": latency.as_millis() as u64,
+            "error": null,
+        }),
+        Err(e) => json!({
+            "timestamp": timestamp,
+            "ok": false,
+            "online": null,
+            "max": null,
+            "latency_ms": latency.as_millis() as u64,
+            "error": e.to_string(),
+        }),
+    };
+
+    let mut line = entry.to_string();
+    line.push('\n');
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(line.as_bytes())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::OxideVaultError;
+    use serde_json::Value;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_append_ping_log() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("pings.jsonl");
+
+        let status = ServerStatus::builder().players(3, 20).build();
+        append_ping_log(&path, &Ok(status.clone()), Duration::from_millis(42), 1_700_000_000).unwrap();
+        append_ping_log(
+            &path,
+            &Err(OxideVaultError::ServerProtocol("Connection failed".to_string())),
+            Duration::from_millis(10_000),
+            1_700_000_060,
+        ).unwrap();
+        append_ping_log(&path, &Ok(status), Duration::from_millis(38), 1_700_000_120).unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let entries: Vec<Value> = contents
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(entries.len(), 3);
+
+        assert_eq!(entries[0]["ok"], true);
+        assert_eq!(entries[0]["online"], 3);
+        assert_eq!(entries[0]["max"], 20);
+        assert_eq!(entries[0]["latency_ms"], 42);
+        assert_eq!(entries[0]["timestamp"], 1_700_000_000);
+        assert!(entries[0]["error"].is_null());
+
+        assert_eq!(entries[1]["ok"], false);
+        assert!(entries[1]["online"].is_null());
+        assert_eq!(entries[1]["error"], "Server protocol error: Connection failed");
+
+        assert_eq!(entries[2]["timestamp"], 1_700_000_120);
+    }
+}
@@ -5,6 +5,13 @@
 
 use std::io::{Read, Write};
 use std::net::TcpStream;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+/// Largest packet length accepted from a server (2 MiB).
+///
+/// Status responses are far smaller; anything bigger is treated as malformed
+/// rather than allocated.
+pub const MAX_PACKET_LENGTH: usize = 2 * 1024 * 1024;
 
 /// Send a packet to the Minecraft server.
 ///
@@ -20,13 +27,49 @@ pub fn send_packet(stream: &mut TcpStream, data: &[u8]) -> std::io::Result<()> {
 /// Read a complete packet from the Minecraft server.
 ///
 /// Returns the packet data without the length prefix.
-pub fn read_packet(stream: &mut TcpStream) -> std::io::Result<Vec<u8>> {
-    let length = read_varint(stream)?;
-    let mut buffer = vec![0u8; length as usize];
+pub fn read_packet<R: Read>(stream: &mut R) -> std::io::Result<Vec<u8>> {
+    let length = checked_packet_length(read_varint(stream)?)?;
+    let mut buffer = vec![0u8; length];
     stream.read_exact(&mut buffer)?;
     Ok(buffer)
 }
 
+/// Send a packet to the Minecraft server asynchronously.
+///
+/// Async counterpart of [`send_packet`].
+pub async fn send_packet_async<W: AsyncWrite + Unpin>(stream: &mut W, data: &[u8]) -> std::io::Result<()> {
+    let mut packet = Vec::new();
+    write_varint(&mut packet, data.len() as i32)?;
+    packet.extend_from_slice(data);
+    stream.write_all(&packet).await?;
+    stream.flush().await?;
+    Ok(())
+}
+
+/// Read a complete packet from the Minecraft server asynchronously.
+///
+/// Async counterpart of [`read_packet`].
+pub async fn read_packet_async<R: AsyncRead + Unpin>(stream: &mut R) -> std::io::Result<Vec<u8>> {
+    let length = checked_packet_length(read_varint_async(stream).await?)?;
+    let mut buffer = vec![0u8; length];
+    stream.read_exact(&mut buffer).await?;
+    Ok(buffer)
+}
+
+/// Validate a decoded packet length before allocating a buffer for it.
+///
+/// Returns an `InvalidData` error if the length is negative or exceeds
+/// [`MAX_PACKET_LENGTH`].
+fn checked_packet_length(length: i32) -> std::io::Result<usize> {
+    match usize::try_from(length) {
+        Ok(length) if length <= MAX_PACKET_LENGTH => Ok(length),
+        _ => Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("Invalid packet length: {}", length),
+        )),
+    }
+}
+
 /// Write a VarInt to a buffer.
 ///
 /// VarInts are variable-length encoded integers used in the Minecraft protocol.
@@ -48,20 +91,53 @@ pub fn write_varint(buf: &mut Vec<u8>, value: i32) -> std::io::Result<()> {
     Ok(())
 }
 
-/// Read a VarInt from a TCP stream.
-pub fn read_varint(stream: &mut TcpStream) -> std::io::Result<i32> {
+/// Read a VarInt from a stream.
+pub fn read_varint<R: Read>(stream: &mut R) -> std::io::Result<i32> {
     let mut result = 0;
     let mut shift = 0;
     loop {
-        let mut byte = [0u8; 1];
-        stream.read_exact(&mut byte)?;
-        if process_varint_byte(byte[0], &mut result, &mut shift)? {
+        let byte = read_byte(stream)?;
+        if process_varint_byte(byte, &mut result, &mut shift)? {
             break;
         }
     }
     Ok(result)
 }
 
+/// Read a VarInt from an async stream.
+pub async fn read_varint_async<R: AsyncRead + Unpin>(stream: &mut R) -> std::io::Result<i32> {
+    let mut result = 0;
+    let mut shift = 0;
+    loop {
+        // read_u8 maps a closed stream to UnexpectedEof and retries interrupted reads
+        let byte = stream.read_u8().await?;
+        if process_varint_byte(byte, &mut result, &mut shift)? {
+            break;
+        }
+    }
+    Ok(result)
+}
+
+/// Read a single byte, retrying if the read is interrupted.
+///
+/// Returns an `UnexpectedEof` error if the stream is closed before a byte arrives.
+fn read_byte<R: Read>(stream: &mut R) -> std::io::Result<u8> {
+    let mut byte = [0u8; 1];
+    loop {
+        match stream.read(&mut byte) {
+            Ok(0) => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::UnexpectedEof,
+                    "Connection closed while reading VarInt",
+                ));
+            }
+            Ok(_) => return Ok(byte[0]),
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        }
+    }
+}
+
 /// Read a VarInt from a byte slice.
 ///
 /// Returns the decoded value and the number of bytes consumed.
@@ -117,13 +193,17 @@ pub fn write_string(buf: &mut Vec<u8>, s: &str) -> std::io::Result<()> {
 /// Returns the decoded string.
 pub fn read_string(data: &[u8]) -> std::io::Result<String> {
     let (len, offset) = read_varint_from_slice(data)?;
-    if offset + len as usize > data.len() {
+    let len = usize::try_from(len).map_err(|_| std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        "Negative string length",
+    ))?;
+    if offset + len > data.len() {
         return Err(std::io::Error::new(
             std::io::ErrorKind::UnexpectedEof,
             "String length exceeds data size",
         ));
     }
-    let s = String::from_utf8_lossy(&data[offset..offset + len as usize]);
+    let s = String::from_utf8_lossy(&data[offset..offset + len]);
     Ok(s.to_string())
 }
 
@@ -153,6 +233,62 @@ mod tests {
         assert_eq!(read_varint_from_slice(&[0x80, 0x01]).unwrap(), (128, 2));
     }
 
+    /// Reader that fails with `Interrupted` before each successful read.
+    struct InterruptingReader {
+        data: Vec<u8>,
+        pos: usize,
+        interrupt_next: bool,
+    }
+
+    impl Read for InterruptingReader {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.interrupt_next {
+                self.interrupt_next = false;
+                return Err(std::io::Error::new(std::io::ErrorKind::Interrupted, "interrupted"));
+            }
+            self.interrupt_next = true;
+            if self.pos >= self.data.len() || buf.is_empty() {
+                return Ok(0);
+            }
+            buf[0] = self.data[self.pos];
+            self.pos += 1;
+            Ok(1)
+        }
+    }
+
+    #[test]
+    fn test_read_varint_retries_interrupted() {
+        let mut reader = InterruptingReader { data: vec![0x80, 0x01], pos: 0, interrupt_next: true };
+        assert_eq!(read_varint(&mut reader).unwrap(), 128);
+    }
+
+    #[test]
+    fn test_read_varint_eof() {
+        let mut reader: &[u8] = &[0x80];
+        let err = read_varint(&mut reader).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+    }
+
+    #[tokio::test]
+    async fn test_read_varint_async() {
+        let mut reader: &[u8] = &[0x80, 0x01, 0x7F];
+        assert_eq!(read_varint_async(&mut reader).await.unwrap(), 128);
+        assert_eq!(read_varint_async(&mut reader).await.unwrap(), 127);
+
+        let err = read_varint_async(&mut reader).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+    }
+
+    #[tokio::test]
+    async fn test_packet_round_trip_async() {
+        let mut buf = Vec::new();
+        send_packet_async(&mut buf, b"hello").await.unwrap();
+        assert_eq!(buf, vec![5, b'h', b'e', b'l', b'l', b'o']);
+
+        let mut reader: &[u8] = &buf;
+        assert_eq!(read_packet_async(&mut reader).await.unwrap(), b"hello");
+    }
+
     #[test]
     fn test_string_encoding() {
         let mut buf = Vec::new();
@@ -165,4 +301,44 @@ mod tests {
         let data = vec![4, b't', b'e', b's', b't'];
         assert_eq!(read_string(&data).unwrap(), "test");
     }
+
+    #[test]
+    fn test_read_packet_rejects_oversized_length() {
+        // Length of i32::MAX followed by no data
+        let mut buf = Vec::new();
+        write_varint(&mut buf, i32::MAX).unwrap();
+        let err = read_packet(&mut buf.as_slice()).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+        // Just over the limit
+        let mut buf = Vec::new();
+        write_varint(&mut buf, MAX_PACKET_LENGTH as i32 + 1).unwrap();
+        let err = read_packet(&mut buf.as_slice()).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_read_packet_rejects_negative_length() {
+        let mut buf = Vec::new();
+        write_varint(&mut buf, -1).unwrap();
+        let err = read_packet(&mut buf.as_slice()).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+        let mut string = Vec::new();
+        write_varint(&mut string, -5).unwrap();
+        assert_eq!(read_string(&string).unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[tokio::test]
+    async fn test_read_packet_async_rejects_oversized_length() {
+        let mut buf = Vec::new();
+        write_varint(&mut buf, i32::MAX).unwrap();
+        let err = read_packet_async(&mut buf.as_slice()).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+        let mut buf = Vec::new();
+        write_varint(&mut buf, i32::MIN).unwrap();
+        let err = read_packet_async(&mut buf.as_slice()).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
 }
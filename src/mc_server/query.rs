@@ -0,0 +1,341 @@
+//! UDP Query protocol (GameSpot4) client.
+//!
+//! Servers with `enable-query=true` answer a "full stat" request over UDP with
+//! the complete online player list and plugin information, unlike the status
+//! protocol's capped player sample.
+
+use std::time::Duration;
+use tokio::net::UdpSocket;
+use crate::error::{OxideVaultError, Result};
+
+/// Magic bytes prefixed to every client request.
+const MAGIC: [u8; 2] = [0xFE, 0xFD];
+const TYPE_HANDSHAKE: u8 = 0x09;
+const TYPE_STAT: u8 = 0x00;
+
+/// Padding between the response header and the key/value section.
+const KV_PADDING: &[u8] = b"splitnum\x00\x80\x00";
+/// Padding between the key/value section and the player list.
+const PLAYER_PADDING: &[u8] = b"\x01player_\x00\x00";
+
+/// Maximum size of a query response datagram.
+const MAX_RESPONSE_SIZE: usize = 8192;
+
+/// Full stat response from the Query protocol.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueryResponse {
+    /// Server MOTD
+    pub motd: String,
+    /// Game type (always "SMP" for vanilla servers)
+    pub game_type: String,
+    /// Server version
+    pub version: String,
+    /// Server software reported alongside plugins (e.g. "Paper on 1.21"), if any
+    pub server_mod: Option<String>,
+    /// Installed plugins
+    pub plugins: Vec<String>,
+    /// Name of the main world
+    pub map: String,
+    pub online: u32,
+    pub max: u32,
+    /// Names of every online player
+    pub players: Vec<String>,
+}
+
+/// Query a Minecraft server for its full stat over UDP.
+///
+/// # Arguments
+///
+/// * `address` - Query address in "host:port" format (the `query.port` from server.properties)
+/// * `timeout` - How long to wait for each response
+///
+/// # Errors
+///
+/// Returns an error if the server doesn't answer in time (query is likely
+/// disabled) or sends a malformed response.
+pub async fn query_server(address: &str, timeout: Duration) -> Result<QueryResponse> {
+    let socket = UdpSocket::bind("0.0.0.0:0").await?;
+    socket.connect(address).await
+        .map_err(|e| OxideVaultError::DnsResolution(format!("Failed to resolve query address: {}", e)))?;
+
+    // Session IDs must only use the low 4 bits of each byte
+    let session_id = rand::random::<u32>() & 0x0F0F_0F0F;
+
+    let mut handshake = Vec::with_capacity(7);
+    handshake.extend_from_slice(&MAGIC);
+    handshake.push(TYPE_HANDSHAKE);
+    handshake.extend_from_slice(&session_id.to_be_bytes());
+
+    let response = exchange(&socket, &handshake, timeout).await?;
+    let token = parse_challenge(&response, session_id)?;
+
+    let mut stat_request = Vec::with_capacity(15);
+    stat_request.extend_from_slice(&MAGIC);
+    stat_request.push(TYPE_STAT);
+    stat_request.extend_from_slice(&session_id.to_be_bytes());
+    stat_request.extend_from_slice(&token.to_be_bytes());
+    stat_request.extend_from_slice(&[0, 0, 0, 0]); // Padding requests the full stat
+
+    let response = exchange(&socket, &stat_request, timeout).await?;
+    check_header(&response, TYPE_STAT, session_id)?;
+    parse_full_stat(&response[5..])
+}
+
+/// Send a request and wait for a single response datagram.
+async fn exchange(socket: &UdpSocket, request: &[u8], timeout: Duration) -> Result<Vec<u8>> {
+    socket.send(request).await?;
+
+    let mut buffer = vec![0u8; MAX_RESPONSE_SIZE];
+    let len = tokio::time::timeout(timeout, socket.recv(&mut buffer))
+        .await
+        .map_err(|_| OxideVaultError::ServerProtocol(
+            "Timed out waiting for query response. Is enable-query=true set?".to_string()
+        ))??;
+
+    buffer.truncate(len);
+    Ok(buffer)
+}
+
+/// Check a response's type and session ID.
+fn check_header(response: &[u8], expected_type: u8, session_id: u32) -> Result<()> {
+    if response.len() < 5 || response[0] != expected_type {
+        return Err(OxideVaultError::ServerProtocol("Invalid query response header".to_string()));
+    }
+
+    if response[1..5] != session_id.to_be_bytes() {
+        return Err(OxideVaultError::ServerProtocol("Query response session ID mismatch".to_string()));
+    }
+
+    Ok(())
+}
+
+/// Parse the challenge token from a handshake response.
+fn parse_challenge(response: &[u8], session_id: u32) -> Result<i32> {
+    check_header(response, TYPE_HANDSHAKE, session_id)?;
+
+    let mut fields = NullTerminated::new(&response[5..]);
+    fields.next()
+        .and_then(|token| token.trim().parse::<i32>().ok())
+        .ok_or_else(|| OxideVaultError::ServerProtocol("Invalid query challenge token".to_string()))
+}
+
+/// Parse the body of a full stat response (after the type and session ID).
+fn parse_full_stat(body: &[u8]) -> Result<QueryResponse> {
+    let body = body.strip_prefix(KV_PADDING)
+        .ok_or_else(|| OxideVaultError::ServerProtocol("Invalid full stat response".to_string()))?;
+
+    let mut fields = NullTerminated::new(body);
+    let mut response = QueryResponse::default();
+
+    // Key/value pairs, terminated by an empty key
+    while let Some(key) = fields.next() {
+        if key.is_empty() {
+            break;
+        }
+        let value = fields.next().unwrap_or_default();
+
+        match key.as_str() {
+            "hostname" => response.motd = value,
+            "gametype" => response.game_type = value,
+            "version" => response.version = value,
+            "plugins" => (response.server_mod, response.plugins) = parse_plugins(&value),
+            "map" => response.map = value,
+            "numplayers" => response.online = value.parse().unwrap_or(0),
+            "maxplayers" => response.max = value.parse().unwrap_or(0),
+            _ => {}
+        }
+    }
+
+    let rest = fields.remaining().strip_prefix(PLAYER_PADDING)
+        .ok_or_else(|| OxideVaultError::ServerProtocol("Missing player list in full stat response".to_string()))?;
+
+    // Player names, terminated by an empty name
+    response.players = NullTerminated::new(rest)
+        .take_while(|name| !name.is_empty())
+        .collect();
+
+    Ok(response)
+}
+
+/// Split a plugins value ("Server: Plugin A 1.0; Plugin B") into server software and plugins.
+fn parse_plugins(value: &str) -> (Option<String>, Vec<String>) {
+    let value = value.trim();
+    if value.is_empty() {
+        return (None, Vec::new());
+    }
+
+    match value.split_once(':') {
+        Some((server_mod, plugins)) => {
+            let plugins = plugins
+                .split(';')
+                .map(str::trim)
+                .filter(|plugin| !plugin.is_empty())
+                .map(str::to_string)
+                .collect();
+            (Some(server_mod.trim().to_string()), plugins)
+        }
+        None => (Some(value.to_string()), Vec::new()),
+    }
+}
+
+/// Iterator over null-terminated strings in a byte slice.
+struct NullTerminated<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> NullTerminated<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    /// Bytes that have not been consumed yet.
+    fn remaining(&self) -> &'a [u8] {
+        self.data
+    }
+}
+
+impl Iterator for NullTerminated<'_> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        let end = self.data.iter().position(|&b| b == 0)?;
+        let value = String::from_utf8_lossy(&self.data[..end]).into_owned();
+        self.data = &self.data[end + 1..];
+        Some(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a full stat body with the given key/value pairs and players.
+    fn full_stat_body(pairs: &[(&str, &str)], players: &[&str]) -> Vec<u8> {
+        let mut body = KV_PADDING.to_vec();
+        for (key, value) in pairs {
+            body.extend_from_slice(key.as_bytes());
+            body.push(0);
+            body.extend_from_slice(value.as_bytes());
+            body.push(0);
+        }
+        body.push(0);
+        body.extend_from_slice(PLAYER_PADDING);
+        for player in players {
+            body.extend_from_slice(player.as_bytes());
+            body.push(0);
+        }
+        body.push(0);
+        body
+    }
+
+    fn sample_pairs() -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("hostname", "A Minecraft Server"),
+            ("gametype", "SMP"),
+            ("game_id", "MINECRAFT"),
+            ("version", "1.21.4"),
+            ("plugins", "Paper on 1.21.4: WorldEdit 7.3; LuckPerms 5.4"),
+            ("map", "world"),
+            ("numplayers", "3"),
+            ("maxplayers", "20"),
+            ("hostport", "25565"),
+            ("hostip", "0.0.0.0"),
+        ]
+    }
+
+    #[test]
+    fn test_parse_full_stat() {
+        let body = full_stat_body(&sample_pairs(), &["Alice", "Bob", "Charlie"]);
+        let response = parse_full_stat(&body).unwrap();
+
+        assert_eq!(response.motd, "A Minecraft Server");
+        assert_eq!(response.game_type, "SMP");
+        assert_eq!(response.version, "1.21.4");
+        assert_eq!(response.server_mod.as_deref(), Some("Paper on 1.21.4"));
+        assert_eq!(response.plugins, vec!["WorldEdit 7.3", "LuckPerms 5.4"]);
+        assert_eq!(response.map, "world");
+        assert_eq!((response.online, response.max), (3, 20));
+        assert_eq!(response.players, vec!["Alice", "Bob", "Charlie"]);
+    }
+
+    #[test]
+    fn test_parse_full_stat_vanilla_no_players() {
+        let mut pairs = sample_pairs();
+        pairs[4] = ("plugins", "");
+        pairs[6] = ("numplayers", "0");
+        let response = parse_full_stat(&full_stat_body(&pairs, &[])).unwrap();
+
+        assert_eq!(response.server_mod, None);
+        assert!(response.plugins.is_empty());
+        assert!(response.players.is_empty());
+    }
+
+    #[test]
+    fn test_parse_full_stat_malformed() {
+        assert!(parse_full_stat(b"garbage").is_err());
+
+        // Missing player section
+        let mut body = KV_PADDING.to_vec();
+        body.extend_from_slice(b"map\0world\0\0");
+        assert!(parse_full_stat(&body).is_err());
+    }
+
+    #[test]
+    fn test_parse_challenge() {
+        let session_id: u32 = 0x0102_0304;
+        let mut response = vec![TYPE_HANDSHAKE];
+        response.extend_from_slice(&session_id.to_be_bytes());
+        response.extend_from_slice(b"-9513307\0");
+
+        assert_eq!(parse_challenge(&response, session_id).unwrap(), -9_513_307);
+        assert!(parse_challenge(&response, 0x0505_0505).is_err());
+        assert!(parse_challenge(&[TYPE_HANDSHAKE], session_id).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_query_server_with_stub() {
+        let server = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let address = server.local_addr().unwrap().to_string();
+
+        let handle = tokio::spawn(async move {
+            let mut buf = [0u8; 64];
+
+            // Handshake: echo the session ID with a challenge token
+            let (len, peer) = server.recv_from(&mut buf).await.unwrap();
+            assert_eq!(&buf[..3], &[0xFE, 0xFD, TYPE_HANDSHAKE]);
+            let session = buf[3..7].to_vec();
+            assert_eq!(len, 7);
+
+            let mut reply = vec![TYPE_HANDSHAKE];
+            reply.extend_from_slice(&session);
+            reply.extend_from_slice(b"12345\0");
+            server.send_to(&reply, peer).await.unwrap();
+
+            // Full stat request carries the token and padding
+            let (len, peer) = server.recv_from(&mut buf).await.unwrap();
+            assert_eq!(len, 15);
+            assert_eq!(&buf[7..11], &12345i32.to_be_bytes());
+
+            let mut reply = vec![TYPE_STAT];
+            reply.extend_from_slice(&session);
+            reply.extend_from_slice(&full_stat_body(&sample_pairs(), &["Alice", "Bob", "Charlie"]));
+            server.send_to(&reply, peer).await.unwrap();
+        });
+
+        let response = query_server(&address, Duration::from_secs(5)).await.unwrap();
+        handle.await.unwrap();
+
+        assert_eq!(response.players, vec!["Alice", "Bob", "Charlie"]);
+        assert_eq!(response.map, "world");
+    }
+
+    #[tokio::test]
+    async fn test_query_server_timeout() {
+        // A bound socket that never answers
+        let silent = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let address = silent.local_addr().unwrap().to_string();
+
+        let result = query_server(&address, Duration::from_millis(200)).await;
+        assert!(matches!(result, Err(OxideVaultError::ServerProtocol(_))));
+    }
+}
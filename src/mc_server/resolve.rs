@@ -0,0 +1,185 @@
+//! Minecraft server address resolution.
+//!
+//! Resolves a configured "host:port" address the way Minecraft clients do: an
+//! `_minecraft._tcp` SRV record, if present, redirects to another host and port,
+//! which is then resolved to its A/AAAA addresses.
+
+use std::future::Future;
+use std::net::{IpAddr, SocketAddr};
+use hickory_resolver::error::ResolveErrorKind;
+use hickory_resolver::TokioAsyncResolver;
+use crate::error::{OxideVaultError, Result};
+use super::split_host_port;
+
+/// Target of a `_minecraft._tcp` SRV record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SrvTarget {
+    pub host: String,
+    pub port: u16,
+}
+
+/// Result of resolving a server address.
+#[derive(Debug, Clone)]
+pub struct ResolvedServer {
+    /// Host from the configured address
+    pub host: String,
+    /// Port from the configured address
+    pub port: u16,
+    /// SRV record that redirected the address, if any
+    pub srv: Option<SrvTarget>,
+    /// Resolved socket addresses, in resolver order
+    pub addresses: Vec<SocketAddr>,
+}
+
+impl ResolvedServer {
+    /// The address a direct connection would use (the first resolved address).
+    pub fn connect_target(&self) -> Option<SocketAddr> {
+        self.addresses.first().copied()
+    }
+}
+
+/// DNS lookups needed to resolve a server address.
+///
+/// Abstracted so that resolution can be tested without real DNS.
+pub trait AddressResolver {
+    /// Look up the preferred SRV target for `name`, or `None` if there is no record.
+    fn lookup_srv(&self, name: &str) -> impl Future<Output = Result<Option<SrvTarget>>> + Send;
+
+    /// Resolve `host` to socket addresses using `port`.
+    fn lookup_ip(&self, host: &str, port: u16) -> impl Future<Output = Result<Vec<SocketAddr>>> + Send;
+}
+
+/// Resolver using the system DNS configuration.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DnsResolver;
+
+impl AddressResolver for DnsResolver {
+    async fn lookup_srv(&self, name: &str) -> Result<Option<SrvTarget>> {
+        let resolver = TokioAsyncResolver::tokio_from_system_conf()
+            .map_err(|e| OxideVaultError::Network(format!("Failed to load DNS configuration: {}", e)))?;
+
+        let lookup = match resolver.srv_lookup(name).await {
+            Ok(lookup) => lookup,
+            Err(e) if matches!(e.kind(), ResolveErrorKind::NoRecordsFound { .. }) => return Ok(None),
+            Err(e) => return Err(OxideVaultError::Network(format!("SRV lookup failed: {}", e))),
+        };
+
+        // Lowest priority wins; among equals, prefer the highest weight
+        Ok(lookup
+            .iter()
+            .min_by_key(|srv| (srv.priority(), std::cmp::Reverse(srv.weight())))
+            .map(|srv| SrvTarget {
+                host: srv.target().to_utf8().trim_end_matches('.').to_string(),
+                port: srv.port(),
+            }))
+    }
+
+    async fn lookup_ip(&self, host: &str, port: u16) -> Result<Vec<SocketAddr>> {
+        tokio::net::lookup_host((host, port))
+            .await
+            .map(Iterator::collect)
+            .map_err(|e| OxideVaultError::DnsResolution(format!("Failed to resolve address: {}", e)))
+    }
+}
+
+/// Resolve a "host:port" server address.
+///
+/// IP literals skip the SRV lookup. SRV lookup failures are not fatal; the
+/// configured host is resolved directly instead.
+///
+/// # Errors
+///
+/// Returns an error if the address is malformed or resolves to no addresses.
+pub async fn resolve_server<R: AddressResolver>(resolver: &R, address: &str) -> Result<ResolvedServer> {
+    let (host, port) = split_host_port(address)?;
+
+    let srv = if host.parse::<IpAddr>().is_ok() {
+        None
+    } else {
+        resolver
+            .lookup_srv(&format!("_minecraft._tcp.{}", host))
+            .await
+            .unwrap_or(None)
+    };
+
+    let (lookup_host, lookup_port) = match &srv {
+        Some(target) => (target.host.as_str(), target.port),
+        None => (host.as_str(), port),
+    };
+
+    let addresses = resolver.lookup_ip(lookup_host, lookup_port).await?;
+    if addresses.is_empty() {
+        return Err(OxideVaultError::DnsResolution("Could not resolve address".to_string()));
+    }
+
+    Ok(ResolvedServer { host, port, srv, addresses })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Resolver returning fixed records.
+    struct StubResolver {
+        srv: Option<SrvTarget>,
+        addresses: Vec<&'static str>,
+    }
+
+    impl AddressResolver for StubResolver {
+        async fn lookup_srv(&self, _name: &str) -> Result<Option<SrvTarget>> {
+            Ok(self.srv.clone())
+        }
+
+        async fn lookup_ip(&self, _host: &str, port: u16) -> Result<Vec<SocketAddr>> {
+            Ok(self.addresses
+                .iter()
+                .map(|ip| SocketAddr::new(ip.parse().unwrap(), port))
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resolve_server_direct() {
+        let resolver = StubResolver { srv: None, addresses: vec!["203.0.113.5", "2001:db8::5"] };
+        let resolved = resolve_server(&resolver, "mc.example.com:25565").await.unwrap();
+
+        assert_eq!(resolved.host, "mc.example.com");
+        assert!(resolved.srv.is_none());
+        assert_eq!(resolved.addresses.len(), 2);
+        assert_eq!(resolved.connect_target(), Some("203.0.113.5:25565".parse().unwrap()));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_server_follows_srv() {
+        let resolver = StubResolver {
+            srv: Some(SrvTarget { host: "node1.example.net".to_string(), port: 25570 }),
+            addresses: vec!["198.51.100.7"],
+        };
+        let resolved = resolve_server(&resolver, "example.com:25565").await.unwrap();
+
+        assert_eq!(resolved.srv.as_ref().unwrap().host, "node1.example.net");
+        assert_eq!(resolved.connect_target(), Some("198.51.100.7:25570".parse().unwrap()));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_server_ip_literal_skips_srv() {
+        let resolver = StubResolver {
+            srv: Some(SrvTarget { host: "ignored".to_string(), port: 1 }),
+            addresses: vec!["127.0.0.1"],
+        };
+        let resolved = resolve_server(&resolver, "127.0.0.1:25565").await.unwrap();
+
+        assert!(resolved.srv.is_none());
+        assert_eq!(resolved.connect_target(), Some("127.0.0.1:25565".parse().unwrap()));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_server_no_addresses() {
+        let resolver = StubResolver { srv: None, addresses: Vec::new() };
+        assert!(matches!(
+            resolve_server(&resolver, "mc.example.com:25565").await,
+            Err(OxideVaultError::DnsResolution(_))
+        ));
+        assert!(resolve_server(&resolver, "no-port").await.is_err());
+    }
+}
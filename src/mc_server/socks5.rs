@@ -0,0 +1,316 @@
+//! Minimal SOCKS5 client (RFC 1928) for routing server pings through a proxy.
+//!
+//! Only the CONNECT command is supported, with either no authentication or
+//! username/password authentication (RFC 1929).
+
+use std::fmt;
+use std::io::{Read, Write};
+use std::net::{IpAddr, TcpStream, ToSocketAddrs};
+use std::time::Duration;
+use crate::error::{OxideVaultError, Result};
+use super::split_host_port;
+
+const SOCKS_VERSION: u8 = 0x05;
+const AUTH_VERSION: u8 = 0x01;
+const METHOD_NO_AUTH: u8 = 0x00;
+const METHOD_USERNAME_PASSWORD: u8 = 0x02;
+const METHOD_NOT_ACCEPTABLE: u8 = 0xFF;
+const COMMAND_CONNECT: u8 = 0x01;
+const ATYP_IPV4: u8 = 0x01;
+const ATYP_DOMAIN: u8 = 0x03;
+const ATYP_IPV6: u8 = 0x04;
+
+/// A SOCKS5 proxy endpoint, optionally with username/password credentials.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Socks5Proxy {
+    /// Proxy address in "host:port" format
+    pub address: String,
+    /// Optional username and password
+    pub credentials: Option<(String, String)>,
+}
+
+impl fmt::Debug for Socks5Proxy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Never print the proxy password
+        f.debug_struct("Socks5Proxy")
+            .field("address", &self.address)
+            .field("username", &self.credentials.as_ref().map(|(user, _)| user))
+            .finish()
+    }
+}
+
+impl Socks5Proxy {
+    /// Parse a proxy specification of the form `[user:password@]host:port`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `Config` error if the address or credentials are malformed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use oxidevault::mc_server::Socks5Proxy;
+    ///
+    /// let proxy = Socks5Proxy::parse("127.0.0.1:1080").unwrap();
+    /// assert_eq!(proxy.address, "127.0.0.1:1080");
+    /// assert!(proxy.credentials.is_none());
+    ///
+    /// assert!(Socks5Proxy::parse("127.0.0.1").is_err());
+    /// ```
+    pub fn parse(spec: &str) -> Result<Self> {
+        let (credentials, address) = match spec.rsplit_once('@') {
+            Some((auth, address)) => {
+                let (user, password) = auth.split_once(':').ok_or_else(|| OxideVaultError::Config(
+                    "Invalid MC_SOCKS5_PROXY credentials. Expected 'user:password@host:port'.".to_string()
+                ))?;
+                if user.is_empty() || user.len() > 255 || password.len() > 255 {
+                    return Err(OxideVaultError::Config(
+                        "MC_SOCKS5_PROXY username must be 1-255 bytes and password at most 255 bytes.".to_string()
+                    ));
+                }
+                (Some((user.to_string(), password.to_string())), address)
+            }
+            None => (None, spec),
+        };
+
+        split_host_port(address).map_err(|e| OxideVaultError::Config(
+            format!("Invalid MC_SOCKS5_PROXY: {}", e)
+        ))?;
+
+        Ok(Self {
+            address: address.to_string(),
+            credentials,
+        })
+    }
+
+    /// Open a TCP connection to `target_host:target_port` through this proxy.
+    ///
+    /// Hostnames are resolved by the proxy rather than locally.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ProxyAuth` if the proxy rejects authentication, and `Proxy` if the
+    /// proxy is unreachable or refuses to connect to the target.
+    pub fn connect(&self, target_host: &str, target_port: u16, timeout: Duration) -> Result<TcpStream> {
+        let proxy_addr = self.address.to_socket_addrs()
+            .map_err(|e| OxideVaultError::Proxy(format!("Failed to resolve proxy address: {}", e)))?
+            .next()
+            .ok_or_else(|| OxideVaultError::Proxy("Could not resolve proxy address".to_string()))?;
+
+        let mut stream = TcpStream::connect_timeout(&proxy_addr, timeout)
+            .map_err(|e| OxideVaultError::Proxy(format!("Connection to proxy failed: {}", e)))?;
+        stream.set_read_timeout(Some(timeout))?;
+        stream.set_write_timeout(Some(timeout))?;
+
+        self.negotiate_auth(&mut stream)?;
+        send_connect_request(&mut stream, target_host, target_port)?;
+
+        Ok(stream)
+    }
+
+    /// Negotiate an authentication method and authenticate if required.
+    fn negotiate_auth(&self, stream: &mut TcpStream) -> Result<()> {
+        let greeting: &[u8] = if self.credentials.is_some() {
+            &[SOCKS_VERSION, 2, METHOD_NO_AUTH, METHOD_USERNAME_PASSWORD]
+        } else {
+            &[SOCKS_VERSION, 1, METHOD_NO_AUTH]
+        };
+        stream.write_all(greeting).map_err(proxy_io_error)?;
+
+        let mut reply = [0u8; 2];
+        stream.read_exact(&mut reply).map_err(proxy_io_error)?;
+        if reply[0] != SOCKS_VERSION {
+            return Err(OxideVaultError::Proxy(
+                format!("Unexpected SOCKS version in proxy reply: {}", reply[0])
+            ));
+        }
+
+        match (reply[1], &self.credentials) {
+            (METHOD_NO_AUTH, _) => Ok(()),
+            (METHOD_USERNAME_PASSWORD, Some((user, password))) => {
+                let mut request = vec![AUTH_VERSION, user.len() as u8];
+                request.extend_from_slice(user.as_bytes());
+                request.push(password.len() as u8);
+                request.extend_from_slice(password.as_bytes());
+                stream.write_all(&request).map_err(proxy_io_error)?;
+
+                let mut status = [0u8; 2];
+                stream.read_exact(&mut status).map_err(proxy_io_error)?;
+                if status[1] != 0x00 {
+                    return Err(OxideVaultError::ProxyAuth(
+                        "SOCKS5 proxy rejected the configured credentials".to_string()
+                    ));
+                }
+                Ok(())
+            }
+            (METHOD_NOT_ACCEPTABLE, None) => Err(OxideVaultError::ProxyAuth(
+                "SOCKS5 proxy requires authentication but no credentials are configured".to_string()
+            )),
+            (METHOD_NOT_ACCEPTABLE, Some(_)) => Err(OxideVaultError::ProxyAuth(
+                "SOCKS5 proxy does not accept username/password authentication".to_string()
+            )),
+            (method, _) => Err(OxideVaultError::Proxy(
+                format!("SOCKS5 proxy selected an unsupported authentication method: {}", method)
+            )),
+        }
+    }
+}
+
+/// Send a CONNECT request and consume the proxy's reply.
+fn send_connect_request(stream: &mut TcpStream, host: &str, port: u16) -> Result<()> {
+    let mut request = vec![SOCKS_VERSION, COMMAND_CONNECT, 0x00];
+    match host.parse::<IpAddr>() {
+        Ok(IpAddr::V4(ip)) => {
+            request.push(ATYP_IPV4);
+            request.extend_from_slice(&ip.octets());
+        }
+        Ok(IpAddr::V6(ip)) => {
+            request.push(ATYP_IPV6);
+            request.extend_from_slice(&ip.octets());
+        }
+        Err(_) => {
+            if host.len() > 255 {
+                return Err(OxideVaultError::Proxy(
+                    format!("Hostname too long for SOCKS5: {} bytes (max 255)", host.len())
+                ));
+            }
+            request.push(ATYP_DOMAIN);
+            request.push(host.len() as u8);
+            request.extend_from_slice(host.as_bytes());
+        }
+    }
+    request.extend_from_slice(&port.to_be_bytes());
+    stream.write_all(&request).map_err(proxy_io_error)?;
+
+    let mut header = [0u8; 4];
+    stream.read_exact(&mut header).map_err(proxy_io_error)?;
+    if header[1] != 0x00 {
+        return Err(OxideVaultError::Proxy(
+            format!("SOCKS5 proxy refused the connection: {}", reply_message(header[1]))
+        ));
+    }
+
+    // Skip the bound address and port, which we don't need
+    let address_len = match header[3] {
+        ATYP_IPV4 => 4,
+        ATYP_IPV6 => 16,
+        ATYP_DOMAIN => {
+            let mut len = [0u8; 1];
+            stream.read_exact(&mut len).map_err(proxy_io_error)?;
+            len[0] as usize
+        }
+        atyp => {
+            return Err(OxideVaultError::Proxy(
+                format!("SOCKS5 proxy returned an unknown address type: {}", atyp)
+            ));
+        }
+    };
+    let mut bound = vec![0u8; address_len + 2];
+    stream.read_exact(&mut bound).map_err(proxy_io_error)?;
+
+    Ok(())
+}
+
+/// Describe a SOCKS5 reply code.
+fn reply_message(code: u8) -> &'static str {
+    match code {
+        0x01 => "general failure",
+        0x02 => "connection not allowed by ruleset",
+        0x03 => "network unreachable",
+        0x04 => "host unreachable",
+        0x05 => "connection refused",
+        0x06 => "TTL expired",
+        0x07 => "command not supported",
+        0x08 => "address type not supported",
+        _ => "unknown error",
+    }
+}
+
+fn proxy_io_error(err: std::io::Error) -> OxideVaultError {
+    OxideVaultError::Proxy(format!("SOCKS5 handshake failed: {}", err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+    use std::thread;
+
+    #[test]
+    fn test_parse_proxy() {
+        let proxy = Socks5Proxy::parse("proxy.example.com:1080").unwrap();
+        assert_eq!(proxy.address, "proxy.example.com:1080");
+        assert_eq!(proxy.credentials, None);
+
+        let proxy = Socks5Proxy::parse("alice:s3cr:et@127.0.0.1:1080").unwrap();
+        assert_eq!(proxy.address, "127.0.0.1:1080");
+        assert_eq!(proxy.credentials, Some(("alice".to_string(), "s3cr:et".to_string())));
+
+        assert!(Socks5Proxy::parse("proxy.example.com").is_err());
+        assert!(Socks5Proxy::parse("proxy.example.com:abc").is_err());
+        assert!(Socks5Proxy::parse(":1080").is_err());
+        assert!(Socks5Proxy::parse("alice@127.0.0.1:1080").is_err());
+        assert!(Socks5Proxy::parse(":password@127.0.0.1:1080").is_err());
+    }
+
+    #[test]
+    fn test_debug_redacts_password() {
+        let proxy = Socks5Proxy::parse("alice:hunter2@127.0.0.1:1080").unwrap();
+        let debug = format!("{:?}", proxy);
+        assert!(debug.contains("alice"));
+        assert!(!debug.contains("hunter2"));
+    }
+
+    /// Spawn a one-shot SOCKS5 stub that accepts a no-auth CONNECT and returns the request bytes.
+    fn spawn_socks_stub(method: u8) -> (String, thread::JoinHandle<Vec<u8>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut greeting = [0u8; 3];
+            stream.read_exact(&mut greeting).unwrap();
+            stream.write_all(&[SOCKS_VERSION, method]).unwrap();
+            if method != METHOD_NO_AUTH {
+                return Vec::new();
+            }
+
+            // Read the CONNECT request for a domain target
+            let mut header = [0u8; 5];
+            stream.read_exact(&mut header).unwrap();
+            let mut rest = vec![0u8; header[4] as usize + 2];
+            stream.read_exact(&mut rest).unwrap();
+            stream.write_all(&[SOCKS_VERSION, 0x00, 0x00, ATYP_IPV4, 127, 0, 0, 1, 0, 0]).unwrap();
+
+            let mut request = header.to_vec();
+            request.extend_from_slice(&rest);
+            request
+        });
+
+        (address, handle)
+    }
+
+    #[test]
+    fn test_connect_through_stub() {
+        let (address, handle) = spawn_socks_stub(METHOD_NO_AUTH);
+        let proxy = Socks5Proxy::parse(&address).unwrap();
+
+        let result = proxy.connect("mc.example.com", 25565, Duration::from_secs(5));
+        assert!(result.is_ok());
+
+        let request = handle.join().unwrap();
+        assert_eq!(&request[..5], &[SOCKS_VERSION, COMMAND_CONNECT, 0x00, ATYP_DOMAIN, 14]);
+        assert_eq!(&request[5..19], b"mc.example.com");
+        assert_eq!(&request[19..], &25565u16.to_be_bytes());
+    }
+
+    #[test]
+    fn test_connect_auth_required() {
+        let (address, handle) = spawn_socks_stub(METHOD_NOT_ACCEPTABLE);
+        let proxy = Socks5Proxy::parse(&address).unwrap();
+
+        let result = proxy.connect("mc.example.com", 25565, Duration::from_secs(5));
+        handle.join().unwrap();
+        assert!(matches!(result, Err(OxideVaultError::ProxyAuth(_))));
+    }
+}
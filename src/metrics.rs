@@ -0,0 +1,213 @@
+//! Process-wide metrics in the Prometheus text exposition format.
+//!
+//! Counters are recorded through the global [`METRICS`] at the call sites that
+//! dispatch commands, query the Mojang API, and ping Minecraft servers. The
+//! database pool gauges are read from the pool each time metrics are rendered.
+//! They are exposed at `GET /metrics` when the HTTP server is enabled.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// Metrics recorded by the running bot.
+pub static METRICS: Metrics = Metrics::new();
+
+/// Connection counts of a database connection pool.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PoolStats {
+    /// Connections open and waiting to be used
+    pub idle: u32,
+    /// Connections currently checked out
+    pub in_use: u32,
+}
+
+/// Reads the current [`PoolStats`] of the database pool.
+type PoolStatsSource = Box<dyn Fn() -> PoolStats + Send + Sync>;
+
+/// Counters and gauges for the bot, rendered by [`Metrics::render`].
+pub struct Metrics {
+    /// Commands dispatched, by qualified command name
+    commands: Mutex<BTreeMap<String, u64>>,
+    /// Mojang API requests, by HTTP status code (or `error` if none was received)
+    mojang_requests: Mutex<BTreeMap<String, u64>>,
+    /// Server pings that failed after all retries
+    ping_failures: AtomicU64,
+    /// Online player count from the last successful ping, by server address
+    players_online: Mutex<BTreeMap<String, u64>>,
+    /// Source of the database pool gauges, if a pool was registered
+    db_pool: Mutex<Option<PoolStatsSource>>,
+}
+
+impl Metrics {
+    /// Create an empty set of metrics.
+    pub const fn new() -> Self {
+        Self {
+            commands: Mutex::new(BTreeMap::new()),
+            mojang_requests: Mutex::new(BTreeMap::new()),
+            ping_failures: AtomicU64::new(0),
+            players_online: Mutex::new(BTreeMap::new()),
+            db_pool: Mutex::new(None),
+        }
+    }
+
+    /// Count a dispatched command.
+    pub fn record_command(&self, command: &str) {
+        increment(&self.commands, command);
+    }
+
+    /// Count a Mojang API request by its response status (e.g. `200`, `429`, `error`).
+    pub fn record_mojang_request(&self, status: &str) {
+        increment(&self.mojang_requests, status);
+    }
+
+    /// Count a failed server ping.
+    pub fn record_ping_failure(&self) {
+        self.ping_failures.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Set the last-known online player count of a server.
+    pub fn set_players_online(&self, server: &str, online: u64) {
+        lock(&self.players_online).insert(server.to_string(), online);
+    }
+
+    /// Report the database pool's connection counts, as read by `stats` each
+    /// time metrics are rendered.
+    pub fn set_db_pool_source(&self, stats: impl Fn() -> PoolStats + Send + Sync + 'static) {
+        *self.db_pool.lock().unwrap_or_else(|e| e.into_inner()) = Some(Box::new(stats));
+    }
+
+    /// Render all metrics in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+
+        write_labeled(
+            &mut out,
+            "commands_total",
+            "counter",
+            "Slash commands dispatched.",
+            "command",
+            &lock(&self.commands),
+        );
+        write_labeled(
+            &mut out,
+            "mojang_requests_total",
+            "counter",
+            "Mojang API requests by HTTP status.",
+            "status",
+            &lock(&self.mojang_requests),
+        );
+
+        let _ = writeln!(out, "# HELP mc_ping_failures_total Minecraft server pings that failed.");
+        let _ = writeln!(out, "# TYPE mc_ping_failures_total counter");
+        let _ = writeln!(out, "mc_ping_failures_total {}", self.ping_failures.load(Ordering::Relaxed));
+
+        write_labeled(
+            &mut out,
+            "mc_players_online",
+            "gauge",
+            "Online players reported by the last successful ping.",
+            "server",
+            &lock(&self.players_online),
+        );
+
+        if let Some(stats) = self.db_pool.lock().unwrap_or_else(|e| e.into_inner()).as_ref() {
+            let stats = stats();
+            write_gauge(&mut out, "db_pool_connections_idle", "Idle database pool connections.", stats.idle);
+            write_gauge(&mut out, "db_pool_connections_in_use", "Database pool connections in use.", stats.in_use);
+        }
+
+        out
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Lock a metrics map, recovering from a poisoned lock.
+fn lock(map: &Mutex<BTreeMap<String, u64>>) -> std::sync::MutexGuard<'_, BTreeMap<String, u64>> {
+    map.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Add one to the counter for `label`.
+fn increment(map: &Mutex<BTreeMap<String, u64>>, label: &str) {
+    *lock(map).entry(label.to_string()).or_default() += 1;
+}
+
+/// Write a metric with one sample per label value.
+fn write_labeled(out: &mut String, name: &str, kind: &str, help: &str, label: &str, values: &BTreeMap<String, u64>) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+    for (value, count) in values {
+        let _ = writeln!(out, "{}{{{}=\"{}\"}} {}", name, label, escape_label(value), count);
+    }
+}
+
+/// Write an unlabeled gauge.
+fn write_gauge(out: &mut String, name: &str, help: &str, value: u32) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} gauge", name);
+    let _ = writeln!(out, "{} {}", name, value);
+}
+
+/// Escape a label value as required by the exposition format.
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let metrics = Metrics::new();
+        metrics.record_command("online");
+        metrics.record_command("online");
+        metrics.record_command("backup");
+        metrics.record_mojang_request("200");
+        metrics.record_mojang_request("429");
+        metrics.record_ping_failure();
+        metrics.set_players_online("mc.example.com:25565", 7);
+        metrics.set_players_online("mc.example.com:25565", 5);
+
+        let rendered = metrics.render();
+        assert!(rendered.contains("# TYPE commands_total counter\n"));
+        assert!(rendered.contains("commands_total{command=\"online\"} 2\n"));
+        assert!(rendered.contains("commands_total{command=\"backup\"} 1\n"));
+        assert!(rendered.contains("mojang_requests_total{status=\"200\"} 1\n"));
+        assert!(rendered.contains("mojang_requests_total{status=\"429\"} 1\n"));
+        assert!(rendered.contains("mc_ping_failures_total 1\n"));
+        assert!(rendered.contains("# TYPE mc_players_online gauge\n"));
+        assert!(rendered.contains("mc_players_online{server=\"mc.example.com:25565\"} 5\n"));
+    }
+
+    #[test]
+    fn test_render_db_pool() {
+        let metrics = Metrics::new();
+        assert!(!metrics.render().contains("db_pool_"));
+
+        metrics.set_db_pool_source(|| PoolStats { idle: 3, in_use: 7 });
+        let rendered = metrics.render();
+        assert!(rendered.contains("# TYPE db_pool_connections_idle gauge\n"));
+        assert!(rendered.contains("db_pool_connections_idle 3\n"));
+        assert!(rendered.contains("db_pool_connections_in_use 7\n"));
+    }
+
+    #[test]
+    fn test_render_empty() {
+        let rendered = Metrics::new().render();
+        assert!(rendered.contains("# TYPE commands_total counter\n"));
+        assert!(rendered.contains("mc_ping_failures_total 0\n"));
+        assert!(!rendered.contains("commands_total{"));
+    }
+
+    #[test]
+    fn test_escape_label() {
+        assert_eq!(escape_label("plain"), "plain");
+        assert_eq!(escape_label("a\"b\\c\nd"), "a\\\"b\\\\c\\nd");
+    }
+}
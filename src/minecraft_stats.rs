@@ -0,0 +1,123 @@
+//! Vanilla Minecraft player statistics.
+//!
+//! Servers write each player's statistics to `world/stats/<uuid>.json`, grouped
+//! by category (`minecraft:custom`, `minecraft:mined`, ...). This module
+//! flattens those files into [`PlayerStat`] rows.
+
+use crate::database::PlayerStat;
+use crate::error::{OxideVaultError, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+/// Derived stat holding a player's total play time, in seconds.
+pub const PLAYTIME_SECONDS_STAT: &str = "playtime_seconds";
+
+/// Vanilla play time stats, in ticks (`play_one_minute` before 1.17).
+const PLAY_TIME_STATS: [&str; 2] = [
+    "minecraft:custom/minecraft:play_time",
+    "minecraft:custom/minecraft:play_one_minute",
+];
+
+/// Game ticks per second.
+const TICKS_PER_SECOND: i64 = 20;
+
+/// Layout of a vanilla stats file; other top-level keys (e.g. `DataVersion`)
+/// are ignored.
+#[derive(Deserialize)]
+struct StatsFile {
+    stats: BTreeMap<String, BTreeMap<String, i64>>,
+}
+
+/// Parse a vanilla stats file into stats for `mc_uuid`, recorded at `timestamp`.
+///
+/// Each stat is named `<category>/<stat>`, e.g.
+/// `minecraft:custom/minecraft:play_time`, and the result is sorted by name.
+/// When the file has a play time stat, [`PLAYTIME_SECONDS_STAT`] is added
+/// with the play time converted from ticks to seconds.
+///
+/// # Errors
+///
+/// Returns a validation error if the JSON doesn't have the vanilla layout.
+pub fn parse_minecraft_stats(json: &str, mc_uuid: &str, timestamp: i64) -> Result<Vec<PlayerStat>> {
+    let file: StatsFile = serde_json::from_str(json)
+        .map_err(|e| OxideVaultError::Validation(format!("Invalid Minecraft stats file: {}", e)))?;
+
+    let stat = |stat_name: String, stat_value: i64| PlayerStat {
+        mc_uuid: mc_uuid.to_string(),
+        stat_name,
+        stat_value,
+        timestamp,
+    };
+
+    let mut stats: Vec<PlayerStat> = file
+        .stats
+        .into_iter()
+        .flat_map(|(category, values)| {
+            values
+                .into_iter()
+                .map(move |(name, value)| (format!("{}/{}", category, name), value))
+        })
+        .map(|(name, value)| stat(name, value))
+        .collect();
+
+    let play_time = PLAY_TIME_STATS
+        .iter()
+        .find_map(|name| stats.iter().find(|stat| stat.stat_name == *name))
+        .map(|play_time| play_time.stat_value / TICKS_PER_SECOND);
+    if let Some(seconds) = play_time {
+        stats.push(stat(PLAYTIME_SECONDS_STAT.to_string(), seconds));
+    }
+
+    stats.sort_by(|a, b| a.stat_name.cmp(&b.stat_name));
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UUID: &str = "069a79f444e94726a5befca90e38aaf5";
+
+    #[test]
+    fn test_parse_minecraft_stats() {
+        let json = r#"{
+            "stats": {
+                "minecraft:mined": {"minecraft:stone": 120, "minecraft:dirt": 30},
+                "minecraft:custom": {"minecraft:play_time": 72000, "minecraft:deaths": 2}
+            },
+            "DataVersion": 3953
+        }"#;
+
+        let stats = parse_minecraft_stats(json, UUID, 1_000).unwrap();
+        let names: Vec<(&str, i64)> = stats.iter().map(|s| (s.stat_name.as_str(), s.stat_value)).collect();
+        assert_eq!(names, vec![
+            ("minecraft:custom/minecraft:deaths", 2),
+            ("minecraft:custom/minecraft:play_time", 72_000),
+            ("minecraft:mined/minecraft:dirt", 30),
+            ("minecraft:mined/minecraft:stone", 120),
+            (PLAYTIME_SECONDS_STAT, 3_600),
+        ]);
+        assert!(stats.iter().all(|s| s.mc_uuid == UUID && s.timestamp == 1_000));
+    }
+
+    #[test]
+    fn test_parse_minecraft_stats_legacy_play_time() {
+        let json = r#"{"stats": {"minecraft:custom": {"minecraft:play_one_minute": 200}}}"#;
+        let stats = parse_minecraft_stats(json, UUID, 0).unwrap();
+        assert_eq!(stats.last().unwrap().stat_name, PLAYTIME_SECONDS_STAT);
+        assert_eq!(stats.last().unwrap().stat_value, 10);
+
+        let empty = parse_minecraft_stats(r#"{"stats": {}}"#, UUID, 0).unwrap();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_parse_minecraft_stats_invalid() {
+        for json in ["", "[]", r#"{"stat.playOneMinute": 5}"#, r#"{"stats": {"minecraft:custom": {"a": "b"}}}"#] {
+            assert!(matches!(
+                parse_minecraft_stats(json, UUID, 0),
+                Err(OxideVaultError::Validation(_))
+            ));
+        }
+    }
+}
@@ -3,8 +3,16 @@
 //! This module provides functions for interacting with the Mojang API
 //! to retrieve player profile information.
 
+use base64::Engine;
+use md5::{Digest, Md5};
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
 use crate::error::{OxideVaultError, Result};
+use crate::metrics::METRICS;
+use crate::utils::validation::{format_uuid, normalize_uuid, validate_minecraft_username};
 
 /// Player profile information from Mojang API.
 #[derive(Deserialize, Debug, Clone)]
@@ -13,6 +21,190 @@ pub struct MojangProfile {
     pub id: String,
     /// Current player username
     pub name: String,
+    /// Profile properties such as textures (only returned by the session server)
+    #[serde(default)]
+    pub properties: Vec<ProfileProperty>,
+}
+
+/// A profile property from the Mojang session server.
+#[derive(Deserialize, Debug, Clone)]
+pub struct ProfileProperty {
+    /// Property name (e.g. "textures")
+    pub name: String,
+    /// Base64-encoded property value
+    pub value: String,
+    /// Yggdrasil signature, only present when requested
+    #[serde(default)]
+    pub signature: Option<String>,
+}
+
+/// Largest response body accepted from the Mojang API or PlayerDB, in bytes.
+///
+/// Profiles are well under a kilobyte, so anything near this is a broken or
+/// hostile upstream.
+pub const MAX_RESPONSE_SIZE: usize = 64 * 1024;
+
+/// Base URL of the Mojang profile API.
+pub const MOJANG_API_BASE: &str = "https://api.mojang.com";
+
+/// Base URL of the PlayerDB Minecraft API, used as an opt-in fallback for
+/// username lookups.
+pub const PLAYERDB_API_BASE: &str = "https://playerdb.co/api/player/minecraft";
+
+/// Arm model used by a skin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkinModel {
+    /// Four-pixel-wide arms ("Steve")
+    Classic,
+    /// Three-pixel-wide arms ("Alex")
+    Slim,
+}
+
+/// Skin and cape textures of a player.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlayerTextures {
+    /// Skin texture URL, `None` when the player uses a default skin
+    pub skin_url: Option<String>,
+    /// Cape texture URL, if the player has a cape
+    pub cape_url: Option<String>,
+    pub model: SkinModel,
+}
+
+/// Decoded `textures` property payload.
+#[derive(Deserialize)]
+struct TexturesPayload {
+    #[serde(default)]
+    textures: TextureSet,
+}
+
+#[derive(Deserialize, Default)]
+struct TextureSet {
+    #[serde(rename = "SKIN")]
+    skin: Option<Texture>,
+    #[serde(rename = "CAPE")]
+    cape: Option<Texture>,
+}
+
+#[derive(Deserialize)]
+struct Texture {
+    url: String,
+    metadata: Option<TextureMetadata>,
+}
+
+#[derive(Deserialize)]
+struct TextureMetadata {
+    model: Option<String>,
+}
+
+/// Maximum number of usernames accepted by one bulk profile request.
+const BATCH_SIZE: usize = 10;
+
+/// Base URL of the Mojang session server.
+pub const SESSION_SERVER_BASE: &str = "https://sessionserver.mojang.com";
+
+/// Client for the Mojang profile API and session server.
+///
+/// Both base URLs can be overridden, e.g. to point at a mock server in tests
+/// or at a caching proxy. Username lookups can optionally fall back to
+/// PlayerDB when the Mojang API fails (see [`MojangClient::with_fallback_base`]).
+///
+/// # Examples
+///
+/// ```no_run
+/// use oxidevault::mojang::MojangClient;
+///
+/// # async fn example() -> oxidevault::Result<()> {
+/// let client = MojangClient::new(reqwest::Client::new())
+///     .with_api_base("https://mojang-proxy.example.com");
+///
+/// if let Some(profile) = client.fetch_profile("Notch").await? {
+///     println!("UUID: {}", profile.id);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct MojangClient {
+    http: reqwest::Client,
+    api_base: String,
+    session_base: String,
+    /// PlayerDB base URL tried when a username lookup fails, if enabled
+    fallback_base: Option<String>,
+}
+
+impl MojangClient {
+    /// Create a client using the official Mojang endpoints.
+    pub fn new(http: reqwest::Client) -> Self {
+        Self {
+            http,
+            api_base: MOJANG_API_BASE.to_string(),
+            session_base: SESSION_SERVER_BASE.to_string(),
+            fallback_base: None,
+        }
+    }
+
+    /// Use a different base URL for the profile API.
+    pub fn with_api_base(mut self, url: impl Into<String>) -> Self {
+        self.api_base = url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Use a different base URL for the session server.
+    pub fn with_session_base(mut self, url: impl Into<String>) -> Self {
+        self.session_base = url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Fall back to a PlayerDB-compatible API (e.g. [`PLAYERDB_API_BASE`]) when
+    /// a username lookup on the Mojang API fails or is rate limited.
+    pub fn with_fallback_base(mut self, url: impl Into<String>) -> Self {
+        self.fallback_base = Some(url.into().trim_end_matches('/').to_string());
+        self
+    }
+
+    /// Fetch a player profile by username; see [`fetch_profile`].
+    ///
+    /// If a fallback is configured and the Mojang API fails, the lookup is
+    /// retried on PlayerDB. Should that fail too, the Mojang error is returned.
+    pub async fn fetch_profile(&self, name: &str) -> Result<Option<MojangProfile>> {
+        let error = match fetch_profile_from(&self.http, &self.api_base, name).await {
+            Ok(profile) => return Ok(profile),
+            Err(e) => e,
+        };
+
+        let Some(fallback_base) = &self.fallback_base else {
+            return Err(error);
+        };
+
+        tracing::warn!(player = %name, error = %error, "Mojang lookup failed, falling back to PlayerDB");
+        match fetch_playerdb_profile(&self.http, fallback_base, name).await {
+            Ok(profile) => Ok(profile),
+            Err(fallback_error) => {
+                tracing::warn!(player = %name, error = %fallback_error, "PlayerDB lookup failed");
+                Err(error)
+            }
+        }
+    }
+
+    /// Fetch several player profiles in bulk; see [`fetch_profiles_batch`].
+    pub async fn fetch_profiles_batch(&self, names: &[String]) -> Result<Vec<MojangProfile>> {
+        fetch_profiles_batch_from(&self.http, &self.api_base, names).await
+    }
+
+    /// Fetch a player profile by UUID; see [`fetch_profile_by_uuid`].
+    pub async fn fetch_profile_by_uuid(&self, uuid: &str) -> Result<Option<MojangProfile>> {
+        fetch_profile_by_uuid_from(&self.http, &self.session_base, uuid).await
+    }
+
+    /// Fetch a player's skin and cape textures; see [`fetch_textures`].
+    pub async fn fetch_textures(&self, uuid: &str) -> Result<PlayerTextures> {
+        fetch_textures_from(&self.http, &self.session_base, uuid).await
+    }
+
+    /// Resolve a player from a username or UUID; see [`resolve`].
+    pub async fn resolve(&self, input: &str) -> Result<Option<MojangProfile>> {
+        resolve_with(&self.http, &self.api_base, &self.session_base, input).await
+    }
 }
 
 /// Fetch a player profile from the Mojang API.
@@ -46,27 +238,378 @@ pub struct MojangProfile {
 /// # }
 /// ```
 pub async fn fetch_profile(client: &reqwest::Client, name: &str) -> Result<Option<MojangProfile>> {
-    let url = format!("https://api.mojang.com/users/profiles/minecraft/{}", name);
-    let resp = client.get(&url).send().await
-        .map_err(|e| OxideVaultError::MojangApi(format!("Request failed: {}", e)))?;
-
-    if resp.status().is_success() {
-        let profile = resp.json::<MojangProfile>().await
-            .map_err(|e| OxideVaultError::MojangApi(format!("Invalid response: {}", e)))?;
-        Ok(Some(profile))
-    } else if resp.status().as_u16() == 404 {
-        Ok(None)
-    } else {
-        Err(OxideVaultError::MojangApi(
+    fetch_profile_from(client, MOJANG_API_BASE, name).await
+}
+
+async fn fetch_profile_from(client: &reqwest::Client, base_url: &str, name: &str) -> Result<Option<MojangProfile>> {
+    let url = format!("{}/users/profiles/minecraft/{}", base_url, name);
+    fetch_optional_profile(client, &url).await
+}
+
+/// Fetch the profiles of several players in bulk.
+///
+/// Names are sent to the Mojang API in chunks of ten. Names that don't exist
+/// are simply missing from the result, so callers should diff it against the
+/// input if they need to know which ones failed.
+///
+/// # Errors
+///
+/// Returns an error if any request fails or returns an unexpected status code.
+pub async fn fetch_profiles_batch(client: &reqwest::Client, names: &[String]) -> Result<Vec<MojangProfile>> {
+    fetch_profiles_batch_from(client, MOJANG_API_BASE, names).await
+}
+
+async fn fetch_profiles_batch_from(client: &reqwest::Client, base_url: &str, names: &[String]) -> Result<Vec<MojangProfile>> {
+    let url = format!("{}/profiles/minecraft", base_url);
+    let mut profiles = Vec::with_capacity(names.len());
+
+    for chunk in names.chunks(BATCH_SIZE) {
+        let resp = send_counted(client.post(&url).json(chunk)).await?;
+
+        if resp.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(rate_limited(&resp));
+        }
+        if !resp.status().is_success() {
+            return Err(OxideVaultError::MojangApi(
+                format!("API returned error: {}", resp.status())
+            ));
+        }
+
+        let found: Vec<MojangProfile> = read_json(resp).await?;
+        profiles.extend(found);
+    }
+
+    Ok(profiles)
+}
+
+/// Fetch a player profile from the Mojang session server by UUID.
+///
+/// Accepts both dashed and undashed UUIDs.
+///
+/// # Returns
+///
+/// Returns `Some(profile)` if the player exists, `None` if not found.
+///
+/// # Errors
+///
+/// Returns a validation error if `uuid` is not a valid UUID, or an API error if
+/// the request fails or returns an unexpected status code.
+pub async fn fetch_profile_by_uuid(client: &reqwest::Client, uuid: &str) -> Result<Option<MojangProfile>> {
+    fetch_profile_by_uuid_from(client, SESSION_SERVER_BASE, uuid).await
+}
+
+async fn fetch_profile_by_uuid_from(client: &reqwest::Client, base_url: &str, uuid: &str) -> Result<Option<MojangProfile>> {
+    let uuid = normalize_uuid(uuid).ok_or_else(|| OxideVaultError::Validation(
+        format!("Invalid UUID: '{}'", uuid)
+    ))?;
+    let url = format!("{}/session/minecraft/profile/{}", base_url, uuid);
+    fetch_optional_profile(client, &url).await
+}
+
+/// Fetch the skin and cape textures of a player.
+///
+/// Accepts both dashed and undashed UUIDs.
+///
+/// # Errors
+///
+/// Returns an error if the UUID is invalid, the player doesn't exist, or the
+/// profile has no valid `textures` property.
+pub async fn fetch_textures(client: &reqwest::Client, uuid: &str) -> Result<PlayerTextures> {
+    fetch_textures_from(client, SESSION_SERVER_BASE, uuid).await
+}
+
+async fn fetch_textures_from(client: &reqwest::Client, base_url: &str, uuid: &str) -> Result<PlayerTextures> {
+    let profile = fetch_profile_by_uuid_from(client, base_url, uuid).await?
+        .ok_or_else(|| OxideVaultError::MojangApi(format!("No profile found for UUID '{}'", uuid)))?;
+
+    let property = profile.properties.iter()
+        .find(|property| property.name == "textures")
+        .ok_or_else(|| OxideVaultError::MojangApi("Profile has no textures property".to_string()))?;
+
+    decode_textures(&property.value)
+}
+
+/// Decode a base64 `textures` property value.
+fn decode_textures(value: &str) -> Result<PlayerTextures> {
+    let json = base64::engine::general_purpose::STANDARD.decode(value.trim())
+        .map_err(|e| OxideVaultError::MojangApi(format!("Invalid textures encoding: {}", e)))?;
+    let payload: TexturesPayload = serde_json::from_slice(&json)
+        .map_err(|e| OxideVaultError::MojangApi(format!("Invalid textures payload: {}", e)))?;
+
+    let TextureSet { skin, cape } = payload.textures;
+    // Only slim skins carry a model hint
+    let model = match skin.as_ref().and_then(|skin| skin.metadata.as_ref()).and_then(|m| m.model.as_deref()) {
+        Some("slim") => SkinModel::Slim,
+        _ => SkinModel::Classic,
+    };
+
+    Ok(PlayerTextures {
+        skin_url: skin.map(|skin| skin.url),
+        cape_url: cape.map(|cape| cape.url),
+        model,
+    })
+}
+
+/// Send a request to the Mojang API, counting it in the request metrics by status.
+async fn send_counted(request: reqwest::RequestBuilder) -> Result<reqwest::Response> {
+    match request.send().await {
+        Ok(resp) => {
+            METRICS.record_mojang_request(resp.status().as_str());
+            Ok(resp)
+        }
+        Err(e) => {
+            METRICS.record_mojang_request("error");
+            Err(OxideVaultError::MojangApi(format!("Request failed: {}", e)))
+        }
+    }
+}
+
+/// Read a response body of at most [`MAX_RESPONSE_SIZE`] bytes.
+///
+/// Bodies announcing a larger `Content-Length` are rejected without being
+/// read; otherwise reading stops as soon as the limit is exceeded.
+async fn read_limited(mut resp: reqwest::Response) -> Result<Vec<u8>> {
+    let too_large = || OxideVaultError::MojangApi(
+        format!("Response exceeds the {} byte limit", MAX_RESPONSE_SIZE)
+    );
+
+    if resp.content_length().is_some_and(|length| length > MAX_RESPONSE_SIZE as u64) {
+        return Err(too_large());
+    }
+
+    let mut body = Vec::new();
+    while let Some(chunk) = resp.chunk().await
+        .map_err(|e| OxideVaultError::MojangApi(format!("Failed to read response: {}", e)))?
+    {
+        if body.len() + chunk.len() > MAX_RESPONSE_SIZE {
+            return Err(too_large());
+        }
+        body.extend_from_slice(&chunk);
+    }
+
+    Ok(body)
+}
+
+/// Deserialize a JSON response body, read with [`read_limited`].
+async fn read_json<T: DeserializeOwned>(resp: reqwest::Response) -> Result<T> {
+    let body = read_limited(resp).await?;
+    serde_json::from_slice(&body).map_err(|e| OxideVaultError::MojangApi(format!("Invalid response: {}", e)))
+}
+
+/// GET a profile, mapping "no such player" responses to `None`.
+async fn fetch_optional_profile(client: &reqwest::Client, url: &str) -> Result<Option<MojangProfile>> {
+    let resp = send_counted(client.get(url)).await?;
+
+    match resp.status().as_u16() {
+        // The session server answers 204 No Content for unknown UUIDs
+        204 | 404 => Ok(None),
+        429 => Err(rate_limited(&resp)),
+        _ if resp.status().is_success() => {
+            let profile: MojangProfile = read_json(resp).await?;
+            Ok(Some(profile))
+        }
+        _ => Err(OxideVaultError::MojangApi(
             format!("API returned error: {}", resp.status())
-        ))
+        )),
     }
 }
 
+/// Response envelope of the PlayerDB API.
+#[derive(Deserialize)]
+struct PlayerDbResponse {
+    #[serde(default)]
+    code: String,
+    #[serde(default)]
+    success: bool,
+    data: Option<PlayerDbData>,
+}
+
+#[derive(Deserialize)]
+struct PlayerDbData {
+    player: Option<PlayerDbPlayer>,
+}
+
+#[derive(Deserialize)]
+struct PlayerDbPlayer {
+    username: String,
+    /// Undashed UUID
+    raw_id: String,
+}
+
+/// Look up a player by username on a PlayerDB-compatible API.
+///
+/// PlayerDB wraps the profile in its own envelope; the result is mapped into
+/// a [`MojangProfile`] with an undashed UUID and no properties.
+async fn fetch_playerdb_profile(client: &reqwest::Client, base_url: &str, name: &str) -> Result<Option<MojangProfile>> {
+    let url = format!("{}/{}", base_url, name);
+    let resp = client.get(&url).send().await
+        .map_err(|e| OxideVaultError::MojangApi(format!("PlayerDB request failed: {}", e)))?;
+
+    if resp.status().as_u16() == 429 {
+        return Err(rate_limited(&resp));
+    }
+
+    // PlayerDB reports unknown players with an error status and a JSON body
+    let status = resp.status();
+    let body: PlayerDbResponse = serde_json::from_slice(&read_limited(resp).await?)
+        .map_err(|e| OxideVaultError::MojangApi(format!("Invalid PlayerDB response ({}): {}", status, e)))?;
+
+    match body.data.and_then(|data| data.player) {
+        Some(player) if body.success => Ok(Some(MojangProfile {
+            id: normalize_uuid(&player.raw_id).unwrap_or(player.raw_id),
+            name: player.username,
+            properties: Vec::new(),
+        })),
+        _ if body.code == "minecraft.invalid_username" || status.as_u16() == 404 => Ok(None),
+        _ => Err(OxideVaultError::MojangApi(format!("PlayerDB returned error: {} ({})", status, body.code))),
+    }
+}
+
+/// Default time a cached username lookup stays fresh.
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// A cached username lookup result.
+#[derive(Debug, Clone)]
+struct CachedProfile {
+    fetched_at: Instant,
+    /// `None` records that the player doesn't exist
+    profile: Option<MojangProfile>,
+}
+
+/// In-memory cache of username lookups.
+///
+/// Both found and not-found results are cached for the configured TTL.
+/// Expired entries are evicted lazily, when they are next looked up.
+#[derive(Debug)]
+pub struct MojangCache {
+    ttl: Duration,
+    entries: RwLock<HashMap<String, CachedProfile>>,
+}
+
+impl MojangCache {
+    /// Create an empty cache whose entries stay fresh for `ttl`.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Fetch a player profile, using a cached result if it is still fresh.
+    ///
+    /// Usernames are case-insensitive, so lookups are keyed on the lowercase name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the profile has to be fetched and the request fails.
+    /// Errors are not cached.
+    pub async fn fetch_profile_cached(&self, client: &MojangClient, name: &str) -> Result<Option<MojangProfile>> {
+        let key = name.to_lowercase();
+
+        if let Some(cached) = self.entries.read().await.get(&key) {
+            if cached.fetched_at.elapsed() < self.ttl {
+                return Ok(cached.profile.clone());
+            }
+        }
+
+        // Drop the stale entry before fetching so a failed request doesn't leave it behind
+        self.entries.write().await.remove(&key);
+
+        let profile = client.fetch_profile(name).await?;
+        self.entries.write().await.insert(key, CachedProfile {
+            fetched_at: Instant::now(),
+            profile: profile.clone(),
+        });
+
+        Ok(profile)
+    }
+}
+
+impl Default for MojangCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_CACHE_TTL)
+    }
+}
+
+/// Build a rate limit error from a 429 response.
+///
+/// Only the delay-seconds form of `Retry-After` is understood; an HTTP date
+/// is treated as missing.
+fn rate_limited(resp: &reqwest::Response) -> OxideVaultError {
+    let retry_after = resp
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .map(Duration::from_secs);
+
+    OxideVaultError::RateLimited { retry_after }
+}
+
+/// Resolve a player profile from either a username or a UUID.
+///
+/// UUIDs (dashed or undashed) are looked up on the session server and usernames
+/// on the profile API. Input valid as both is treated as a UUID. The returned
+/// profile's `id` is always in normalized undashed, lowercase form.
+///
+/// # Errors
+///
+/// Returns a validation error if the input is neither a valid username nor a UUID,
+/// or an API error if the lookup fails.
+pub async fn resolve(client: &reqwest::Client, input: &str) -> Result<Option<MojangProfile>> {
+    resolve_with(client, MOJANG_API_BASE, SESSION_SERVER_BASE, input).await
+}
+
+async fn resolve_with(
+    client: &reqwest::Client,
+    api_base: &str,
+    session_base: &str,
+    input: &str,
+) -> Result<Option<MojangProfile>> {
+    let input = input.trim();
+
+    let profile = if normalize_uuid(input).is_some() {
+        fetch_profile_by_uuid_from(client, session_base, input).await?
+    } else if validate_minecraft_username(input).is_ok() {
+        fetch_profile_from(client, api_base, input).await?
+    } else {
+        return Err(OxideVaultError::Validation(
+            format!("'{}' is not a valid Minecraft username or UUID", input)
+        ));
+    };
+
+    Ok(profile.map(|mut profile| {
+        if let Some(id) = normalize_uuid(&profile.id) {
+            profile.id = id;
+        }
+        profile
+    }))
+}
+
+/// Compute the UUID an offline-mode server assigns to a username.
+///
+/// This is the version 3 UUID of `"OfflinePlayer:" + name`, hashed without a
+/// namespace as Java's `UUID.nameUUIDFromBytes` does. No network call is made.
+///
+/// # Examples
+///
+/// ```
+/// use oxidevault::mojang::offline_uuid;
+///
+/// assert_eq!(offline_uuid("Notch"), "b50ad385-829d-3141-a216-7e7d7539ba7f");
+/// ```
+pub fn offline_uuid(name: &str) -> String {
+    let mut bytes: [u8; 16] = Md5::digest(format!("OfflinePlayer:{}", name)).into();
+    bytes[6] = (bytes[6] & 0x0f) | 0x30; // Version 3
+    bytes[8] = (bytes[8] & 0x3f) | 0x80; // IETF variant
+
+    let hex: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+    format_uuid(&hex).unwrap_or(hex)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use mockito;
 
     #[tokio::test]
     async fn test_fetch_profile_success() {
@@ -78,16 +621,8 @@ mod tests {
             .create_async()
             .await;
 
-        let client = reqwest::Client::new();
-        let url = format!("{}/users/profiles/minecraft/Notch", server.url());
-        
-        // Make the request to the mock server
-        let resp = client.get(&url).send().await.unwrap();
-        let profile: Option<MojangProfile> = if resp.status().is_success() {
-            Some(resp.json().await.unwrap())
-        } else {
-            None
-        };
+        let client = MojangClient::new(reqwest::Client::new()).with_api_base(server.url());
+        let profile = client.fetch_profile("Notch").await.unwrap();
 
         mock.assert_async().await;
         assert!(profile.is_some());
@@ -96,6 +631,19 @@ mod tests {
         assert_eq!(profile.name, "Notch");
     }
 
+    #[test]
+    fn test_mojang_client_base_urls() {
+        let client = MojangClient::new(reqwest::Client::new());
+        assert_eq!(client.api_base, MOJANG_API_BASE);
+        assert_eq!(client.session_base, SESSION_SERVER_BASE);
+
+        let client = client
+            .with_api_base("http://proxy.local/api/")
+            .with_session_base("http://proxy.local/session");
+        assert_eq!(client.api_base, "http://proxy.local/api");
+        assert_eq!(client.session_base, "http://proxy.local/session");
+    }
+
     #[tokio::test]
     async fn test_fetch_profile_not_found() {
         let mut server = mockito::Server::new_async().await;
@@ -104,17 +652,8 @@ mod tests {
             .create_async()
             .await;
 
-        let client = reqwest::Client::new();
-        let url = format!("{}/users/profiles/minecraft/NonExistentPlayer", server.url());
-        
-        let resp = client.get(&url).send().await.unwrap();
-        let profile: Option<MojangProfile> = if resp.status().is_success() {
-            Some(resp.json().await.unwrap())
-        } else if resp.status().as_u16() == 404 {
-            None
-        } else {
-            panic!("Unexpected status");
-        };
+        let client = MojangClient::new(reqwest::Client::new()).with_api_base(server.url());
+        let profile = client.fetch_profile("NonExistentPlayer").await.unwrap();
 
         mock.assert_async().await;
         assert!(profile.is_none());
@@ -130,14 +669,11 @@ mod tests {
             .create_async()
             .await;
 
-        let client = reqwest::Client::new();
-        let url = format!("{}/users/profiles/minecraft/TestPlayer", server.url());
-        
-        let resp = client.get(&url).send().await.unwrap();
-        let result: std::result::Result<MojangProfile, reqwest::Error> = resp.json().await;
+        let client = MojangClient::new(reqwest::Client::new()).with_api_base(server.url());
+        let result = client.fetch_profile("TestPlayer").await;
 
         mock.assert_async().await;
-        assert!(result.is_err());
+        assert!(matches!(result, Err(OxideVaultError::MojangApi(msg)) if msg.starts_with("Invalid response")));
     }
 
     #[tokio::test]
@@ -148,14 +684,445 @@ mod tests {
             .create_async()
             .await;
 
+        let client = MojangClient::new(reqwest::Client::new()).with_api_base(server.url());
+        let result = client.fetch_profile("ErrorPlayer").await;
+
+        mock.assert_async().await;
+        assert!(matches!(result, Err(OxideVaultError::MojangApi(msg)) if msg.contains("500")));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_profile_by_uuid_with_properties() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server.mock("GET", "/session/minecraft/profile/069a79f444e94726a5befca90e38aaf5")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"id":"069a79f444e94726a5befca90e38aaf5","name":"Notch","properties":[{"name":"textures","value":"eyJ0aW1lc3RhbXAiOjB9"}]}"#)
+            .create_async()
+            .await;
+
+        let client = reqwest::Client::new();
+        let profile = fetch_profile_by_uuid_from(&client, &server.url(), "069A79F4-44E9-4726-A5BE-FCA90E38AAF5")
+            .await
+            .unwrap()
+            .unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(profile.name, "Notch");
+        assert_eq!(profile.properties.len(), 1);
+        assert_eq!(profile.properties[0].name, "textures");
+        assert_eq!(profile.properties[0].value, "eyJ0aW1lc3RhbXAiOjB9");
+        assert_eq!(profile.properties[0].signature, None);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_profile_by_uuid_not_found_and_invalid() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server.mock("GET", "/session/minecraft/profile/00000000000000000000000000000002")
+            .with_status(404)
+            .create_async()
+            .await;
+
+        let client = reqwest::Client::new();
+        let profile = fetch_profile_by_uuid_from(&client, &server.url(), "00000000-0000-0000-0000-000000000002")
+            .await
+            .unwrap();
+        mock.assert_async().await;
+        assert!(profile.is_none());
+
+        let result = fetch_profile_by_uuid_from(&client, &server.url(), "not-a-uuid").await;
+        assert!(matches!(result, Err(OxideVaultError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_profiles_batch_chunks_requests() {
+        let mut server = mockito::Server::new_async().await;
+        let names: Vec<String> = (0..12).map(|i| format!("Player{}", i)).collect();
+
+        let first = server.mock("POST", "/profiles/minecraft")
+            .match_body(mockito::Matcher::Json(serde_json::json!(names[..10])))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"[{"id":"00000000000000000000000000000000","name":"Player0"}]"#)
+            .create_async()
+            .await;
+        let second = server.mock("POST", "/profiles/minecraft")
+            .match_body(mockito::Matcher::Json(serde_json::json!(names[10..])))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"[{"id":"00000000000000000000000000000011","name":"Player11"}]"#)
+            .create_async()
+            .await;
+
+        let client = reqwest::Client::new();
+        let profiles = fetch_profiles_batch_from(&client, &server.url(), &names).await.unwrap();
+
+        first.assert_async().await;
+        second.assert_async().await;
+        let found: Vec<_> = profiles.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(found, vec!["Player0", "Player11"]);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_profiles_batch_empty_and_errors() {
+        let mut server = mockito::Server::new_async().await;
+        let client = reqwest::Client::new();
+
+        // No names, no requests
+        assert!(fetch_profiles_batch_from(&client, &server.url(), &[]).await.unwrap().is_empty());
+
+        let mock = server.mock("POST", "/profiles/minecraft")
+            .with_status(429)
+            .create_async()
+            .await;
+
+        let result = fetch_profiles_batch_from(&client, &server.url(), &["Notch".to_string()]).await;
+        mock.assert_async().await;
+        assert!(matches!(result, Err(OxideVaultError::RateLimited { retry_after: None })));
+    }
+
+    fn encode_textures(json: &str) -> String {
+        base64::engine::general_purpose::STANDARD.encode(json)
+    }
+
+    #[tokio::test]
+    async fn test_fetch_textures() {
+        let textures = encode_textures(
+            r#"{"timestamp":0,"profileName":"Alex","textures":{"SKIN":{"url":"http://textures.minecraft.net/texture/skin","metadata":{"model":"slim"}},"CAPE":{"url":"http://textures.minecraft.net/texture/cape"}}}"#,
+        );
+        let body = format!(
+            r#"{{"id":"6ab4317889fd490597f60f67d9d76fd9","name":"Alex","properties":[{{"name":"textures","value":"{}"}}]}}"#,
+            textures
+        );
+
+        let mut server = mockito::Server::new_async().await;
+        let mock = server.mock("GET", "/session/minecraft/profile/6ab4317889fd490597f60f67d9d76fd9")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(body)
+            .create_async()
+            .await;
+
+        let client = reqwest::Client::new();
+        let textures = fetch_textures_from(&client, &server.url(), "6ab43178-89fd-4905-97f6-0f67d9d76fd9")
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(textures, PlayerTextures {
+            skin_url: Some("http://textures.minecraft.net/texture/skin".to_string()),
+            cape_url: Some("http://textures.minecraft.net/texture/cape".to_string()),
+            model: SkinModel::Slim,
+        });
+    }
+
+    #[test]
+    fn test_decode_textures_defaults() {
+        // Classic skin without a cape
+        let classic = decode_textures(&encode_textures(
+            r#"{"textures":{"SKIN":{"url":"http://textures.minecraft.net/texture/steve"}}}"#,
+        )).unwrap();
+        assert_eq!(classic.model, SkinModel::Classic);
+        assert_eq!(classic.cape_url, None);
+
+        // Default skin
+        let default = decode_textures(&encode_textures(r#"{"textures":{}}"#)).unwrap();
+        assert_eq!(default.skin_url, None);
+
+        assert!(decode_textures("not base64!").is_err());
+        assert!(decode_textures(&encode_textures("not json")).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_textures_missing_property() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server.mock("GET", "/session/minecraft/profile/069a79f444e94726a5befca90e38aaf5")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"id":"069a79f444e94726a5befca90e38aaf5","name":"Notch","properties":[]}"#)
+            .create_async()
+            .await;
+
+        let client = reqwest::Client::new();
+        let result = fetch_textures_from(&client, &server.url(), "069a79f444e94726a5befca90e38aaf5").await;
+
+        mock.assert_async().await;
+        assert!(matches!(result, Err(OxideVaultError::MojangApi(_))));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_profile_rate_limited() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server.mock("GET", "/users/profiles/minecraft/Notch")
+            .with_status(429)
+            .with_header("retry-after", "30")
+            .create_async()
+            .await;
+
+        let client = reqwest::Client::new();
+        let result = fetch_profile_from(&client, &server.url(), "Notch").await;
+
+        mock.assert_async().await;
+        match result {
+            Err(OxideVaultError::RateLimited { retry_after }) => {
+                assert_eq!(retry_after, Some(Duration::from_secs(30)));
+            }
+            other => panic!("Expected RateLimited error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_mojang_cache_reuses_fresh_entries() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server.mock("GET", "/users/profiles/minecraft/Notch")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"id":"069a79f444e94726a5befca90e38aaf5","name":"Notch"}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = MojangClient::new(reqwest::Client::new()).with_api_base(server.url());
+        let cache = MojangCache::default();
+
+        let first = cache.fetch_profile_cached(&client, "Notch").await.unwrap();
+        // Differently cased names share the cache entry
+        let second = cache.fetch_profile_cached(&client, "notch").await.unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(first.unwrap().id, second.unwrap().id);
+        assert_eq!(cache.entries.read().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_mojang_cache_refetches_expired_entries() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server.mock("GET", "/users/profiles/minecraft/Ghost")
+            .with_status(404)
+            .expect(2)
+            .create_async()
+            .await;
+
+        let client = MojangClient::new(reqwest::Client::new()).with_api_base(server.url());
+        let cache = MojangCache::new(Duration::ZERO);
+
+        assert!(cache.fetch_profile_cached(&client, "Ghost").await.unwrap().is_none());
+        assert!(cache.fetch_profile_cached(&client, "Ghost").await.unwrap().is_none());
+
+        mock.assert_async().await;
+        assert_eq!(cache.entries.read().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_mojang_cache_does_not_store_errors() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server.mock("GET", "/users/profiles/minecraft/Flaky")
+            .with_status(500)
+            .create_async()
+            .await;
+
+        let client = MojangClient::new(reqwest::Client::new()).with_api_base(server.url());
+        let cache = MojangCache::default();
+
+        assert!(cache.fetch_profile_cached(&client, "Flaky").await.is_err());
+        mock.assert_async().await;
+        assert_eq!(cache.entries.read().await.len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_username() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server.mock("GET", "/users/profiles/minecraft/Notch")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"id":"069a79f444e94726a5befca90e38aaf5","name":"Notch"}"#)
+            .create_async()
+            .await;
+
         let client = reqwest::Client::new();
-        let url = format!("{}/users/profiles/minecraft/ErrorPlayer", server.url());
-        
-        let resp = client.get(&url).send().await.unwrap();
-        let status = resp.status();
+        let profile = resolve_with(&client, &server.url(), &server.url(), "Notch").await.unwrap();
+
+        mock.assert_async().await;
+        let profile = profile.unwrap();
+        assert_eq!(profile.id, "069a79f444e94726a5befca90e38aaf5");
+        assert_eq!(profile.name, "Notch");
+    }
+
+    #[tokio::test]
+    async fn test_resolve_uuid() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server.mock("GET", "/session/minecraft/profile/069a79f444e94726a5befca90e38aaf5")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"id":"069A79F444E94726A5BEFCA90E38AAF5","name":"Notch","properties":[]}"#)
+            .create_async()
+            .await;
+
+        let client = reqwest::Client::new();
+        let profile = resolve_with(&client, &server.url(), &server.url(), "069a79f4-44e9-4726-a5be-fca90e38aaf5")
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+        let profile = profile.unwrap();
+        assert_eq!(profile.id, "069a79f444e94726a5befca90e38aaf5");
+        assert_eq!(profile.name, "Notch");
+    }
+
+    #[tokio::test]
+    async fn test_resolve_unknown_uuid() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server.mock("GET", "/session/minecraft/profile/00000000000000000000000000000001")
+            .with_status(204)
+            .create_async()
+            .await;
+
+        let client = reqwest::Client::new();
+        let profile = resolve_with(&client, &server.url(), &server.url(), "00000000000000000000000000000001")
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+        assert!(profile.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_invalid_input() {
+        let server = mockito::Server::new_async().await;
+        let client = reqwest::Client::new();
+
+        for input in ["", "Invalid Name", "not-a-uuid-and-too-long"] {
+            let result = resolve_with(&client, &server.url(), &server.url(), input).await;
+            assert!(matches!(result, Err(OxideVaultError::Validation(_))), "input: {:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_offline_uuid() {
+        assert_eq!(offline_uuid("Notch"), "b50ad385-829d-3141-a216-7e7d7539ba7f");
+        assert_eq!(offline_uuid("jeb_"), "a762f560-4fce-3236-812a-b80efff0b62b");
+        // Case matters: offline servers hash the name as typed
+        assert_ne!(offline_uuid("notch"), offline_uuid("Notch"));
+    }
+
+    const PLAYERDB_NOTCH: &str = r#"{"code":"player.found","message":"Successfully found player by given ID.","data":{"player":{"username":"Notch","id":"069a79f4-44e9-4726-a5be-fca90e38aaf5","raw_id":"069a79f444e94726a5befca90e38aaf5","properties":[]}},"success":true}"#;
+
+    #[tokio::test]
+    async fn test_fetch_profile_playerdb_fallback() {
+        let mut mojang = mockito::Server::new_async().await;
+        let mojang_mock = mojang.mock("GET", "/users/profiles/minecraft/Notch")
+            .with_status(429)
+            .create_async()
+            .await;
+        let mut playerdb = mockito::Server::new_async().await;
+        let playerdb_mock = playerdb.mock("GET", "/Notch")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(PLAYERDB_NOTCH)
+            .create_async()
+            .await;
+
+        let client = MojangClient::new(reqwest::Client::new())
+            .with_api_base(mojang.url())
+            .with_fallback_base(playerdb.url());
+        let profile = client.fetch_profile("Notch").await.unwrap().unwrap();
+
+        mojang_mock.assert_async().await;
+        playerdb_mock.assert_async().await;
+        assert_eq!(profile.id, "069a79f444e94726a5befca90e38aaf5");
+        assert_eq!(profile.name, "Notch");
+    }
+
+    #[tokio::test]
+    async fn test_fetch_profile_playerdb_not_found() {
+        let mut mojang = mockito::Server::new_async().await;
+        mojang.mock("GET", "/users/profiles/minecraft/Nobody")
+            .with_status(500)
+            .create_async()
+            .await;
+        let mut playerdb = mockito::Server::new_async().await;
+        playerdb.mock("GET", "/Nobody")
+            .with_status(400)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"code":"minecraft.invalid_username","message":"Invalid username.","data":{},"success":false}"#)
+            .create_async()
+            .await;
+
+        let client = MojangClient::new(reqwest::Client::new())
+            .with_api_base(mojang.url())
+            .with_fallback_base(playerdb.url());
+        assert!(client.fetch_profile("Nobody").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_profile_fallback_failure_returns_mojang_error() {
+        let mut mojang = mockito::Server::new_async().await;
+        mojang.mock("GET", "/users/profiles/minecraft/Notch")
+            .with_status(429)
+            .create_async()
+            .await;
+        let mut playerdb = mockito::Server::new_async().await;
+        playerdb.mock("GET", "/Notch")
+            .with_status(500)
+            .create_async()
+            .await;
+
+        let client = MojangClient::new(reqwest::Client::new())
+            .with_api_base(mojang.url())
+            .with_fallback_base(playerdb.url());
+        let result = client.fetch_profile("Notch").await;
+        assert!(matches!(result, Err(OxideVaultError::RateLimited { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_profile_without_fallback() {
+        let mut mojang = mockito::Server::new_async().await;
+        mojang.mock("GET", "/users/profiles/minecraft/Notch")
+            .with_status(500)
+            .create_async()
+            .await;
+
+        let client = MojangClient::new(reqwest::Client::new()).with_api_base(mojang.url());
+        assert!(matches!(client.fetch_profile("Notch").await, Err(OxideVaultError::MojangApi(_))));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_profile_rejects_oversized_body() {
+        let mut server = mockito::Server::new_async().await;
+        let padding = " ".repeat(MAX_RESPONSE_SIZE);
+        let mock = server.mock("GET", "/users/profiles/minecraft/Notch")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(format!(r#"{{"id":"069a79f444e94726a5befca90e38aaf5","name":"Notch"}}{}"#, padding))
+            .create_async()
+            .await;
+
+        let client = MojangClient::new(reqwest::Client::new()).with_api_base(server.url());
+        let result = client.fetch_profile("Notch").await;
+
+        mock.assert_async().await;
+        assert!(matches!(result, Err(OxideVaultError::MojangApi(msg)) if msg.contains("byte limit")));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_profile_rejects_oversized_stream() {
+        let mut server = mockito::Server::new_async().await;
+        // Chunked, so the size is only discovered while reading
+        let mock = server.mock("GET", "/users/profiles/minecraft/Notch")
+            .with_status(200)
+            .with_chunked_body(|writer| {
+                for _ in 0..(MAX_RESPONSE_SIZE / 1024 + 1) {
+                    writer.write_all(&[b' '; 1024])?;
+                }
+                Ok(())
+            })
+            .create_async()
+            .await;
+
+        let client = MojangClient::new(reqwest::Client::new()).with_api_base(server.url());
+        let result = client.fetch_profile("Notch").await;
 
         mock.assert_async().await;
-        assert!(!status.is_success());
-        assert_eq!(status.as_u16(), 500);
+        assert!(matches!(result, Err(OxideVaultError::MojangApi(msg)) if msg.contains("byte limit")));
     }
 }
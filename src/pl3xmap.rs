@@ -0,0 +1,819 @@
+//! Pl3xmap integration.
+//!
+//! This module builds the JSON structures used to add marker layers to a
+//! Pl3xmap web map, and writes them next to the marker file they collect.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde_json::{json, Value};
+use crate::error::{OxideVaultError, Result};
+
+/// Maximum length of a layer key.
+const MAX_LAYER_KEY_LENGTH: usize = 64;
+
+/// Default maximum radius (in blocks) for drawn shapes.
+pub const DEFAULT_MAX_RADIUS: u32 = 10_000;
+
+/// Limits on the radius of drawn shapes.
+///
+/// A global cap applies to every map scale unless a scale-specific cap is set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RadiusLimits {
+    /// Cap used for scales without a specific limit
+    pub max_radius: u32,
+    /// Scale-specific caps, keyed by map scale
+    pub per_scale: HashMap<u32, u32>,
+}
+
+impl Default for RadiusLimits {
+    fn default() -> Self {
+        Self {
+            max_radius: DEFAULT_MAX_RADIUS,
+            per_scale: HashMap::new(),
+        }
+    }
+}
+
+impl RadiusLimits {
+    /// Build limits from the values of `DRAW_MAX_RADIUS` and
+    /// `DRAW_SCALE_MAX_RADIUS`, using the defaults for missing values.
+    ///
+    /// `DRAW_SCALE_MAX_RADIUS` is a comma-separated list of `scale=radius` pairs,
+    /// e.g. `1=2000,4=10000`. Both are read by [`crate::config::Config`] at
+    /// startup, so malformed values stop the bot before it connects.
+    ///
+    /// # Errors
+    ///
+    /// Returns a configuration error if either value is malformed.
+    pub fn from_values(max_radius: Option<&str>, per_scale: Option<&str>) -> Result<Self> {
+        let max_radius = match max_radius {
+            Some(value) => Self::parse_radius(value, "DRAW_MAX_RADIUS")?,
+            None => DEFAULT_MAX_RADIUS,
+        };
+
+        let per_scale = match per_scale {
+            Some(spec) => Self::parse_per_scale(spec)?,
+            None => HashMap::new(),
+        };
+
+        Ok(Self { max_radius, per_scale })
+    }
+
+    /// Parse a positive radius from an environment variable value.
+    fn parse_radius(value: &str, name: &str) -> Result<u32> {
+        match value.trim().parse::<u32>() {
+            Ok(radius) if radius > 0 => Ok(radius),
+            _ => Err(OxideVaultError::Config(
+                format!("{} must be a positive integer, got: '{}'", name, value)
+            )),
+        }
+    }
+
+    /// Parse a comma-separated list of `scale=radius` pairs.
+    pub fn parse_per_scale(spec: &str) -> Result<HashMap<u32, u32>> {
+        spec.split(',')
+            .map(str::trim)
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let (scale, radius) = pair.split_once('=').ok_or_else(|| OxideVaultError::Config(
+                    format!("Invalid DRAW_SCALE_MAX_RADIUS entry '{}'. Expected 'scale=radius'.", pair)
+                ))?;
+                let scale = scale.trim().parse::<u32>().map_err(|_| OxideVaultError::Config(
+                    format!("Invalid scale in DRAW_SCALE_MAX_RADIUS: '{}'", scale.trim())
+                ))?;
+                Ok((scale, Self::parse_radius(radius, "DRAW_SCALE_MAX_RADIUS")?))
+            })
+            .collect()
+    }
+
+    /// Get the radius cap for a map scale, and whether it is scale-specific.
+    pub fn cap_for(&self, scale: u32) -> (u32, bool) {
+        match self.per_scale.get(&scale) {
+            Some(&cap) => (cap, true),
+            None => (self.max_radius, false),
+        }
+    }
+
+    /// Validate a radius against the cap for a map scale.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error naming the cap that applied if the radius is
+    /// zero or exceeds it.
+    pub fn validate(&self, radius: u32, scale: u32) -> Result<()> {
+        if radius == 0 {
+            return Err(OxideVaultError::Validation(
+                "Radius must be greater than 0".to_string()
+            ));
+        }
+
+        let (cap, scale_specific) = self.cap_for(scale);
+        if radius > cap {
+            let which = if scale_specific {
+                format!("the cap for scale {}", scale)
+            } else {
+                "the global cap".to_string()
+            };
+            return Err(OxideVaultError::Validation(
+                format!("Radius {} exceeds {} of {} blocks", radius, which, cap)
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Validate that a color component is within `0.0..=1.0`.
+fn validate_range_0_to_1(value: f32, name: &str) -> Result<()> {
+    if !(0.0..=1.0).contains(&value) {
+        return Err(OxideVaultError::Validation(
+            format!("{} must be between 0 and 1, got: {}", name, value)
+        ));
+    }
+    Ok(())
+}
+
+/// Validate that a hue is within `0.0..=360.0` degrees.
+fn validate_hue(hue: f32) -> Result<()> {
+    if !(0.0..=360.0).contains(&hue) {
+        return Err(OxideVaultError::Validation(
+            format!("Hue must be between 0 and 360, got: {}", hue)
+        ));
+    }
+    Ok(())
+}
+
+/// Convert an HSL color to RGB.
+///
+/// `h` is the hue in degrees, `s` and `l` the saturation and lightness in
+/// `0.0..=1.0`. Out-of-range inputs are wrapped (hue) or clamped; use
+/// [`hsl_to_argb`] to reject them instead.
+///
+/// # Examples
+///
+/// ```
+/// use oxidevault::pl3xmap::hsl_to_rgb;
+///
+/// assert_eq!(hsl_to_rgb(120.0, 1.0, 0.5), (0, 255, 0));
+/// ```
+pub fn hsl_to_rgb(h: f32, s: f32, l: f32) -> (u8, u8, u8) {
+    let h = h.rem_euclid(360.0);
+    let s = s.clamp(0.0, 1.0);
+    let l = l.clamp(0.0, 1.0);
+
+    let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let sector = h / 60.0;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+    let (r, g, b) = match sector as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+
+    let m = l - chroma / 2.0;
+    let to_byte = |channel: f32| ((channel + m) * 255.0).round().clamp(0.0, 255.0) as u8;
+    (to_byte(r), to_byte(g), to_byte(b))
+}
+
+/// Build an ARGB color (as used by Pl3xmap) from HSL and an opacity.
+///
+/// # Errors
+///
+/// Returns a validation error if the hue is outside `0..=360` or any of
+/// saturation, lightness, or opacity is outside `0..=1`.
+pub fn hsl_to_argb(h: f32, s: f32, l: f32, opacity: f32) -> Result<u32> {
+    validate_hue(h)?;
+    validate_range_0_to_1(s, "Saturation")?;
+    validate_range_0_to_1(l, "Lightness")?;
+    validate_range_0_to_1(opacity, "Opacity")?;
+
+    let (r, g, b) = hsl_to_rgb(h, s, l);
+    let alpha = (opacity * 255.0).round() as u8;
+    Ok(u32::from_be_bytes([alpha, r, g, b]))
+}
+
+/// Default stroke weight (in pixels) of drawn shapes.
+pub const DEFAULT_STROKE_WEIGHT: u32 = 3;
+
+/// Stroke and fill colors of a Pl3xmap marker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pl3xmapColors {
+    /// Outline color (ARGB)
+    pub stroke: u32,
+    /// Fill color (ARGB)
+    pub fill: u32,
+    /// Outline width in pixels
+    pub weight: u32,
+}
+
+impl Pl3xmapColors {
+    /// Create colors with the default stroke weight.
+    pub fn new(stroke: u32, fill: u32) -> Self {
+        Self { stroke, fill, weight: DEFAULT_STROKE_WEIGHT }
+    }
+
+    /// Set the stroke weight.
+    pub fn with_weight(mut self, weight: u32) -> Self {
+        self.weight = weight;
+        self
+    }
+}
+
+/// Opacity (`0.0..=1.0`) of an ARGB color's alpha channel.
+fn argb_opacity(argb: u32) -> f64 {
+    let alpha = (argb >> 24) as f64 / 255.0;
+    (alpha * 100.0).round() / 100.0
+}
+
+/// Build a Pl3xmap circle marker.
+///
+/// Pl3xmap stores colors as signed 32-bit ARGB integers; the opacity of each
+/// color is also given separately for renderers that expect it.
+///
+/// # Arguments
+///
+/// * `x`, `z` - Center of the circle in block coordinates
+/// * `radius` - Radius in blocks
+/// * `scale` - Map scale the shape was drawn for (part of the marker key)
+/// * `colors` - Stroke and fill colors
+pub fn build_circle_marker(x: i32, z: i32, radius: u32, scale: u32, colors: &Pl3xmapColors) -> Value {
+    json!({
+        "type": "circle",
+        "data": {
+            "key": format!("circle_{}_{}_{}_{}", x, z, radius, scale),
+            "center": { "x": x, "z": z },
+            "radius": radius,
+        },
+        "options": {
+            "stroke": {
+                "enabled": true,
+                "weight": colors.weight,
+                "color": colors.stroke as i32,
+                "opacity": argb_opacity(colors.stroke),
+            },
+            "fill": {
+                "enabled": true,
+                "color": colors.fill as i32,
+                "opacity": argb_opacity(colors.fill),
+            },
+        },
+    })
+}
+
+/// The 16 basic CSS color keywords.
+const CSS_COLORS: &[(&str, &str)] = &[
+    ("black", "#000000"),
+    ("silver", "#C0C0C0"),
+    ("gray", "#808080"),
+    ("white", "#FFFFFF"),
+    ("maroon", "#800000"),
+    ("red", "#FF0000"),
+    ("purple", "#800080"),
+    ("fuchsia", "#FF00FF"),
+    ("green", "#008000"),
+    ("lime", "#00FF00"),
+    ("olive", "#808000"),
+    ("yellow", "#FFFF00"),
+    ("navy", "#000080"),
+    ("blue", "#0000FF"),
+    ("teal", "#008080"),
+    ("aqua", "#00FFFF"),
+];
+
+/// Minecraft's 16 chat formatting colors.
+const MINECRAFT_COLORS: &[(&str, &str)] = &[
+    ("black", "#000000"),
+    ("dark_blue", "#0000AA"),
+    ("dark_green", "#00AA00"),
+    ("dark_aqua", "#00AAAA"),
+    ("dark_red", "#AA0000"),
+    ("dark_purple", "#AA00AA"),
+    ("gold", "#FFAA00"),
+    ("gray", "#AAAAAA"),
+    ("dark_gray", "#555555"),
+    ("blue", "#5555FF"),
+    ("green", "#55FF55"),
+    ("aqua", "#55FFFF"),
+    ("red", "#FF5555"),
+    ("light_purple", "#FF55FF"),
+    ("yellow", "#FFFF55"),
+    ("white", "#FFFFFF"),
+];
+
+/// Look up the hex value of a named color (case-insensitive).
+///
+/// Accepts the 16 basic CSS colors and Minecraft's 16 formatting colors.
+/// Names shared by both (e.g. `red`) use the CSS value; prefix them with
+/// `minecraft:` to get the Minecraft one.
+///
+/// # Examples
+///
+/// ```
+/// use oxidevault::pl3xmap::color_name_to_hex;
+///
+/// assert_eq!(color_name_to_hex("Red"), Some("#FF0000"));
+/// assert_eq!(color_name_to_hex("minecraft:red"), Some("#FF5555"));
+/// assert_eq!(color_name_to_hex("gold"), Some("#FFAA00"));
+/// assert_eq!(color_name_to_hex("reddish"), None);
+/// ```
+pub fn color_name_to_hex(name: &str) -> Option<&'static str> {
+    let name = name.trim().to_ascii_lowercase();
+    let lookup = |table: &[(&str, &'static str)], name: &str| {
+        table.iter().find(|(color, _)| *color == name).map(|(_, hex)| *hex)
+    };
+
+    match name.strip_prefix("minecraft:") {
+        Some(name) => lookup(MINECRAFT_COLORS, name),
+        None => lookup(CSS_COLORS, &name).or_else(|| lookup(MINECRAFT_COLORS, &name)),
+    }
+}
+
+/// Parse a color given as a name (see [`color_name_to_hex`]) or a hex string
+/// (see [`parse_hex_to_argb`]) into an ARGB value.
+///
+/// # Errors
+///
+/// Returns a validation error if the color is neither a known name nor a
+/// valid hex color, or the opacity is outside `0..=1`.
+pub fn parse_color_to_argb(color: &str, opacity: f32) -> Result<u32> {
+    if let Some(hex) = color_name_to_hex(color) {
+        return parse_hex_to_argb(hex, opacity);
+    }
+
+    let digits = color.trim().trim_start_matches('#');
+    if !digits.is_empty() && !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(OxideVaultError::Validation(format!(
+            "Unknown color '{}'. Use a hex color like #FF5733 or a name like red or minecraft:gold.",
+            color.trim()
+        )));
+    }
+
+    parse_hex_to_argb(color, opacity)
+}
+
+/// Validate a hex color: 6 (`RRGGBB`) or 8 hex digits, with an optional `#`.
+///
+/// # Errors
+///
+/// Returns a validation error for any other length or non-hex characters.
+pub fn validate_hex_color(hex: &str) -> Result<()> {
+    let digits = hex.trim().trim_start_matches('#');
+
+    if !matches!(digits.len(), 6 | 8) || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(OxideVaultError::Validation(
+            format!("Invalid hex color '{}'. Expected RRGGBB, RRGGBBAA, or #AARRGGBB.", hex.trim())
+        ));
+    }
+
+    Ok(())
+}
+
+/// Parse a hex color into an ARGB value (as used by Pl3xmap).
+///
+/// Six-digit colors (`RRGGBB` or `#RRGGBB`) take their alpha from `opacity`.
+/// Eight-digit colors carry their own alpha: `RRGGBBAA` as copied from
+/// design tools, or `#AARRGGBB` with a leading `#`. The embedded alpha is
+/// multiplied by `opacity`, so pass `1.0` to use it unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use oxidevault::pl3xmap::parse_hex_to_argb;
+///
+/// assert_eq!(parse_hex_to_argb("#FF5733", 1.0).unwrap(), 0xFFFF5733);
+/// assert_eq!(parse_hex_to_argb("FF5733AA", 1.0).unwrap(), 0xAAFF5733);
+/// assert_eq!(parse_hex_to_argb("#80FFFFFF", 1.0).unwrap(), 0x80FFFFFF);
+/// ```
+///
+/// # Errors
+///
+/// Returns a validation error if the color is malformed or the opacity is
+/// outside `0..=1`.
+pub fn parse_hex_to_argb(hex: &str, opacity: f32) -> Result<u32> {
+    validate_hex_color(hex)?;
+    validate_range_0_to_1(opacity, "Opacity")?;
+
+    let hex = hex.trim();
+    let digits = hex.trim_start_matches('#');
+    // Validated above, so every digit parses
+    let value = u32::from_str_radix(digits, 16)
+        .map_err(|_| OxideVaultError::Validation(format!("Invalid hex color '{}'", hex)))?;
+
+    let (alpha, rgb) = match (digits.len(), hex.starts_with('#')) {
+        (6, _) => (255, value),
+        (_, true) => (value >> 24, value & 0x00FF_FFFF),
+        (_, false) => (value & 0xFF, value >> 8),
+    };
+
+    let alpha = (alpha as f32 * opacity).round() as u32;
+    Ok((alpha << 24) | rgb)
+}
+
+/// Validate that a layer key is a safe identifier.
+///
+/// Layer keys double as file names, so they may only contain ASCII letters,
+/// digits, underscores, and dashes, and must be 1 to 64 characters long.
+///
+/// # Examples
+///
+/// ```
+/// use oxidevault::pl3xmap::validate_layer_key;
+///
+/// assert!(validate_layer_key("spawn_regions").is_ok());
+/// assert!(validate_layer_key("../etc/passwd").is_err());
+/// ```
+pub fn validate_layer_key(key: &str) -> Result<()> {
+    if key.is_empty() {
+        return Err(OxideVaultError::Validation(
+            "Layer key cannot be empty".to_string()
+        ));
+    }
+
+    if key.len() > MAX_LAYER_KEY_LENGTH {
+        return Err(OxideVaultError::Validation(
+            format!("Layer key too long: {} characters (max {})", key.len(), MAX_LAYER_KEY_LENGTH)
+        ));
+    }
+
+    if !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+        return Err(OxideVaultError::Validation(
+            "Layer key can only contain letters, numbers, underscores, and dashes".to_string()
+        ));
+    }
+
+    Ok(())
+}
+
+/// Build a Pl3xmap marker layer.
+///
+/// # Arguments
+///
+/// * `name` - Human-readable layer label shown in the map's layer control
+/// * `key` - Unique layer identifier (validated with [`validate_layer_key`])
+/// * `markers` - Marker objects to include in the layer
+///
+/// # Errors
+///
+/// Returns a validation error if the key is not a safe identifier.
+pub fn build_layer(name: &str, key: &str, markers: Vec<Value>) -> Result<Value> {
+    validate_layer_key(key)?;
+
+    Ok(json!({
+        "key": key,
+        "label": name,
+        "updateInterval": 15,
+        "showControls": true,
+        "defaultHidden": false,
+        "priority": 99,
+        "zIndex": 99,
+        "markers": markers,
+    }))
+}
+
+/// Read the JSON marker array stored at `path`.
+///
+/// A missing or empty file is treated as an empty array.
+///
+/// # Errors
+///
+/// Returns an error if the file isn't a JSON array or can't be read.
+pub fn read_markers(path: &Path) -> Result<Vec<Value>> {
+    match fs::read_to_string(path) {
+        Ok(contents) if contents.trim().is_empty() => Ok(Vec::new()),
+        Ok(contents) => match serde_json::from_str::<Value>(&contents) {
+            Ok(Value::Array(markers)) => Ok(markers),
+            _ => Err(OxideVaultError::Validation(
+                format!("Marker file '{}' does not contain a JSON array", path.display())
+            )),
+        },
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Append markers to the JSON marker array stored at `path`.
+///
+/// A missing file is treated as an empty array. The file is rewritten
+/// atomically: the new contents go to a temporary file next to it, which is
+/// then renamed over the original.
+///
+/// # Errors
+///
+/// Returns an error if the existing file isn't a JSON array or the file
+/// can't be read or written.
+pub fn write_markers(path: &Path, markers: &[Value]) -> Result<()> {
+    let mut all_markers = read_markers(path)?;
+    all_markers.extend_from_slice(markers);
+    write_json_atomically(path, &Value::Array(all_markers))
+}
+
+/// Path of the layer file for `key`, next to the marker file at `markers_path`.
+pub fn layer_path(markers_path: &Path, key: &str) -> PathBuf {
+    markers_path.with_file_name(format!("{}.json", key))
+}
+
+/// Collect the markers stored at `markers_path` into a layer (see
+/// [`build_layer`]) and write it to [`layer_path`], replacing any previous
+/// version of the layer.
+///
+/// Returns the layer file's path and the number of markers in the layer.
+///
+/// # Errors
+///
+/// Returns a validation error if the key is not a safe identifier or would
+/// overwrite the marker file itself, or an error if a file can't be read or
+/// written.
+pub fn write_layer(markers_path: &Path, name: &str, key: &str) -> Result<(PathBuf, usize)> {
+    validate_layer_key(key)?;
+
+    let path = layer_path(markers_path, key);
+    if path == markers_path {
+        return Err(OxideVaultError::Validation(
+            format!("Layer key '{}' would overwrite the marker file", key)
+        ));
+    }
+
+    let markers = read_markers(markers_path)?;
+    let count = markers.len();
+    write_json_atomically(&path, &build_layer(name, key, markers)?)?;
+
+    Ok((path, count))
+}
+
+/// Write `value` as pretty-printed JSON to `path` through a temporary file
+/// next to it, so readers never see a partially written file.
+fn write_json_atomically(path: &Path, value: &Value) -> Result<()> {
+    let file_name = path.file_name().ok_or_else(|| OxideVaultError::Validation(
+        format!("Marker path '{}' has no file name", path.display())
+    ))?;
+    let temp_path = path.with_file_name(format!(".{}.tmp", file_name.to_string_lossy()));
+
+    let contents = serde_json::to_string_pretty(value)?;
+    fs::write(&temp_path, contents)?;
+    fs::rename(&temp_path, path).inspect_err(|_| {
+        let _ = fs::remove_file(&temp_path);
+    })?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_layer_key() {
+        assert!(validate_layer_key("regions").is_ok());
+        assert!(validate_layer_key("spawn-area_2").is_ok());
+        assert!(validate_layer_key(&"a".repeat(64)).is_ok());
+
+        assert!(validate_layer_key("").is_err());
+        assert!(validate_layer_key(&"a".repeat(65)).is_err());
+        assert!(validate_layer_key("../escape").is_err());
+        assert!(validate_layer_key("my layer").is_err());
+        assert!(validate_layer_key("layer.json").is_err());
+        assert!(validate_layer_key("dir/layer").is_err());
+    }
+
+    #[test]
+    fn test_hsl_to_rgb_primaries() {
+        assert_eq!(hsl_to_rgb(0.0, 1.0, 0.5), (255, 0, 0));
+        assert_eq!(hsl_to_rgb(120.0, 1.0, 0.5), (0, 255, 0));
+        assert_eq!(hsl_to_rgb(240.0, 1.0, 0.5), (0, 0, 255));
+        assert_eq!(hsl_to_rgb(360.0, 1.0, 0.5), (255, 0, 0));
+        assert_eq!(hsl_to_rgb(60.0, 1.0, 0.5), (255, 255, 0));
+    }
+
+    #[test]
+    fn test_hsl_to_rgb_grayscale() {
+        assert_eq!(hsl_to_rgb(0.0, 0.0, 0.0), (0, 0, 0));
+        assert_eq!(hsl_to_rgb(200.0, 0.0, 1.0), (255, 255, 255));
+        assert_eq!(hsl_to_rgb(90.0, 0.0, 0.5), (128, 128, 128));
+        // Full lightness is white regardless of saturation
+        assert_eq!(hsl_to_rgb(0.0, 1.0, 1.0), (255, 255, 255));
+    }
+
+    #[test]
+    fn test_hsl_to_argb() {
+        assert_eq!(hsl_to_argb(0.0, 1.0, 0.5, 1.0).unwrap(), 0xFFFF0000);
+        assert_eq!(hsl_to_argb(240.0, 1.0, 0.5, 0.5).unwrap(), 0x800000FF);
+        assert_eq!(hsl_to_argb(0.0, 0.0, 0.0, 0.0).unwrap(), 0x00000000);
+
+        assert!(hsl_to_argb(-1.0, 1.0, 0.5, 1.0).is_err());
+        assert!(hsl_to_argb(361.0, 1.0, 0.5, 1.0).is_err());
+        assert!(hsl_to_argb(0.0, 1.5, 0.5, 1.0).is_err());
+        assert!(hsl_to_argb(0.0, 1.0, -0.1, 1.0).is_err());
+        assert!(hsl_to_argb(0.0, 1.0, 0.5, 2.0).is_err());
+        assert!(hsl_to_argb(f32::NAN, 1.0, 0.5, 1.0).is_err());
+    }
+
+    #[test]
+    fn test_color_name_to_hex() {
+        assert_eq!(color_name_to_hex("red"), Some("#FF0000"));
+        assert_eq!(color_name_to_hex(" NAVY "), Some("#000080"));
+        assert_eq!(color_name_to_hex("minecraft:gold"), Some("#FFAA00"));
+        assert_eq!(color_name_to_hex("Minecraft:Dark_Purple"), Some("#AA00AA"));
+        assert_eq!(color_name_to_hex("light_purple"), Some("#FF55FF"));
+        // CSS wins for shared names unless the Minecraft prefix is used
+        assert_eq!(color_name_to_hex("gray"), Some("#808080"));
+        assert_eq!(color_name_to_hex("minecraft:gray"), Some("#AAAAAA"));
+
+        assert_eq!(color_name_to_hex("reddish"), None);
+        assert_eq!(color_name_to_hex("minecraft:teal"), None);
+        assert_eq!(color_name_to_hex(""), None);
+        for table in [CSS_COLORS, MINECRAFT_COLORS] {
+            assert_eq!(table.len(), 16);
+            assert!(table.iter().all(|(_, hex)| validate_hex_color(hex).is_ok()));
+        }
+    }
+
+    #[test]
+    fn test_parse_color_to_argb() {
+        assert_eq!(parse_color_to_argb("gold", 1.0).unwrap(), 0xFFFFAA00);
+        assert_eq!(parse_color_to_argb("#3388FF", 1.0).unwrap(), 0xFF3388FF);
+        assert_eq!(parse_color_to_argb("minecraft:aqua", 0.5).unwrap(), 0x8055FFFF);
+
+        match parse_color_to_argb("reddish", 1.0) {
+            Err(OxideVaultError::Validation(msg)) => assert!(msg.contains("Unknown color 'reddish'")),
+            other => panic!("Expected validation error, got {:?}", other),
+        }
+        match parse_color_to_argb("#FFF", 1.0) {
+            Err(OxideVaultError::Validation(msg)) => assert!(msg.contains("Invalid hex color")),
+            other => panic!("Expected validation error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_validate_hex_color() {
+        assert!(validate_hex_color("FF5733").is_ok());
+        assert!(validate_hex_color("#ff5733").is_ok());
+        assert!(validate_hex_color("FF5733AA").is_ok());
+        assert!(validate_hex_color("#80FFFFFF").is_ok());
+
+        assert!(validate_hex_color("").is_err());
+        assert!(validate_hex_color("#FFF").is_err());
+        assert!(validate_hex_color("FF5733A").is_err());
+        assert!(validate_hex_color("GG5733").is_err());
+        assert!(validate_hex_color("+F5733").is_err());
+    }
+
+    #[test]
+    fn test_parse_hex_to_argb() {
+        // Six digits take the separate opacity
+        assert_eq!(parse_hex_to_argb("FF5733", 1.0).unwrap(), 0xFFFF5733);
+        assert_eq!(parse_hex_to_argb("#FF5733", 0.5).unwrap(), 0x80FF5733);
+
+        // Eight digits carry their own alpha
+        assert_eq!(parse_hex_to_argb("FF5733AA", 1.0).unwrap(), 0xAAFF5733);
+        assert_eq!(parse_hex_to_argb("#80FFFFFF", 1.0).unwrap(), 0x80FFFFFF);
+        assert_eq!(parse_hex_to_argb("FF5733FF", 0.5).unwrap(), 0x80FF5733);
+        assert_eq!(parse_hex_to_argb("#00FFFFFF", 1.0).unwrap(), 0x00FFFFFF);
+
+        assert!(parse_hex_to_argb("FF57", 1.0).is_err());
+        assert!(parse_hex_to_argb("FF5733", 1.5).is_err());
+    }
+
+    #[test]
+    fn test_build_circle_marker() {
+        let colors = Pl3xmapColors::new(0xFFFF0000, 0x800000FF).with_weight(2);
+        let marker = build_circle_marker(100, -250, 64, 4, &colors);
+
+        assert_eq!(marker["type"], "circle");
+        assert_eq!(marker["data"]["key"], "circle_100_-250_64_4");
+        assert_eq!(marker["data"]["center"], json!({"x": 100, "z": -250}));
+        assert_eq!(marker["data"]["radius"], 64);
+
+        let stroke = &marker["options"]["stroke"];
+        assert_eq!(stroke["weight"], 2);
+        assert_eq!(stroke["color"], 0xFFFF0000u32 as i32);
+        assert_eq!(stroke["opacity"], 1.0);
+
+        let fill = &marker["options"]["fill"];
+        assert_eq!(fill["color"], 0x800000FFu32 as i32);
+        assert_eq!(fill["opacity"], 0.5);
+    }
+
+    #[test]
+    fn test_write_markers_appends() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("markers.json");
+
+        write_markers(&path, &[json!({"type": "circle", "n": 1})]).unwrap();
+        write_markers(&path, &[json!({"type": "circle", "n": 2}), json!({"type": "circle", "n": 3})]).unwrap();
+
+        let stored: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        let numbers: Vec<_> = stored.as_array().unwrap().iter().map(|m| m["n"].as_i64().unwrap()).collect();
+        assert_eq!(numbers, vec![1, 2, 3]);
+
+        // No temporary file is left behind
+        assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_write_markers_rejects_non_array() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("markers.json");
+        fs::write(&path, r#"{"not": "an array"}"#).unwrap();
+
+        assert!(write_markers(&path, &[json!({})]).is_err());
+        // The existing file is left untouched
+        assert_eq!(fs::read_to_string(&path).unwrap(), r#"{"not": "an array"}"#);
+    }
+
+    #[test]
+    fn test_build_layer() {
+        let markers = vec![json!({"type": "circle"}), json!({"type": "circle"})];
+        let layer = build_layer("Spawn Regions", "spawn_regions", markers).unwrap();
+
+        assert_eq!(layer["key"], "spawn_regions");
+        assert_eq!(layer["label"], "Spawn Regions");
+        assert_eq!(layer["showControls"], true);
+        assert_eq!(layer["defaultHidden"], false);
+        assert_eq!(layer["markers"].as_array().unwrap().len(), 2);
+        assert_eq!(layer["markers"][0]["type"], "circle");
+    }
+
+    fn limits() -> RadiusLimits {
+        RadiusLimits {
+            max_radius: 5_000,
+            per_scale: RadiusLimits::parse_per_scale("1=2000, 4=20000").unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_parse_per_scale() {
+        assert!(RadiusLimits::parse_per_scale("").unwrap().is_empty());
+        assert_eq!(limits().per_scale.get(&1), Some(&2_000));
+        assert_eq!(limits().per_scale.get(&4), Some(&20_000));
+
+        assert!(RadiusLimits::parse_per_scale("1").is_err());
+        assert!(RadiusLimits::parse_per_scale("x=100").is_err());
+        assert!(RadiusLimits::parse_per_scale("1=0").is_err());
+    }
+
+    #[test]
+    fn test_radius_cap_for() {
+        assert_eq!(RadiusLimits::default().cap_for(1), (DEFAULT_MAX_RADIUS, false));
+        assert_eq!(limits().cap_for(1), (2_000, true));
+        assert_eq!(limits().cap_for(4), (20_000, true));
+        assert_eq!(limits().cap_for(2), (5_000, false));
+    }
+
+    #[test]
+    fn test_validate_radius() {
+        let limits = limits();
+
+        // Global cap applies to scales without a specific limit
+        assert!(limits.validate(5_000, 2).is_ok());
+        let err = limits.validate(5_001, 2).unwrap_err();
+        assert!(err.to_string().contains("the global cap of 5000 blocks"));
+
+        // Scale-specific caps can be lower or higher than the global one
+        assert!(limits.validate(2_000, 1).is_ok());
+        let err = limits.validate(2_001, 1).unwrap_err();
+        assert!(err.to_string().contains("the cap for scale 1 of 2000 blocks"));
+        assert!(limits.validate(15_000, 4).is_ok());
+
+        assert!(matches!(limits.validate(0, 2), Err(OxideVaultError::Validation(_))));
+    }
+
+    #[test]
+    fn test_build_layer_invalid_key() {
+        let result = build_layer("Bad", "../bad", Vec::new());
+        assert!(matches!(result, Err(OxideVaultError::Validation(_))));
+    }
+
+    #[test]
+    fn test_write_layer() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let markers_path = temp_dir.path().join("markers.json");
+        fs::write(&markers_path, r#"[{"type": "circle"}, {"type": "circle"}]"#).unwrap();
+
+        let (path, count) = write_layer(&markers_path, "Spawn Regions", "spawn_regions").unwrap();
+        assert_eq!(path, temp_dir.path().join("spawn_regions.json"));
+        assert_eq!(count, 2);
+
+        let layer: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(layer["key"], "spawn_regions");
+        assert_eq!(layer["markers"].as_array().unwrap().len(), 2);
+
+        // Unsafe keys and the marker file's own name are refused
+        assert!(write_layer(&markers_path, "Escape", "../escape").is_err());
+        assert!(write_layer(&markers_path, "Markers", "markers").is_err());
+        assert_eq!(read_markers(&markers_path).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_read_markers() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("markers.json");
+        assert!(read_markers(&path).unwrap().is_empty());
+
+        fs::write(&path, r#"{"not": "an array"}"#).unwrap();
+        assert!(matches!(read_markers(&path), Err(OxideVaultError::Validation(_))));
+    }
+}
@@ -0,0 +1,192 @@
+//! Configuration reloading on SIGHUP.
+//!
+//! Reloading reads the configuration again with [`Config::load`] and swaps in
+//! the new [`LiveSettings`], which take effect for the next command:
+//!
+//! - `MC_SERVER_ADDRESS` and `MC_SERVERS`
+//! - `BACKUP_FOLDER`, `BACKUP_PUBLISH_ROOT`, `BACKUP_PUBLIC_BASE_URL` and `BACKUP_EXTENSIONS`
+//! - the backup cooldowns and `BACKUP_LINK_TTL_SECS`
+//! - `DRAW_MAX_RADIUS` and `DRAW_SCALE_MAX_RADIUS`
+//! - `STATUS_CACHE_TTL_SECS`
+//!
+//! Every other setting (the Discord token, database path, HTTP server, Mojang
+//! endpoints, ping settings, ...) only changes on restart; a warning lists
+//! any of them that differ. The background status poller switches to a new
+//! `MC_SERVER_ADDRESS` on its next poll.
+//!
+//! Environment variables can't change in a running process, so in practice
+//! only edits to the `OXIDEVAULT_CONFIG` file are picked up. If the new
+//! configuration is invalid, the current settings are kept.
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::types::{LiveSettings, LiveSettingsHandle};
+use std::sync::Arc;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+/// Names of settings that differ between `old` and `new` but need a restart
+/// to take effect.
+pub fn restart_required_changes(old: &Config, new: &Config) -> Vec<&'static str> {
+    let checks = [
+        ("DISCORD_TOKEN", old.discord_token != new.discord_token),
+        ("DB_PATH", old.db_path != new.db_path),
+        ("DB_POOL_TIMEOUT_SECS", old.db_pool_timeout != new.db_pool_timeout),
+        ("MC_SOCKS5_PROXY", old.mc_socks5_proxy != new.mc_socks5_proxy),
+        ("MC_QUERY_ENABLED", old.mc_query_enabled != new.mc_query_enabled),
+        ("ADMIN_USER_IDS", old.admin_user_ids != new.admin_user_ids),
+        ("DISABLED_COMMANDS", old.disabled_commands != new.disabled_commands),
+        ("MAX_PLAYER_SAMPLE", old.max_player_sample != new.max_player_sample),
+        ("PING_LOG_PATH", old.ping_log_path != new.ping_log_path),
+        ("MC_PING_TIMEOUT_SECS", old.ping_timeout != new.ping_timeout),
+        ("PING_RETRIES", old.ping_retries != new.ping_retries),
+        ("STATUS_POLL_INTERVAL_SECS", old.status_poll_interval != new.status_poll_interval),
+        ("MOJANG_CACHE_TTL_SECS", old.mojang_cache_ttl != new.mojang_cache_ttl),
+        ("MOJANG_API_URL", old.mojang_api_url != new.mojang_api_url),
+        ("MOJANG_SESSION_URL", old.mojang_session_url != new.mojang_session_url),
+        ("PLAYERDB_FALLBACK", old.playerdb_fallback != new.playerdb_fallback),
+        ("PL3XMAP_MARKERS_PATH", old.pl3xmap_markers_path != new.pl3xmap_markers_path),
+        ("MINECRAFT_STATS_DIR", old.minecraft_stats_dir != new.minecraft_stats_dir),
+        ("HTTP_BIND_ADDRESS", old.http_bind_address != new.http_bind_address),
+        ("HTTP_RATE_LIMIT_BURST", old.http_rate_limit_burst != new.http_rate_limit_burst),
+        ("HTTP_RATE_LIMIT_PER_SEC", old.http_rate_limit_per_sec != new.http_rate_limit_per_sec),
+        ("HTTP_RATE_LIMIT_EXEMPT_LOOPBACK", old.http_rate_limit_exempt_loopback != new.http_rate_limit_exempt_loopback),
+    ];
+
+    checks.into_iter().filter(|(_, changed)| *changed).map(|(name, _)| name).collect()
+}
+
+/// Load a new configuration with `load` and apply its live settings.
+///
+/// Returns the new configuration, or the load error with `settings` left
+/// untouched.
+pub fn reload_with(
+    settings: &LiveSettingsHandle,
+    current: &Config,
+    load: impl FnOnce() -> Result<Config>,
+) -> Result<Config> {
+    let config = load()?;
+
+    let pending = restart_required_changes(current, &config);
+    if !pending.is_empty() {
+        tracing::warn!(settings = ?pending, "Some changed settings only take effect after a restart");
+    }
+
+    settings.replace(LiveSettings::from_config(&config));
+    Ok(config)
+}
+
+/// Spawn a task reloading the configuration on every SIGHUP until `shutdown`
+/// becomes `true` (or its sender is dropped).
+///
+/// Does nothing on platforms without SIGHUP.
+pub fn spawn_reload_listener(
+    settings: Arc<LiveSettingsHandle>,
+    config: Config,
+    mut shutdown: watch::Receiver<bool>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        #[cfg(unix)]
+        {
+            use tokio::signal::unix::{signal, SignalKind};
+
+            let mut hangup = match signal(SignalKind::hangup()) {
+                Ok(hangup) => hangup,
+                Err(e) => {
+                    tracing::warn!(error = %e, "Failed to listen for SIGHUP, config reload is disabled");
+                    return;
+                }
+            };
+
+            let mut current = config;
+            loop {
+                tokio::select! {
+                    _ = hangup.recv() => {
+                        tracing::info!("SIGHUP received, reloading configuration");
+                        match reload_with(&settings, &current, Config::load) {
+                            Ok(config) => {
+                                current = config;
+                                tracing::info!("Configuration reloaded");
+                            }
+                            Err(e) => tracing::error!(error = %e, "Failed to reload configuration, keeping the current one"),
+                        }
+                    }
+                    changed = shutdown.changed() => {
+                        if changed.is_err() || *shutdown.borrow() {
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+
+        #[cfg(not(unix))]
+        {
+            let _ = (settings, config, &mut shutdown);
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::OxideVaultError;
+    use std::fs;
+    use std::path::Path;
+
+    /// Well-formed but fake bot token.
+    const FAKE_TOKEN: &str = "MTIzNDU2Nzg5MDEyMzQ1Njc4OQ.GaBcDe.abcdefghijklmnopqrstuvwxyz_-0123456789";
+
+    fn load_config(dir: &Path, server: &str, db_path: &str) -> Config {
+        let path = dir.join("oxidevault.toml");
+        fs::write(&path, format!(
+            "discord_token = \"{}\"\n\
+             db_path = \"{}\"\n\
+             mc_server_address = \"{}\"\n\
+             backup_folder = \"{}\"\n",
+            FAKE_TOKEN,
+            db_path,
+            server,
+            dir.display()
+        )).unwrap();
+        Config::from_file(&path).unwrap()
+    }
+
+    #[test]
+    fn test_reload_applies_live_settings() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let initial = load_config(temp_dir.path(), "old.example.com:25565", "bot.db");
+        let settings = LiveSettingsHandle::new(LiveSettings::from_config(&initial));
+
+        let snapshot = settings.current();
+        let reloaded = reload_with(&settings, &initial, || {
+            Ok(load_config(temp_dir.path(), "new.example.com:25565", "other.db"))
+        }).unwrap();
+
+        assert_eq!(settings.current().mc_server_address, "new.example.com:25565");
+        assert!(restart_required_changes(&initial, &reloaded).contains(&"DB_PATH"));
+        // Snapshots taken before the reload are unaffected
+        assert_eq!(snapshot.mc_server_address, "old.example.com:25565");
+    }
+
+    #[test]
+    fn test_reload_failure_keeps_settings() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let initial = load_config(temp_dir.path(), "old.example.com:25565", "bot.db");
+        let settings = LiveSettingsHandle::new(LiveSettings::from_config(&initial));
+
+        let result = reload_with(&settings, &initial, || {
+            Err(OxideVaultError::Config("Invalid MC_SERVER_ADDRESS".to_string()))
+        });
+
+        assert!(result.is_err());
+        assert_eq!(settings.current().mc_server_address, "old.example.com:25565");
+    }
+
+    #[test]
+    fn test_restart_required_changes_none() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config = load_config(temp_dir.path(), "old.example.com:25565", "bot.db");
+        assert!(restart_required_changes(&config, &config.clone()).is_empty());
+    }
+}
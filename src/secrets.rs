@@ -0,0 +1,97 @@
+//! Secret retrieval from external backends.
+//!
+//! Lets sensitive values such as the Discord token come from a secret store
+//! instead of plaintext environment variables or files.
+
+use std::process::Command;
+use crate::error::{OxideVaultError, Result};
+
+/// A backend that can produce a secret value.
+pub trait SecretProvider {
+    /// Fetch the secret.
+    fn fetch_secret(&self) -> Result<String>;
+}
+
+/// Provider that runs a shell command and uses its standard output as the secret.
+///
+/// # Examples
+///
+/// ```no_run
+/// use oxidevault::secrets::{CommandSecretProvider, SecretProvider};
+///
+/// let token = CommandSecretProvider::new("pass show discord").fetch_secret()?;
+/// # Ok::<(), oxidevault::OxideVaultError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct CommandSecretProvider {
+    command: String,
+}
+
+impl CommandSecretProvider {
+    /// Create a provider running `command` through `sh -c`.
+    pub fn new(command: impl Into<String>) -> Self {
+        Self { command: command.into() }
+    }
+}
+
+impl SecretProvider for CommandSecretProvider {
+    fn fetch_secret(&self) -> Result<String> {
+        let output = Command::new("sh")
+            .arg("-c")
+            .arg(&self.command)
+            .output()
+            .map_err(|e| OxideVaultError::Config(
+                format!("Failed to run secret command '{}': {}", self.command, e)
+            ))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(OxideVaultError::Config(format!(
+                "Secret command '{}' exited with {}: {}",
+                self.command,
+                output.status,
+                stderr.trim()
+            )));
+        }
+
+        let secret = String::from_utf8(output.stdout)
+            .map_err(|_| OxideVaultError::Config(
+                format!("Secret command '{}' produced non-UTF-8 output", self.command)
+            ))?
+            .trim_end()
+            .to_string();
+
+        if secret.is_empty() {
+            return Err(OxideVaultError::Config(
+                format!("Secret command '{}' produced no output", self.command)
+            ));
+        }
+
+        Ok(secret)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_provider_trims_output() {
+        let provider = CommandSecretProvider::new("echo 'my-secret-token'");
+        assert_eq!(provider.fetch_secret().unwrap(), "my-secret-token");
+
+        let provider = CommandSecretProvider::new("printf 'token  \\n\\n'");
+        assert_eq!(provider.fetch_secret().unwrap(), "token");
+    }
+
+    #[test]
+    fn test_command_provider_failures() {
+        let provider = CommandSecretProvider::new("echo oops >&2; exit 3");
+        let err = provider.fetch_secret().unwrap_err();
+        assert!(matches!(err, OxideVaultError::Config(_)));
+        assert!(err.to_string().contains("oops"));
+
+        let provider = CommandSecretProvider::new("true");
+        assert!(matches!(provider.fetch_secret(), Err(OxideVaultError::Config(_))));
+    }
+}
@@ -0,0 +1,177 @@
+//! Startup self-test.
+//!
+//! Validates configuration, the database, and Minecraft server connectivity
+//! without connecting to Discord. Run with `oxidevault --check`.
+
+use crate::config::Config;
+use crate::database;
+use crate::error::Result;
+use crate::mc_server::{PingStatusSource, StatusSource};
+
+/// Outcome of a single self-test step.
+#[derive(Debug)]
+pub struct CheckResult {
+    /// Name of the step
+    pub name: &'static str,
+    /// Success detail, or the error that made the step fail
+    pub result: Result<String>,
+}
+
+/// Results of all self-test steps, in the order they ran.
+#[derive(Debug, Default)]
+pub struct SelfCheckReport {
+    pub checks: Vec<CheckResult>,
+}
+
+impl SelfCheckReport {
+    /// Whether every step passed.
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(|check| check.result.is_ok())
+    }
+
+    /// Exit code for the report: `0` if all steps passed, otherwise the code
+    /// of the first failing step's error.
+    pub fn exit_code(&self) -> i32 {
+        self.checks
+            .iter()
+            .find_map(|check| check.result.as_ref().err())
+            .map_or(0, |e| e.exit_code())
+    }
+
+    /// Render the report as human-readable lines.
+    pub fn render(&self) -> String {
+        let mut lines: Vec<String> = self.checks
+            .iter()
+            .map(|check| match &check.result {
+                Ok(detail) => format!("✅ {}: {}", check.name, detail),
+                Err(e) => format!("❌ {}: {}", check.name, e),
+            })
+            .collect();
+
+        lines.push(if self.passed() {
+            "Self-test passed.".to_string()
+        } else {
+            "Self-test failed.".to_string()
+        });
+
+        lines.join("\n")
+    }
+}
+
+/// Check the database and server connectivity for an already-loaded configuration.
+///
+/// # Arguments
+///
+/// * `db_path` - Path to the SQLite database to initialize
+/// * `source` - Source used to test-ping the Minecraft server
+pub async fn run_checks<S: StatusSource>(db_path: &str, source: &S) -> SelfCheckReport {
+    let mut report = SelfCheckReport::default();
+
+    report.checks.push(CheckResult {
+        name: "Database",
+        result: database::init_db(db_path)
+            .await
+            .map(|_| format!("initialized at {}", db_path)),
+    });
+
+    report.checks.push(CheckResult {
+        name: "Minecraft server",
+        result: source.fetch_status().await.map(|status| {
+            format!(
+                "reachable ({}, {}/{} players)",
+                status.version.name, status.players.online, status.players.max
+            )
+        }),
+    });
+
+    report
+}
+
+/// Run the full self-test, print the report, and return the process exit code.
+pub async fn run() -> i32 {
+    let report = match Config::load() {
+        Ok(config) => {
+            let source = PingStatusSource::new(config.mc_server_address.clone())
+                .with_proxy(config.mc_socks5_proxy.clone())
+                .with_sample_limit(config.max_player_sample)
+                .with_timeout(config.ping_timeout);
+            let mut report = run_checks(&config.db_path, &source).await;
+            report.checks.insert(0, CheckResult {
+                name: "Configuration",
+                result: Ok("loaded".to_string()),
+            });
+            report
+        }
+        Err(e) => SelfCheckReport {
+            checks: vec![CheckResult { name: "Configuration", result: Err(e) }],
+        },
+    };
+
+    println!("{}", report.render());
+    report.exit_code()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::OxideVaultError;
+    use crate::mc_server::ServerStatus;
+    use tempfile::TempDir;
+
+    /// Status source returning a fixed result.
+    struct MockStatusSource {
+        online: bool,
+    }
+
+    impl StatusSource for MockStatusSource {
+        async fn fetch_status(&self) -> Result<ServerStatus> {
+            if self.online {
+                Ok(ServerStatus::builder().players(3, 20).build())
+            } else {
+                Err(OxideVaultError::ServerProtocol("Connection failed".to_string()))
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_checks_all_pass() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+
+        let report = run_checks(db_path.to_str().unwrap(), &MockStatusSource { online: true }).await;
+
+        assert!(report.passed());
+        assert_eq!(report.exit_code(), 0);
+        assert_eq!(report.checks.len(), 2);
+        assert!(report.render().contains("✅ Minecraft server: reachable (1.21, 3/20 players)"));
+        assert!(report.render().ends_with("Self-test passed."));
+    }
+
+    #[tokio::test]
+    async fn test_run_checks_server_offline() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+
+        let report = run_checks(db_path.to_str().unwrap(), &MockStatusSource { online: false }).await;
+
+        assert!(!report.passed());
+        assert!(report.checks[0].result.is_ok());
+        assert_eq!(report.exit_code(), OxideVaultError::ServerProtocol(String::new()).exit_code());
+        assert!(report.render().contains("❌ Minecraft server"));
+    }
+
+    #[tokio::test]
+    async fn test_run_checks_reports_first_failure() {
+        // A database path nested under a regular file can't be created
+        let temp_dir = TempDir::new().unwrap();
+        let blocker = temp_dir.path().join("not_a_dir");
+        std::fs::write(&blocker, b"").unwrap();
+        let db_path = blocker.join("test.db");
+
+        let report = run_checks(db_path.to_str().unwrap(), &MockStatusSource { online: false }).await;
+
+        assert!(report.checks[0].result.is_err());
+        assert!(report.checks[1].result.is_err());
+        assert_eq!(report.exit_code(), OxideVaultError::Io(std::io::Error::other("")).exit_code());
+    }
+}
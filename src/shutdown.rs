@@ -0,0 +1,118 @@
+//! Graceful shutdown support.
+//!
+//! Waits for Ctrl+C or SIGTERM (sent by `docker stop`) and tracks commands
+//! still running, so the bot can let them finish before exiting.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::sync::Notify;
+
+/// How long shutdown waits for in-flight commands and background tasks.
+pub const SHUTDOWN_GRACE_PERIOD: Duration = Duration::from_secs(30);
+
+/// Wait until the process is asked to stop (Ctrl+C, or SIGTERM on Unix).
+pub async fn wait_for_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::warn!(error = %e, "Failed to listen for Ctrl+C");
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to listen for SIGTERM");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+}
+
+/// Count of commands currently running.
+#[derive(Debug, Default)]
+pub struct InFlight {
+    count: AtomicUsize,
+    idle: Notify,
+}
+
+impl InFlight {
+    /// Record that a command started.
+    pub fn start(&self) {
+        self.count.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// Record that a command finished.
+    pub fn finish(&self) {
+        let previous = self
+            .count
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |count| count.checked_sub(1))
+            .unwrap_or(0);
+        if previous <= 1 {
+            self.idle.notify_waiters();
+        }
+    }
+
+    /// Number of commands currently running.
+    pub fn count(&self) -> usize {
+        self.count.load(Ordering::SeqCst)
+    }
+
+    /// Wait until no commands are running, for at most `timeout`.
+    ///
+    /// Returns whether all commands finished in time.
+    pub async fn wait_idle(&self, timeout: Duration) -> bool {
+        tokio::time::timeout(timeout, async {
+            loop {
+                let idle = self.idle.notified();
+                if self.count() == 0 {
+                    return;
+                }
+                idle.await;
+            }
+        })
+        .await
+        .is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_in_flight_wait_idle() {
+        let in_flight = Arc::new(InFlight::default());
+        assert!(in_flight.wait_idle(Duration::from_millis(10)).await);
+
+        in_flight.start();
+        in_flight.start();
+        assert!(!in_flight.wait_idle(Duration::from_millis(10)).await);
+
+        let finisher = in_flight.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            finisher.finish();
+            finisher.finish();
+        });
+        assert!(in_flight.wait_idle(Duration::from_secs(5)).await);
+        assert_eq!(in_flight.count(), 0);
+
+        // Unbalanced finishes never underflow
+        in_flight.finish();
+        assert_eq!(in_flight.count(), 0);
+    }
+}
@@ -0,0 +1,184 @@
+//! Background server status polling.
+//!
+//! Periodically pings the configured Minecraft server and records each
+//! successful status to the status history, so player counts can be graphed
+//! even when nobody runs `/online`.
+
+use crate::commands::online::record_status;
+use crate::database::{PlayerRepository, StatusHistoryRepository};
+use crate::mc_server::StatusSource;
+use crate::types::LastKnownStatuses;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+
+/// Where the poller records what it sees.
+pub struct PollTargets {
+    pub history: StatusHistoryRepository,
+    pub players: PlayerRepository,
+    pub last_known: Arc<LastKnownStatuses>,
+}
+
+/// Ping the server at `address` once and record the result.
+///
+/// `address` is the key in the history and `last_known`. Failed pings are
+/// logged and otherwise skipped, leaving a gap in the history. Returns whether
+/// the ping succeeded.
+pub async fn poll_once<S: StatusSource>(source: &S, address: &str, targets: &PollTargets) -> bool {
+    match source.fetch_status().await {
+        Ok(status) => {
+            record_status(&status, Some((&targets.history, address)), Some(&targets.players)).await;
+            targets
+                .last_known
+                .write()
+                .await
+                .insert(address.to_string(), (Instant::now(), status));
+            true
+        }
+        Err(e) => {
+            tracing::warn!(server = %address, error = %e, "Background status poll failed");
+            false
+        }
+    }
+}
+
+/// Spawn a task polling a server every `interval` until `shutdown` becomes
+/// `true` (or its sender is dropped).
+///
+/// `source_for` is called before each poll and returns the address to poll
+/// and a status source for it, so a reloaded configuration takes effect on
+/// the next poll. The first poll happens immediately.
+pub fn spawn_status_poller<F, S>(
+    source_for: F,
+    interval: Duration,
+    targets: PollTargets,
+    mut shutdown: watch::Receiver<bool>,
+) -> JoinHandle<()>
+where
+    F: Fn() -> (String, S) + Send + 'static,
+    S: StatusSource + Send + Sync + 'static,
+{
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                _ = ticker.tick() => {
+                    let (address, source) = source_for();
+                    poll_once(&source, &address, &targets).await;
+                }
+                changed = shutdown.changed() => {
+                    if changed.is_err() || *shutdown.borrow() {
+                        break;
+                    }
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{OxideVaultError, Result};
+    use crate::mc_server::ServerStatus;
+
+    /// Status source returning a fixed status, or an error when `None`.
+    struct MockStatusSource {
+        status: Option<ServerStatus>,
+    }
+
+    impl StatusSource for MockStatusSource {
+        async fn fetch_status(&self) -> Result<ServerStatus> {
+            self.status
+                .clone()
+                .ok_or_else(|| OxideVaultError::ServerProtocol("Connection failed".to_string()))
+        }
+    }
+
+    async fn setup_targets(temp_dir: &tempfile::TempDir) -> PollTargets {
+        let db_path = temp_dir.path().join("test.db").to_str().unwrap().to_string();
+        crate::database::init_db(&db_path).await.unwrap();
+        PollTargets {
+            history: StatusHistoryRepository::new(db_path.clone()),
+            players: PlayerRepository::new(db_path),
+            last_known: Arc::new(LastKnownStatuses::default()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_poll_once_records_status() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let targets = setup_targets(&temp_dir).await;
+
+        let up = MockStatusSource { status: Some(ServerStatus::builder().players(6, 20).build()) };
+        assert!(poll_once(&up, "mc.example.com", &targets).await);
+
+        let down = MockStatusSource { status: None };
+        assert!(!poll_once(&down, "mc.example.com", &targets).await);
+
+        let samples = targets.history.get_count_history("mc.example.com", 0).await.unwrap();
+        assert_eq!(samples.len(), 1);
+        assert_eq!((samples[0].online, samples[0].max), (6, 20));
+        assert!(targets.last_known.read().await.contains_key("mc.example.com"));
+    }
+
+    #[tokio::test]
+    async fn test_spawn_status_poller_stops_on_shutdown() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let targets = setup_targets(&temp_dir).await;
+        let history = StatusHistoryRepository::new(temp_dir.path().join("test.db").to_str().unwrap().to_string());
+
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let source_for = || {
+            let source = MockStatusSource { status: Some(ServerStatus::builder().players(1, 20).build()) };
+            ("mc.example.com".to_string(), source)
+        };
+        let handle = spawn_status_poller(source_for, Duration::from_secs(3600), targets, shutdown_rx);
+
+        // The first poll runs right away
+        for _ in 0..100 {
+            if !history.get_count_history("mc.example.com", 0).await.unwrap().is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(history.get_count_history("mc.example.com", 0).await.unwrap().len(), 1);
+
+        shutdown_tx.send(true).unwrap();
+        tokio::time::timeout(Duration::from_secs(5), handle).await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_spawn_status_poller_follows_address_changes() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let targets = setup_targets(&temp_dir).await;
+        let history = StatusHistoryRepository::new(temp_dir.path().join("test.db").to_str().unwrap().to_string());
+
+        // Stands in for the live settings swapped by a reload
+        let address = Arc::new(std::sync::Mutex::new("old.example.com".to_string()));
+        let current = address.clone();
+        let source_for = move || {
+            let source = MockStatusSource { status: Some(ServerStatus::builder().players(1, 20).build()) };
+            (current.lock().unwrap().clone(), source)
+        };
+
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let handle = spawn_status_poller(source_for, Duration::from_millis(20), targets, shutdown_rx);
+
+        *address.lock().unwrap() = "new.example.com".to_string();
+        for _ in 0..100 {
+            if !history.get_count_history("new.example.com", 0).await.unwrap().is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert!(!history.get_count_history("new.example.com", 0).await.unwrap().is_empty());
+
+        shutdown_tx.send(true).unwrap();
+        tokio::time::timeout(Duration::from_secs(5), handle).await.unwrap().unwrap();
+    }
+}
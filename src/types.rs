@@ -2,38 +2,247 @@
 //!
 //! This module contains shared types used throughout the application.
 
-use crate::database::PlayerRepository;
-use std::sync::Arc;
+use crate::config::Config;
+use crate::database::{GuildRepository, PlayerRepository, StatusHistoryRepository};
+use crate::mojang::{MojangCache, MojangClient};
+use crate::mc_server::{PingStatusSource, ServerStatus, Socks5Proxy, DEFAULT_RETRY_BASE_DELAY};
+use crate::pl3xmap::RadiusLimits;
+use crate::shutdown::InFlight;
+use crate::utils::Cooldowns;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, PoisonError};
 use tokio::sync::RwLock;
 use std::collections::HashMap;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
-/// Bot application data shared across all commands.
+/// Last successful status of each pinged server, keyed by address.
+pub type LastKnownStatuses = RwLock<HashMap<String, (Instant, ServerStatus)>>;
+
+/// Settings that take effect immediately when the configuration is reloaded.
 ///
-/// This data is accessible in all command handlers through the context.
-pub struct Data {
-    /// Path to the SQLite database file
-    pub db_path: String,
-    /// HTTP client for making API requests
-    pub http_client: reqwest::Client,
+/// Everything else in [`Data`] is fixed at startup and needs a restart to change.
+#[derive(Debug, Clone)]
+pub struct LiveSettings {
     /// Minecraft server address to query
     pub mc_server_address: String,
+    /// Named Minecraft server addresses, keyed by lowercase name
+    pub mc_servers: HashMap<String, String>,
     /// Backup folder path
     pub backup_folder: String,
-    /// Rate limiter for backup command: tracks last backup time per user
-    pub last_backup_time: Arc<RwLock<HashMap<u64, Instant>>>,
-    /// Global rate limiter: tracks last backup time (any user)
-    pub last_global_backup_time: Arc<RwLock<Option<Instant>>>,
     /// Folder where downloadable backups are published (served by reverse proxy)
     pub backup_publish_root: String,
     /// Public base URL where published backups are accessible
     pub backup_public_base_url: String,
+    /// Minimum time between backup publishes by anyone
+    pub backup_global_cooldown: Duration,
+    /// Minimum time between backup publishes by the same user
+    pub backup_user_cooldown: Duration,
+    /// How long published backup links are kept before being removed
+    pub backup_link_ttl: Duration,
+    /// File extensions (without leading dot, lowercase) considered backups
+    pub backup_extensions: Vec<String>,
+    /// Limits on the radius of shapes drawn with `/draw`
+    pub draw_limits: RadiusLimits,
+    /// How long `/online` serves a remembered status instead of pinging again
+    pub status_cache_ttl: Duration,
+}
+
+impl LiveSettings {
+    /// Take the live settings from a loaded configuration.
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            mc_server_address: config.mc_server_address.clone(),
+            mc_servers: config.mc_servers.clone(),
+            backup_folder: config.backup_folder.clone(),
+            backup_publish_root: config.backup_publish_root.clone(),
+            backup_public_base_url: config.backup_public_base_url.clone(),
+            backup_global_cooldown: config.backup_global_cooldown,
+            backup_user_cooldown: config.backup_user_cooldown,
+            backup_link_ttl: config.backup_link_ttl,
+            backup_extensions: config.backup_extensions.clone(),
+            draw_limits: config.draw_limits.clone(),
+            status_cache_ttl: config.status_cache_ttl,
+        }
+    }
+}
+
+/// Shared, swappable [`LiveSettings`].
+///
+/// Readers get a snapshot that stays consistent for as long as they hold it,
+/// even if the settings are replaced meanwhile.
+#[derive(Debug)]
+pub struct LiveSettingsHandle {
+    current: std::sync::RwLock<Arc<LiveSettings>>,
+}
+
+impl LiveSettingsHandle {
+    /// Wrap the initial settings.
+    pub fn new(settings: LiveSettings) -> Self {
+        Self {
+            current: std::sync::RwLock::new(Arc::new(settings)),
+        }
+    }
+
+    /// Get a snapshot of the current settings.
+    pub fn current(&self) -> Arc<LiveSettings> {
+        self.current.read().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+
+    /// Replace the settings seen by later calls to [`LiveSettingsHandle::current`].
+    pub fn replace(&self, settings: LiveSettings) {
+        *self.current.write().unwrap_or_else(PoisonError::into_inner) = Arc::new(settings);
+    }
+}
+
+/// Bot application data shared across all commands.
+///
+/// This data is accessible in all command handlers through the context.
+pub struct Data {
+    /// Settings that can be reloaded without restarting
+    pub live_settings: Arc<LiveSettingsHandle>,
+    /// Optional SOCKS5 proxy used for server pings
+    pub mc_socks5_proxy: Option<Socks5Proxy>,
+    /// Whether full player lists are fetched over the UDP Query protocol
+    pub mc_query_enabled: bool,
+    /// Per-user and global cooldowns between backup publishes
+    pub backup_cooldowns: Arc<Cooldowns>,
+    /// Per-user cooldowns between `/uuid` lookups
+    pub uuid_cooldowns: Arc<Cooldowns>,
+    /// Discord user IDs granted admin access in addition to ADMINISTRATOR holders
+    pub admin_user_ids: Vec<u64>,
+    /// Maximum number of player sample entries kept from a server status
+    pub max_player_sample: usize,
+    /// Optional JSON Lines file that every server ping is appended to
+    pub ping_log_path: Option<String>,
+    /// Timeout for server pings
+    pub ping_timeout: Duration,
+    /// Number of retries for pings failing with transient errors
+    pub ping_retries: u32,
+    /// Client for the Mojang API, using the configured base URLs
+    pub mojang_client: MojangClient,
+    /// Cache of recent Mojang username lookups
+    pub mojang_cache: Arc<MojangCache>,
+    /// Player repository sharing one connection pool across commands
+    pub players: PlayerRepository,
+    /// Guild settings repository on the same connection pool as `players`
+    pub guilds: GuildRepository,
+    /// Status history repository on the same connection pool as `players`
+    pub status_history: StatusHistoryRepository,
+    /// Optional Pl3xmap marker file that `/draw` appends markers to and
+    /// `/layer` collects them from
+    pub pl3xmap_markers_path: Option<PathBuf>,
+    /// Optional vanilla stats directory (`world/stats`) read by `/importstats`
+    pub minecraft_stats_dir: Option<PathBuf>,
+    /// Commands currently running, waited on during shutdown
+    pub in_flight: Arc<InFlight>,
+    /// Last successful status per server, shown by `/online` while a server is down
+    pub last_known_statuses: Arc<LastKnownStatuses>,
 }
 
 impl Data {
-    /// Create a new player repository for database operations.
+    /// Get the player repository for database operations.
+    ///
+    /// Clones share the same connection pool.
     pub fn player_repository(&self) -> PlayerRepository {
-        PlayerRepository::new(self.db_path.clone())
+        self.players.clone()
+    }
+
+    /// Get a snapshot of the settings that can be reloaded at runtime.
+    pub fn live(&self) -> Arc<LiveSettings> {
+        self.live_settings.current()
+    }
+
+    /// Get the guild settings repository for database operations.
+    ///
+    /// Clones share the player repository's connection pool.
+    pub fn guild_repository(&self) -> GuildRepository {
+        self.guilds.clone()
+    }
+
+    /// Get the status history repository for database operations.
+    ///
+    /// Clones share the player repository's connection pool.
+    pub fn status_history_repository(&self) -> StatusHistoryRepository {
+        self.status_history.clone()
+    }
+
+    /// Get the Minecraft server address for a guild.
+    ///
+    /// Prefers the guild's configured server address and falls back to the global
+    /// `MC_SERVER_ADDRESS` when none is set (or outside of a guild).
+    pub async fn server_address_for(&self, guild_id: Option<u64>) -> String {
+        if let Some(guild_id) = guild_id {
+            if let Ok(Some(settings)) = self.guild_repository().get_guild_settings(guild_id).await {
+                if let Some(address) = settings.server_address {
+                    return address;
+                }
+            }
+        }
+        self.live().mc_server_address.clone()
+    }
+
+    /// Get the backup folder used by a guild.
+    ///
+    /// A guild's configured folder is resolved inside the global `BACKUP_FOLDER`,
+    /// which is used as-is when none is set (or outside of a guild).
+    pub async fn backup_folder_for(&self, guild_id: Option<u64>) -> String {
+        let backup_folder = self.live().backup_folder.clone();
+        if let Some(guild_id) = guild_id {
+            if let Ok(Some(settings)) = self.guild_repository().get_guild_settings(guild_id).await {
+                if let Some(folder) = settings.backup_folder {
+                    return Path::new(&backup_folder).join(folder).to_string_lossy().into_owned();
+                }
+            }
+        }
+        backup_folder
+    }
+
+    /// Look up a server configured in `MC_SERVERS` by name (case-insensitive).
+    pub fn named_server_address(&self, name: &str) -> Option<String> {
+        self.live().mc_servers.get(&name.trim().to_lowercase()).cloned()
+    }
+
+    /// Create a status source for the Minecraft server used by a guild.
+    pub async fn status_source(&self, guild_id: Option<u64>) -> PingStatusSource {
+        self.status_source_at(self.server_address_for(guild_id).await)
+    }
+
+    /// Create a status source for the given server address.
+    pub fn status_source_at(&self, address: String) -> PingStatusSource {
+        PingStatusSource::new(address)
+            .with_proxy(self.mc_socks5_proxy.clone())
+            .with_query(self.mc_query_enabled)
+            .with_sample_limit(self.max_player_sample)
+            .with_ping_log(self.ping_log_path.as_ref().map(PathBuf::from))
+            .with_timeout(self.ping_timeout)
+            .with_retries(self.ping_retries, DEFAULT_RETRY_BASE_DELAY)
+    }
+}
+
+/// Reply produced by a command's core logic.
+///
+/// Keeping command logic free of Discord I/O lets it be tested without a live
+/// context; the `#[poise::command]` wrapper only sends the message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResponseMessage {
+    /// The command completed successfully
+    Success(String),
+    /// The command could not complete; the text explains why
+    Failure(String),
+}
+
+impl ResponseMessage {
+    /// Text content of the reply.
+    pub fn content(&self) -> &str {
+        match self {
+            ResponseMessage::Success(text) | ResponseMessage::Failure(text) => text,
+        }
+    }
+
+    /// Send the reply in the given command context.
+    pub async fn send(&self, context: Context<'_>) -> Result<(), Error> {
+        context.say(self.content()).await?;
+        Ok(())
     }
 }
 
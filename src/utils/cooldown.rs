@@ -0,0 +1,126 @@
+//! Per-user and global command cooldowns.
+//!
+//! A [`Cooldowns`] remembers when each key (typically a Discord user ID) last
+//! used a command and when anyone last did. A use is allowed only once both
+//! cooldowns have elapsed; otherwise the caller is told how long to wait.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+#[derive(Debug)]
+struct State<K> {
+    last_use: HashMap<K, Instant>,
+    last_global_use: Option<Instant>,
+}
+
+/// Cooldown tracker for one command, keyed by user.
+#[derive(Debug)]
+pub struct Cooldowns<K = u64> {
+    state: Mutex<State<K>>,
+}
+
+impl<K: Eq + Hash> Cooldowns<K> {
+    /// Create a tracker with no recorded uses.
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(State {
+                last_use: HashMap::new(),
+                last_global_use: None,
+            }),
+        }
+    }
+
+    /// Record a use by `key` if neither cooldown is active.
+    ///
+    /// Returns `Err(remaining)` with the time until both the `per_user`
+    /// cooldown for `key` and the `global` cooldown have elapsed; nothing is
+    /// recorded in that case.
+    pub fn check_and_update(&self, key: K, per_user: Duration, global: Duration) -> Result<(), Duration> {
+        self.check_and_update_at(key, per_user, global, Instant::now())
+    }
+
+    /// Record a use by `key` at the given instant if neither cooldown is active.
+    pub fn check_and_update_at(
+        &self,
+        key: K,
+        per_user: Duration,
+        global: Duration,
+        now: Instant,
+    ) -> Result<(), Duration> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+
+        let remaining = |last: Option<&Instant>, cooldown: Duration| {
+            last.map_or(Duration::ZERO, |last| cooldown.saturating_sub(now.saturating_duration_since(*last)))
+        };
+        let remaining = remaining(state.last_use.get(&key), per_user)
+            .max(remaining(state.last_global_use.as_ref(), global));
+
+        if !remaining.is_zero() {
+            return Err(remaining);
+        }
+
+        state.last_use.insert(key, now);
+        state.last_global_use = Some(now);
+        Ok(())
+    }
+}
+
+impl<K: Eq + Hash> Default for Cooldowns<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINUTE: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn test_per_user_cooldown() {
+        let cooldowns = Cooldowns::new();
+        let now = Instant::now();
+
+        assert!(cooldowns.check_and_update_at(1, MINUTE, Duration::ZERO, now).is_ok());
+        assert_eq!(
+            cooldowns.check_and_update_at(1, MINUTE, Duration::ZERO, now + Duration::from_secs(20)),
+            Err(Duration::from_secs(40))
+        );
+
+        // Other users are unaffected without a global cooldown
+        assert!(cooldowns.check_and_update_at(2, MINUTE, Duration::ZERO, now).is_ok());
+
+        assert!(cooldowns.check_and_update_at(1, MINUTE, Duration::ZERO, now + MINUTE).is_ok());
+    }
+
+    #[test]
+    fn test_global_cooldown() {
+        let cooldowns = Cooldowns::new();
+        let now = Instant::now();
+
+        assert!(cooldowns.check_and_update_at(1, Duration::ZERO, MINUTE, now).is_ok());
+        assert_eq!(
+            cooldowns.check_and_update_at(2, Duration::ZERO, MINUTE, now + Duration::from_secs(45)),
+            Err(Duration::from_secs(15))
+        );
+        assert!(cooldowns.check_and_update_at(2, Duration::ZERO, MINUTE, now + MINUTE).is_ok());
+    }
+
+    #[test]
+    fn test_reports_longest_remaining_and_skips_update() {
+        let cooldowns = Cooldowns::new();
+        let now = Instant::now();
+
+        assert!(cooldowns.check_and_update_at(1, 2 * MINUTE, MINUTE, now).is_ok());
+        assert_eq!(
+            cooldowns.check_and_update_at(1, 2 * MINUTE, MINUTE, now + Duration::from_secs(30)),
+            Err(Duration::from_secs(90))
+        );
+
+        // The rejected attempt did not restart the global cooldown
+        assert!(cooldowns.check_and_update_at(2, 2 * MINUTE, MINUTE, now + MINUTE).is_ok());
+    }
+}
@@ -0,0 +1,202 @@
+//! Human-readable duration formatting.
+
+use std::time::Duration;
+
+/// Smallest unit shown by [`format_duration`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Granularity {
+    Seconds,
+    Minutes,
+    Hours,
+    Days,
+}
+
+/// Units from largest to smallest, with their length in seconds.
+const UNITS: [(Granularity, u64, &str); 4] = [
+    (Granularity::Days, 86_400, "day"),
+    (Granularity::Hours, 3_600, "hour"),
+    (Granularity::Minutes, 60, "minute"),
+    (Granularity::Seconds, 1, "second"),
+];
+
+/// Format a duration as human-readable text, e.g. "2 hours, 3 minutes".
+///
+/// Components smaller than `granularity` are truncated and zero components are
+/// omitted. A duration shorter than the smallest unit is shown as zero of that
+/// unit (e.g. "0 minutes").
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use oxidevault::utils::{format_duration, Granularity};
+///
+/// let d = Duration::from_secs(2 * 3600 + 3 * 60 + 15);
+/// assert_eq!(format_duration(d, Granularity::Minutes), "2 hours, 3 minutes");
+/// assert_eq!(format_duration(Duration::from_secs(86_400), Granularity::Seconds), "1 day");
+/// ```
+pub fn format_duration(d: Duration, granularity: Granularity) -> String {
+    let mut remaining = d.as_secs();
+    let mut parts = Vec::new();
+
+    for (unit, seconds, name) in UNITS {
+        if unit < granularity {
+            break;
+        }
+
+        let count = remaining / seconds;
+        remaining %= seconds;
+
+        if count > 0 {
+            parts.push(pluralize(count, name));
+        }
+    }
+
+    if parts.is_empty() {
+        let name = UNITS
+            .iter()
+            .find(|(unit, _, _)| *unit == granularity)
+            .map_or("second", |(_, _, name)| *name);
+        return pluralize(0, name);
+    }
+
+    parts.join(", ")
+}
+
+/// Format how long ago something happened, e.g. "2 days ago".
+///
+/// Only the largest unit is shown; anything under a minute is "just now".
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use oxidevault::utils::format_relative;
+///
+/// assert_eq!(format_relative(Duration::from_secs(2 * 86_400 + 3_600)), "2 days ago");
+/// assert_eq!(format_relative(Duration::from_secs(30)), "just now");
+/// ```
+pub fn format_relative(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+
+    UNITS
+        .iter()
+        .filter(|(unit, _, _)| *unit >= Granularity::Minutes)
+        .find(|(_, seconds, _)| secs >= *seconds)
+        .map_or_else(
+            || "just now".to_string(),
+            |(_, seconds, name)| format!("{} ago", pluralize(secs / seconds, name)),
+        )
+}
+
+/// Format a duration compactly as days, hours, and minutes, e.g. "1d 4h 30m".
+///
+/// Leading zero components are omitted; seconds are truncated.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use oxidevault::utils::format_compact;
+///
+/// assert_eq!(format_compact(Duration::from_secs(86_400 + 4 * 3_600 + 30 * 60)), "1d 4h 30m");
+/// assert_eq!(format_compact(Duration::from_secs(95 * 60)), "1h 35m");
+/// ```
+pub fn format_compact(d: Duration) -> String {
+    let minutes = d.as_secs() / 60;
+    let (days, hours, minutes) = (minutes / 1_440, minutes / 60 % 24, minutes % 60);
+
+    if days > 0 {
+        format!("{}d {}h {}m", days, hours, minutes)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+/// Format a count with its unit name, pluralized when the count isn't 1.
+fn pluralize(count: u64, name: &str) -> String {
+    format!("{} {}{}", count, name, if count == 1 { "" } else { "s" })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(s: u64) -> Duration {
+        Duration::from_secs(s)
+    }
+
+    #[test]
+    fn test_format_duration_seconds() {
+        assert_eq!(format_duration(secs(0), Granularity::Seconds), "0 seconds");
+        assert_eq!(format_duration(secs(1), Granularity::Seconds), "1 second");
+        assert_eq!(format_duration(secs(59), Granularity::Seconds), "59 seconds");
+        assert_eq!(format_duration(secs(61), Granularity::Seconds), "1 minute, 1 second");
+        assert_eq!(format_duration(Duration::from_millis(1_999), Granularity::Seconds), "1 second");
+    }
+
+    #[test]
+    fn test_format_duration_boundaries() {
+        assert_eq!(format_duration(secs(60), Granularity::Seconds), "1 minute");
+        assert_eq!(format_duration(secs(3_599), Granularity::Minutes), "59 minutes");
+        assert_eq!(format_duration(secs(3_600), Granularity::Minutes), "1 hour");
+        assert_eq!(format_duration(secs(86_399), Granularity::Hours), "23 hours");
+        assert_eq!(format_duration(secs(86_400), Granularity::Hours), "1 day");
+        assert_eq!(
+            format_duration(secs(5 * 86_400 + 2 * 3_600), Granularity::Hours),
+            "5 days, 2 hours"
+        );
+    }
+
+    #[test]
+    fn test_format_duration_omits_zero_components() {
+        assert_eq!(format_duration(secs(86_400 + 5), Granularity::Seconds), "1 day, 5 seconds");
+        assert_eq!(
+            format_duration(secs(2 * 3_600 + 3 * 60), Granularity::Minutes),
+            "2 hours, 3 minutes"
+        );
+        assert_eq!(format_duration(secs(2 * 3_600 + 59), Granularity::Minutes), "2 hours");
+    }
+
+    #[test]
+    fn test_format_duration_below_granularity() {
+        assert_eq!(format_duration(secs(59), Granularity::Minutes), "0 minutes");
+        assert_eq!(format_duration(secs(3_599), Granularity::Hours), "0 hours");
+        assert_eq!(format_duration(secs(86_399), Granularity::Days), "0 days");
+    }
+
+    #[test]
+    fn test_format_relative() {
+        assert_eq!(format_relative(secs(0)), "just now");
+        assert_eq!(format_relative(secs(59)), "just now");
+        assert_eq!(format_relative(secs(60)), "1 minute ago");
+        assert_eq!(format_relative(secs(3_599)), "59 minutes ago");
+        assert_eq!(format_relative(secs(3_600)), "1 hour ago");
+        assert_eq!(format_relative(secs(86_399)), "23 hours ago");
+        assert_eq!(format_relative(secs(2 * 86_400 + 5 * 3_600)), "2 days ago");
+    }
+
+    #[test]
+    fn test_format_compact() {
+        assert_eq!(format_compact(secs(0)), "0m");
+        assert_eq!(format_compact(secs(59)), "0m");
+        assert_eq!(format_compact(secs(60)), "1m");
+        assert_eq!(format_compact(secs(3_600)), "1h 0m");
+        assert_eq!(format_compact(secs(86_400 + 5 * 60)), "1d 0h 5m");
+        assert_eq!(format_compact(secs(12 * 86_400 + 23 * 3_600 + 59 * 60 + 59)), "12d 23h 59m");
+    }
+
+    #[test]
+    fn test_format_duration_pluralization() {
+        assert_eq!(
+            format_duration(secs(86_400 + 3_600 + 60 + 1), Granularity::Seconds),
+            "1 day, 1 hour, 1 minute, 1 second"
+        );
+        assert_eq!(
+            format_duration(secs(2 * 86_400 + 2 * 3_600 + 2 * 60 + 2), Granularity::Seconds),
+            "2 days, 2 hours, 2 minutes, 2 seconds"
+        );
+    }
+}
@@ -1,3 +1,8 @@
 //! Utility modules for common operations.
 
+pub mod cooldown;
+pub mod duration;
 pub mod validation;
+
+pub use cooldown::Cooldowns;
+pub use duration::{format_compact, format_duration, format_relative, Granularity};
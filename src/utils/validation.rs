@@ -50,6 +50,39 @@ pub fn validate_minecraft_username(username: &str) -> Result<()> {
     Ok(())
 }
 
+/// Validate a folder path that is resolved inside another folder.
+///
+/// The path must be relative and made only of normal components, so it can't
+/// escape the folder it's joined to (no `..`, root, or drive prefixes).
+///
+/// # Examples
+///
+/// ```
+/// use oxidevault::utils::validation::validate_subfolder;
+///
+/// assert!(validate_subfolder("survival").is_ok());
+/// assert!(validate_subfolder("servers/survival").is_ok());
+/// assert!(validate_subfolder("../etc").is_err());
+/// assert!(validate_subfolder("/etc").is_err());
+/// ```
+pub fn validate_subfolder(path: &str) -> Result<()> {
+    let components: Vec<_> = std::path::Path::new(path).components().collect();
+
+    if components.is_empty() {
+        return Err(OxideVaultError::Validation(
+            "Folder cannot be empty".to_string()
+        ));
+    }
+
+    if !components.iter().all(|c| matches!(c, std::path::Component::Normal(_))) {
+        return Err(OxideVaultError::Validation(
+            format!("Folder must be a relative path without '..': '{}'", path)
+        ));
+    }
+
+    Ok(())
+}
+
 /// Format a 32-character UUID string into the standard 8-4-4-4-12 format.
 ///
 /// # Arguments
@@ -80,6 +113,48 @@ pub fn format_uuid(uuid: &str) -> Option<String> {
     ))
 }
 
+/// Normalize a UUID to its canonical undashed, lowercase form.
+///
+/// Accepts both the 32-character undashed form and the dashed 8-4-4-4-12 form.
+///
+/// # Arguments
+///
+/// * `uuid` - The UUID to normalize
+///
+/// # Returns
+///
+/// Returns the normalized UUID, or `None` if the input is not a valid UUID.
+///
+/// # Examples
+///
+/// ```
+/// use oxidevault::utils::validation::normalize_uuid;
+///
+/// let expected = Some("069a79f444e94726a5befca90e38aaf5".to_string());
+/// assert_eq!(normalize_uuid("069a79f4-44e9-4726-a5be-fca90e38aaf5"), expected);
+/// assert_eq!(normalize_uuid("069A79F444E94726A5BEFCA90E38AAF5"), expected);
+/// assert_eq!(normalize_uuid("Notch"), None);
+/// ```
+pub fn normalize_uuid(uuid: &str) -> Option<String> {
+    let undashed = if uuid.len() == 36 {
+        let dashes_in_place = [8, 13, 18, 23]
+            .iter()
+            .all(|&i| uuid.as_bytes()[i] == b'-');
+        if !dashes_in_place {
+            return None;
+        }
+        uuid.replace('-', "")
+    } else {
+        uuid.to_string()
+    };
+
+    if undashed.len() != 32 || !undashed.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    Some(undashed.to_ascii_lowercase())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -100,6 +175,17 @@ mod tests {
         assert!(validate_minecraft_username("Player-123").is_err()); // dash
     }
 
+    #[test]
+    fn test_validate_subfolder() {
+        assert!(validate_subfolder("survival").is_ok());
+        assert!(validate_subfolder("a/b/c").is_ok());
+
+        assert!(validate_subfolder("").is_err());
+        assert!(validate_subfolder(".").is_err());
+        assert!(validate_subfolder("a/../../b").is_err());
+        assert!(validate_subfolder("/backups").is_err());
+    }
+
     #[test]
     fn test_format_uuid() {
         assert_eq!(
@@ -111,4 +197,19 @@ mod tests {
         assert_eq!(format_uuid(""), None);
         assert_eq!(format_uuid("069a79f444e94726a5befca90e38aaf5extra"), None);
     }
+
+    #[test]
+    fn test_normalize_uuid() {
+        let expected = Some("069a79f444e94726a5befca90e38aaf5".to_string());
+        assert_eq!(normalize_uuid("069a79f444e94726a5befca90e38aaf5"), expected);
+        assert_eq!(normalize_uuid("069a79f4-44e9-4726-a5be-fca90e38aaf5"), expected);
+        assert_eq!(normalize_uuid("069A79F4-44E9-4726-A5BE-FCA90E38AAF5"), expected);
+
+        assert_eq!(normalize_uuid(""), None);
+        assert_eq!(normalize_uuid("Notch"), None);
+        assert_eq!(normalize_uuid("069a79f444e94726a5befca90e38aaf"), None); // 31 chars
+        assert_eq!(normalize_uuid("069a79f444e94726a5befca90e38aafz"), None); // non-hex
+        assert_eq!(normalize_uuid("069a79f444-e94726a5befca90e38aaf5"), None); // misplaced dash
+        assert_eq!(normalize_uuid("069a79f4-44e9-4726-a5be-fca90e38aaf"), None); // truncated
+    }
 }
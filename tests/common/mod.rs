@@ -0,0 +1,90 @@
+//! Test helpers shared by the integration tests.
+
+use oxidevault::mc_server::protocol::{read_packet, read_varint, send_packet, write_string, write_varint};
+use std::io::{self, Read};
+use std::net::{TcpListener, TcpStream};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// Packet ID of the handshake, status response and status request.
+const STATUS_PACKET_ID: i32 = 0x00;
+
+/// Packet ID of the ping request and pong response.
+const PING_PACKET_ID: i32 = 0x01;
+
+/// Handshake "next state" asking for the server status.
+const NEXT_STATE_STATUS: i32 = 1;
+
+/// A Minecraft server stand-in answering a single status request with a
+/// canned JSON status.
+///
+/// Listens on a random local port in a background thread. The thread checks
+/// the client speaks the status handshake, replies with the JSON, then echoes
+/// the ping used to measure latency.
+pub struct MockServer {
+    address: String,
+    handle: JoinHandle<io::Result<()>>,
+}
+
+impl MockServer {
+    /// Start a server replying with `status_json`.
+    pub fn start(status_json: &str) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind mock server");
+        let address = listener.local_addr().expect("mock server address").to_string();
+        let status_json = status_json.to_string();
+
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept()?;
+            stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+            serve_status(&mut stream, &status_json)
+        });
+
+        Self { address, handle }
+    }
+
+    /// `host:port` the server listens on.
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    /// Wait for the server thread, failing the test if the client didn't
+    /// speak the protocol as expected.
+    pub fn finish(self) {
+        self.handle
+            .join()
+            .expect("mock server thread panicked")
+            .expect("mock server I/O failed");
+    }
+}
+
+/// Answer one status exchange on `stream`.
+fn serve_status(stream: &mut TcpStream, status_json: &str) -> io::Result<()> {
+    let mut handshake = io::Cursor::new(read_packet(stream)?);
+    assert_eq!(read_varint(&mut handshake)?, STATUS_PACKET_ID, "handshake packet ID");
+    let _protocol = read_varint(&mut handshake)?;
+    let host_len = read_varint(&mut handshake)? as usize;
+    let mut host = vec![0u8; host_len];
+    handshake.read_exact(&mut host)?;
+    let mut port = [0u8; 2];
+    handshake.read_exact(&mut port)?;
+    assert_eq!(read_varint(&mut handshake)?, NEXT_STATE_STATUS, "handshake next state");
+
+    let mut request = io::Cursor::new(read_packet(stream)?);
+    assert_eq!(read_varint(&mut request)?, STATUS_PACKET_ID, "status request packet ID");
+
+    let mut response = Vec::new();
+    write_varint(&mut response, STATUS_PACKET_ID)?;
+    write_string(&mut response, status_json)?;
+    send_packet(stream, &response)?;
+
+    // Echo the latency ping back as the pong
+    let mut ping = io::Cursor::new(read_packet(stream)?);
+    assert_eq!(read_varint(&mut ping)?, PING_PACKET_ID, "ping packet ID");
+    let mut payload = [0u8; 8];
+    ping.read_exact(&mut payload)?;
+
+    let mut pong = Vec::new();
+    write_varint(&mut pong, PING_PACKET_ID)?;
+    pong.extend_from_slice(&payload);
+    send_packet(stream, &pong)
+}
@@ -0,0 +1,69 @@
+//! Server List Ping against a local mock server.
+
+mod common;
+
+use common::MockServer;
+use oxidevault::mc_server::{ping_server, ping_servers};
+
+#[test]
+fn test_ping_server_parses_status() {
+    let server = MockServer::start(
+        r#"{
+            "version": {"name": "Paper 1.21.1", "protocol": 767},
+            "players": {
+                "max": 50,
+                "online": 2,
+                "sample": [
+                    {"name": "Notch", "id": "069a79f4-44e9-4726-a5be-fca90e38aaf5"},
+                    {"name": "jeb_", "id": "853c80ef-3c37-49fd-aa49-938b674adae6"}
+                ]
+            },
+            "description": {"text": "An OxideVault test server"}
+        }"#,
+    );
+
+    let status = ping_server(server.address()).expect("ping the mock server");
+    server.finish();
+
+    assert_eq!(status.version.name, "Paper 1.21.1");
+    assert_eq!(status.version.protocol, 767);
+    assert_eq!(status.players.online, 2);
+    assert_eq!(status.players.max, 50);
+    let names: Vec<_> = status.players.sample.iter().map(|player| player.name.as_str()).collect();
+    assert_eq!(names, ["Notch", "jeb_"]);
+    assert_eq!(status.description.text(), "An OxideVault test server");
+    assert!(status.favicon.is_none());
+    assert!(status.latency_ms.is_some());
+}
+
+#[test]
+fn test_ping_server_plain_description() {
+    let server = MockServer::start(
+        r#"{"version":{"name":"1.8.9","protocol":47},"players":{"max":20,"online":0},"description":"A Minecraft Server"}"#,
+    );
+
+    let status = ping_server(server.address()).expect("ping the mock server");
+    server.finish();
+
+    assert_eq!(status.version.protocol, 47);
+    assert_eq!(status.players.online, 0);
+    assert!(status.players.sample.is_empty());
+    assert_eq!(status.description.text(), "A Minecraft Server");
+}
+
+#[tokio::test]
+async fn test_ping_servers_keeps_order() {
+    let server = MockServer::start(
+        r#"{"version":{"name":"1.21.1","protocol":767},"players":{"max":20,"online":3},"description":"Up"}"#,
+    );
+    let addresses = vec!["127.0.0.1:1".to_string(), server.address().to_string()];
+
+    let results = ping_servers(&addresses).await;
+    server.finish();
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].0, "127.0.0.1:1");
+    assert!(results[0].1.is_err());
+    assert_eq!(results[1].0, addresses[1]);
+    assert_eq!(results[1].1.as_ref().unwrap().players.online, 3);
+}